@@ -18,6 +18,8 @@ pub enum Route {
     QuizStart { workspace: String, task: String, student: String },
     #[route("/quiz/finish")]
     QuizFinish {},
+    #[route("/quiz/review/:workspace/:task/:student")]
+    QuizReview { workspace: String, task: String, student: String },
 
     #[route("/survey/details/:workspace/:task")]
     SurveyDetails { workspace: String, task: String },
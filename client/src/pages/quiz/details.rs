@@ -63,6 +63,22 @@ pub fn QuizDetails(
                             div { i { class: "bi bi-person-fill text-base-content/70" } }
                             div { class: "font-medium", "{details.read().student_name}" }
                         }
+                        if details.read().practice {
+                            li {
+                                class: "list-row py-1 text-accent",
+                                div { i { class: "bi bi-repeat" } }
+                                div { { t!("practice-mode") } }
+                            }
+                            if details.read().practice_attempts > 0 {
+                                li {
+                                    class: "list-row py-1",
+                                    div { i { class: "bi bi-check2-square text-base-content/70" } }
+                                    div {
+                                        { t!("practice-last-score", score = details.read().score, count = details.read().practice_attempts) }
+                                    }
+                                }
+                            }
+                        }
                         if details.read().grade > 0 {
                             li {
                                 class: format!("list-row py-1 {class}", class = match details.read().grade {
@@ -83,6 +99,16 @@ pub fn QuizDetails(
                     }
                     div {
                         class: "card-actions justify-end mt-6",
+                        if details.read().review >= QuizReviewMode::Categories {
+                            button {
+                                class: "btn btn-ghost",
+                                onclick: move |_| {
+                                    navigator.push(Route::QuizReview { workspace: workspace(), task: task(), student: student() });
+                                },
+                                i { class: "bi bi-clipboard-check" }
+                                { t!("attempt-review") }
+                            }
+                        }
                         if details.read().can_take {
                             button {
                                 class: "btn btn-primary",
@@ -90,9 +116,10 @@ pub fn QuizDetails(
                                     QUIZ.signal().set(QuizActivity::default());
                                     CURRENT.signal().set(0);
                                     TIMER.signal().set(0);
+                                    FEEDBACK.signal().set(Default::default());
                                     navigator.push(Route::QuizStart { workspace: workspace(), task: task(), student: student()});
                                 },
-                                if details.read().grade > 0 {
+                                if details.read().grade > 0 || details.read().practice_attempts > 0 {
                                     { t!("try-again") }
                                 } else {
                                     { t!("begin") }
@@ -41,6 +41,8 @@ pub fn QuizFinish() -> Element {
                 quiz: quiz_guard.quiz.clone(),
                 duration: quiz_guard.duration,
                 student: quiz_guard.student.clone(),
+                language: quiz_guard.language.clone(),
+                practice: quiz_guard.practice,
                 questions,
             },
             on_success = move || {
@@ -2,16 +2,20 @@ mod details;
 mod start;
 mod take;
 mod finish;
+mod review;
 
 pub use self::{
     details::*,
     start::*,
     take::*,
     finish::*,
+    review::*,
 };
 
 use crate::prelude::*;
+use ::std::collections::HashMap;
 
 static QUIZ: GlobalSignal<QuizActivity> = Signal::global(QuizActivity::default);
 static CURRENT: GlobalSignal<usize> = Signal::global(|| 0_usize);
-static TIMER: GlobalSignal<i64> = Signal::global(|| 0_i64);
\ No newline at end of file
+static TIMER: GlobalSignal<i64> = Signal::global(|| 0_i64);
+static FEEDBACK: GlobalSignal<HashMap<String, QuizActivityFeedback>> = Signal::global(HashMap::new);
\ No newline at end of file
@@ -0,0 +1,164 @@
+use super::*;
+use crate::{components::*, prelude::*, services::*, utils::*};
+
+#[component]
+pub fn QuizReview(
+    workspace: ReadSignal<String>,
+    task: ReadSignal<String>,
+    student: ReadSignal<String>,
+) -> Element {
+    let navigator = use_navigator();
+    let mut review = use_signal(|| Option::<QuizAttemptReview>::None);
+
+    use_hook(move || {
+        let language = {
+            let quiz_guard = QUIZ.read();
+            if quiz_guard.task == task() { quiz_guard.language.clone() } else { String::new() }
+        };
+        api_fetch!(
+            GET,
+            format!(
+                "/api/v1/activities/review/{workspace}/{task}/{student}?language={language}",
+                language = encode_query_value(language)
+            ),
+            on_success = move |body: QuizAttemptReview| review.set(Some(body)),
+            on_error = move |e: shared::common::Error| ErrorService::show(t!(e.to_string()))
+        )
+    });
+
+    let review_guard = review.read();
+    let Some(review) = review_guard.as_ref() else {
+        return rsx! { Loading {} };
+    };
+
+    rsx! {
+        div {
+            class: "flex-scrollable items-center gap-2 p-4",
+            div {
+                class: "card w-full h-auto max-w-2xl card-border shadow-lg bg-base-100",
+                div {
+                    class: "card-body",
+                    div {
+                        class: "card-title flex text-primary text-xl gap-4",
+                        i { class: "bi bi-clipboard-check" }
+                        { t!("attempt-review") }
+                    }
+                    div {
+                        class: "h-0.25 bg-base-300",
+                    }
+                    ul {
+                        class: "list w-full",
+                        li {
+                            class: "list-row py-1",
+                            div { i { class: "bi bi-anthropic text-base-content/70" } }
+                            div { "{review.quiz_name}" }
+                        }
+                        li {
+                            class: "list-row py-1",
+                            div { i { class: "bi bi-person-fill text-base-content/70" } }
+                            div { class: "font-medium", "{review.student_name}" }
+                        }
+                        li {
+                            class: format!("list-row py-1 {class}", class = match review.grade {
+                                5 => "text-success",
+                                4 => "text-info",
+                                3 => "text-warning",
+                                _ => "text-error",
+                            }),
+                            div { i { class: "bi bi-award-fill" } }
+                            div { { t!("grade", grade = review.grade) } }
+                        }
+                        li {
+                            class: "list-row py-1",
+                            div { i { class: "bi bi-check2-square text-base-content/70" } }
+                            div { { t!("score", score = review.score) } }
+                        }
+                    }
+                    if !review.categories.is_empty() {
+                        div {
+                            class: "font-semibold mt-4",
+                            { t!("categories") }
+                        }
+                        ul {
+                            class: "list w-full",
+                            for (idx, category) in review.categories.iter().enumerate() {
+                                li {
+                                    key: "{idx}",
+                                    class: "list-row py-1 items-center",
+                                    div { class: "list-col-grow", "{category.name}" }
+                                    progress {
+                                        class: "progress progress-primary w-24",
+                                        value: category.score,
+                                        max: 100,
+                                    }
+                                    div { class: "w-12 text-right", "{category.score}%" }
+                                }
+                            }
+                        }
+                    }
+                    if review.mode == QuizReviewMode::Answers {
+                        div {
+                            class: "font-semibold mt-4",
+                            { t!("wrong-answers") }
+                        }
+                        if review.questions.is_empty() {
+                            div {
+                                class: "text-success",
+                                { t!("no-wrong-answers") }
+                            }
+                        }
+                        ul {
+                            class: "list w-full",
+                            for question in review.questions.iter() {
+                                li {
+                                    key: "{question.id}",
+                                    class: "list-row flex flex-col w-full gap-2 py-3",
+                                    div { class: "text-xs text-base-content/60", "{question.category}" }
+                                    if question.img {
+                                        div {
+                                            class: "flex w-full max-w-md items-center justify-start",
+                                            img { class: "max-w-full h-auto object-contain", src: format!("/images/{}/{}/{}.webp", workspace, review.quiz, question.id) }
+                                        }
+                                    }
+                                    div { class: "font-medium text-pretty", "{question.name}" }
+                                    div {
+                                        class: "flex gap-2 text-error",
+                                        i { class: "bi bi-x-circle-fill" }
+                                        div {
+                                            class: "text-pretty",
+                                            if question.answered.is_empty() {
+                                                { t!("no-answer") }
+                                            } else {
+                                                { question.answered.join("; ") }
+                                            }
+                                        }
+                                    }
+                                    div {
+                                        class: "flex gap-2 text-success",
+                                        i { class: "bi bi-check-circle-fill" }
+                                        div { class: "text-pretty", { question.correct.join("; ") } }
+                                    }
+                                    if !question.explanation.is_empty() {
+                                        div {
+                                            class: "flex gap-2 text-base-content/70",
+                                            i { class: "bi bi-lightbulb" }
+                                            div { class: "text-pretty", "{question.explanation}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        class: "card-actions justify-end mt-6",
+                        button {
+                            class: "btn btn-neutral text-base-content/60",
+                            onclick: move |_| { navigator.go_back(); },
+                            { t!("back") }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
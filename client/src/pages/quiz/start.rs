@@ -1,5 +1,5 @@
 use super::*;
-use crate::{components::*, prelude::*, services::*};
+use crate::{components::*, prelude::*, services::*, utils::*};
 
 #[component]
 pub fn QuizStart(
@@ -8,11 +8,15 @@ pub fn QuizStart(
     student: ReadSignal<String>,
 ) -> Element {
     let navigator = use_navigator();
+    let mut languages = use_signal(Vec::<String>::new);
 
-    use_effect(move || {
+    let start_action = use_callback(move |language: String| {
         api_fetch!(
             GET,
-            format!("/api/v1/activities/{workspace}/{task}/{student}"),
+            format!(
+                "/api/v1/activities/{workspace}/{task}/{student}?language={language}",
+                language = encode_query_value(language)
+            ),
             on_success = move |body: QuizActivity| {
                 TIMER.signal().set(body.duration);
                 QUIZ.signal().set(body);
@@ -22,5 +26,61 @@ pub fn QuizStart(
         )
     });
 
-    rsx! { Loading {} }
+    use_effect(move || {
+        api_fetch!(
+            GET,
+            format!("/api/v1/activities/details/{workspace}/{task}/{student}"),
+            on_success = move |body: QuizActivityDetails| {
+                if body.languages.is_empty() {
+                    start_action.call(String::new())
+                } else {
+                    languages.set(body.languages)
+                }
+            },
+            on_error = move |e: shared::common::Error| ErrorService::show(t!(e.to_string()))
+        )
+    });
+
+    if languages.read().is_empty() {
+        return rsx! { Loading {} };
+    }
+
+    rsx! {
+        div {
+            class: "flex-scrollable justify-center items-center gap-2 p-4",
+            div {
+                class: "card w-full h-auto max-w-md card-border shadow-lg bg-base-100",
+                div {
+                    class: "card-body",
+                    div {
+                        class: "card-title flex text-primary text-xl gap-4",
+                        i { class: "bi bi-translate"}
+                        { t!("quiz-language-select") }
+                    }
+                    div {
+                        class: "h-0.25 bg-base-300",
+                    }
+                    div {
+                        class: "flex flex-col gap-2 mt-2",
+                        button {
+                            class: "btn btn-primary",
+                            onclick: move |_| start_action.call(String::new()),
+                            { t!("quiz-language-default") }
+                        }
+                        for language in languages() {
+                            button {
+                                key: "{language}",
+                                class: "btn btn-primary btn-outline",
+                                onclick: {
+                                    let language = language.clone();
+                                    move |_| start_action.call(language.clone())
+                                },
+                                "{language}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
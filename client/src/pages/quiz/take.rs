@@ -82,6 +82,9 @@ pub fn QuizTake() -> Element {
                             }
                         }
                     }
+                    if let Some(feedback) = FEEDBACK.read().get(&question.id).cloned() {
+                        RenderFeedback { feedback }
+                    }
                 }
                 RenderControls {}
             }
@@ -92,6 +95,7 @@ pub fn QuizTake() -> Element {
 #[component]
 fn RenderOpenKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Element {
     let answer = question.peek().answered.iter().next().cloned().unwrap_or_default();
+    let locked = FEEDBACK.read().contains_key(&question.read().id);
 
     rsx! {
         div {
@@ -103,6 +107,7 @@ fn RenderOpenKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Element
                 required: true,
                 minlength: 0,
                 maxlength: 3000,
+                disabled: locked,
                 initial_value: "{answer}",
                 oninput: move |evt| {
                     QUIZ.with_mut(|quiz| {
@@ -120,6 +125,7 @@ fn RenderOpenKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Element
 fn RenderSingleKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Element {
     let quiz = QUIZ.signal();
     let quiz_guard = quiz.read();
+    let correct = FEEDBACK.read().get(&question.read().id).map(|f| f.answers.clone());
 
     rsx! {
         for answer in question.read().answers.values() {
@@ -135,6 +141,7 @@ fn RenderSingleKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Eleme
                             r#type: "radio",
                             class: "radio radio-lg checked:radio-success",
                             checked: question.read().answered.contains(&answer.id),
+                            disabled: correct.is_some(),
                             onchange: {
                                 let answer_id = answer.id.clone();
                                 move |_| {
@@ -160,6 +167,12 @@ fn RenderSingleKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Eleme
                             "{answer.name}"
                         }
                     }
+                    if let Some(correct) = &correct {
+                        RenderAnswerMark {
+                            correct: correct.contains(&answer.id),
+                            answered: question.read().answered.contains(&answer.id),
+                        }
+                    }
                 }
             }
         }
@@ -170,6 +183,7 @@ fn RenderSingleKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Eleme
 fn RenderMultipleKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Element {
     let quiz = QUIZ.signal();
     let quiz_guard = quiz.read();
+    let correct = FEEDBACK.read().get(&question.read().id).map(|f| f.answers.clone());
 
     rsx! {
         for answer in question.read().answers.values() {
@@ -185,6 +199,7 @@ fn RenderMultipleKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Ele
                             r#type: "checkbox",
                             class: "checkbox checkbox-lg rounded-lg checked:checkbox-info",
                             checked: question.read().answered.contains(&answer.id),
+                            disabled: correct.is_some(),
                             onchange: {
                                 let answer_id = answer.id.clone();
                                 move |evt: FormEvent| {
@@ -213,6 +228,56 @@ fn RenderMultipleKindQuestion(question: ReadSignal<QuizActivityQuestion>) -> Ele
                             "{answer.name}"
                         }
                     }
+                    if let Some(correct) = &correct {
+                        RenderAnswerMark {
+                            correct: correct.contains(&answer.id),
+                            answered: question.read().answered.contains(&answer.id),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RenderAnswerMark(correct: bool, answered: bool) -> Element {
+    rsx! {
+        div {
+            class: "flex h-full items-center text-xl",
+            if correct {
+                i { class: "bi bi-check-circle-fill text-success" }
+            } else if answered {
+                i { class: "bi bi-x-circle-fill text-error" }
+            }
+        }
+    }
+}
+
+#[component]
+fn RenderFeedback(feedback: QuizActivityFeedback) -> Element {
+    rsx! {
+        li {
+            class: "list-row flex w-full rounded-none px-4 pt-5",
+            div {
+                class: format!("alert w-full {class}", class = if feedback.correct { "alert-success" } else { "alert-error" }),
+                i { class: if feedback.correct { "bi bi-check-circle-fill text-xl" } else { "bi bi-x-circle-fill text-xl" } }
+                div {
+                    class: "flex flex-col gap-1",
+                    div {
+                        class: "font-semibold",
+                        if feedback.correct {
+                            { t!("practice-correct") }
+                        } else {
+                            { t!("practice-incorrect") }
+                        }
+                    }
+                    if !feedback.reference.is_empty() {
+                        div { { t!("practice-reference", answer = feedback.reference.clone()) } }
+                    }
+                    if !feedback.explanation.is_empty() {
+                        div { class: "text-pretty", "{feedback.explanation}" }
+                    }
                 }
             }
         }
@@ -231,6 +296,39 @@ fn RenderControls() -> Element {
         .get_index(current())
         .map(|(_, question)| !question.answered.is_empty())
         .unwrap_or(false);
+    let needs_check = quiz.read().practice
+        && quiz
+            .read()
+            .questions
+            .get_index(current())
+            .is_some_and(|(id, _)| !FEEDBACK.read().contains_key(id));
+
+    let check_action = move |_| {
+        let quiz_guard = quiz.read();
+        let Some((_, question)) = quiz_guard.questions.get_index(current()) else {
+            return;
+        };
+        api_fetch!(
+            POST,
+            "/api/v1/activities/check",
+            CheckQuizAnswerPayload {
+                workspace: quiz_guard.workspace.clone(),
+                task: quiz_guard.task.clone(),
+                student: quiz_guard.student.clone(),
+                language: quiz_guard.language.clone(),
+                question: QuizActivityQuestion {
+                    answers: Default::default(),
+                    ..question.clone()
+                },
+            },
+            on_success = move |body: QuizActivityFeedback| {
+                FEEDBACK.with_mut(|feedback| {
+                    feedback.insert(body.question.clone(), body);
+                })
+            },
+            on_error = move |e: shared::common::Error| ErrorService::show(t!(e.to_string()))
+        )
+    };
 
     rsx! {
         div {
@@ -243,7 +341,13 @@ fn RenderControls() -> Element {
                 },
                 { t!("previous") }
             }
-            if current() + 1 < questions_count {
+            if needs_check {
+                button {
+                    class: format!("btn btn-lg btn-accent {class}", class = if !has_answered { "btn-disabled" } else { "" }),
+                    onclick: move |evt| if has_answered { check_action(evt) },
+                    { t!("check-answer") }
+                }
+            } else if current() + 1 < questions_count {
                 button {
                     class: format!("btn btn-lg btn-primary {class}", class = if !has_answered { "btn-disabled" } else { "" }),
                     onclick: move |_| if has_answered {
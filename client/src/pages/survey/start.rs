@@ -10,8 +10,10 @@ pub fn SurveyStart(workspace: ReadSignal<String>, task: ReadSignal<String>) -> E
             GET,
             format!("/api/v1/activities/{workspace}/{task}"),
             on_success = move |body: SurveyRecord| {
+                let visible = body.visible_categories(&body);
+                let first = body.categories.keys().position(|id| visible.contains(id));
                 SURVEY.signal().set(body);
-                CURRENT.signal().set(0);
+                CURRENT.signal().set(first.unwrap_or(0));
                 navigator.replace(Route::SurveyTake {});
             },
             on_error = move |e: shared::common::Error| ErrorService::show(t!(e.to_string()))
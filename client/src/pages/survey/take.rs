@@ -14,12 +14,19 @@ pub fn SurveyTake() -> Element {
     }
 
     let survey_guard = survey.read();
-    let category_count = survey_guard.categories.len();
+    let visible = survey_guard.visible_categories(&survey_guard);
+    let category_count = visible.len();
 
     let Some((_id, category)) = survey_guard.categories.get_index(current()) else {
         ErrorService::show(t!("no-category"));
         return rsx! {};
     };
+    let position = survey_guard
+        .categories
+        .keys()
+        .take(current() + 1)
+        .filter(|id| visible.contains(*id))
+        .count();
 
     rsx! {
         div {
@@ -28,11 +35,11 @@ pub fn SurveyTake() -> Element {
                 class: "flex shrink-0 w-full p-4 items-center gap-4",
                 div {
                     class: "text-base-content/60",
-                    { format!("{}/{}", current() + 1, category_count) }
+                    { format!("{}/{}", position, category_count) }
                 }
                 progress {
                     class: "flex flex-1 progress text-primary",
-                    value: current() + 1,
+                    value: position,
                     max: category_count,
                 }
             }
@@ -41,10 +48,39 @@ pub fn SurveyTake() -> Element {
                 id: "scroll-container",
                 class: "flex-scrollable bg-base-100 w-full h-full pb-16",
                 div {
-                    class: "flex w-full bg-base-200 rounded-none flex-wrap font-medium text-pretty items-center px-4 pb-4",
+                    class: "flex w-full bg-base-200 rounded-none flex-wrap font-medium text-pretty items-center gap-2 px-4 pb-4",
                     "{category.name}"
+                    if category.required {
+                        span { class: "text-error", "*" }
+                    }
+                }
+                if let Some(max) = category.max_selections {
+                    div {
+                        class: "flex w-full text-sm text-base-content/60 px-4 pt-4",
+                        { t!("survey-max-selections", max = max) }
+                    }
                 }
-                if category.questions.is_empty() {
+                if category.kind == SurveyCategoryKind::Text {
+                    RenderTextCategory {
+                        key: "{category.id}{current}",
+                        category: category.clone(),
+                    }
+                } else if category.kind == SurveyCategoryKind::Numeric {
+                    RenderNumericCategory {
+                        key: "{category.id}{current}",
+                        category: category.clone(),
+                    }
+                } else if category.kind == SurveyCategoryKind::Scale {
+                    RenderScaleCategory {
+                        key: "{category.id}{current}",
+                        category: category.clone(),
+                    }
+                } else if category.kind == SurveyCategoryKind::Ranking {
+                    RenderRankingCategory {
+                        key: "{category.id}{current}",
+                        category: category.clone(),
+                    }
+                } else if category.questions.is_empty() {
                     RenderSingleCategory {
                         key: "{category.id}{current}",
                         category: category.clone(),
@@ -128,6 +164,8 @@ fn RenderMultiplyCategory(category: ReadSignal<SurveyRecordCategory>) -> Element
                                 r#type: "checkbox",
                                 class: "checkbox checkbox-lg rounded-lg checked:checkbox-info",
                                 checked: *category.read().results.get(idx, 0) > 0,
+                                disabled: *category.read().results.get(idx, 0) == 0
+                                    && category.read().max_selections.is_some_and(|max| category.read().selected_count() >= max),
                                 onchange: {
                                     to_owned![idx];
                                     move |evt| {
@@ -203,36 +241,253 @@ fn RenderMultiplyQuestionCategory(category: ReadSignal<SurveyRecordCategory>) ->
     }
 }
 
+/// Text and numeric categories without questions use the category name as the only prompt.
+fn category_prompts(category: &SurveyRecordCategory) -> Vec<String> {
+    if category.questions.is_empty() {
+        vec![String::new()]
+    } else {
+        category.questions.values().map(|q| q.name.clone()).collect()
+    }
+}
+
+#[component]
+fn RenderTextCategory(category: ReadSignal<SurveyRecordCategory>) -> Element {
+    let prompts = category_prompts(&category.read());
+
+    rsx! {
+        for (idx, prompt) in prompts.into_iter().enumerate() {
+            if !prompt.is_empty() {
+                div {
+                    class: "flex w-full text-pretty items-center font-medium px-4 pt-4",
+                    "{prompt}"
+                }
+            }
+            div {
+                class: "flex px-4 pt-4",
+                textarea {
+                    class: "textarea w-full resize-none overflow-hidden",
+                    style: "field-sizing: content;",
+                    placeholder: t!("survey-text-placeholder"),
+                    maxlength: 2000,
+                    initial_value: category.peek().responses.get(idx).and_then(|r| r.first()).cloned().unwrap_or_default(),
+                    oninput: move |evt| {
+                        SURVEY.with_mut(|survey| {
+                            if let Some(cat) = survey.categories.get_mut(&category.read().id) {
+                                let rows = cat.results.rows();
+                                cat.responses.resize(rows, vec![]);
+                                if let Some(responses) = cat.responses.get_mut(idx) {
+                                    *responses = vec![evt.value()];
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RenderNumericCategory(category: ReadSignal<SurveyRecordCategory>) -> Element {
+    let prompts = category_prompts(&category.read());
+    let min = category.read().min.map(|v| v.to_string()).unwrap_or_default();
+    let max = category.read().max.map(|v| v.to_string()).unwrap_or_default();
+
+    rsx! {
+        for (idx, prompt) in prompts.into_iter().enumerate() {
+            label {
+                class: "flex w-full items-center gap-4 px-4 pt-4",
+                div {
+                    class: "flex w-full text-pretty items-center font-medium",
+                    "{prompt}"
+                }
+                input {
+                    r#type: "number",
+                    class: "input input-lg w-40 shrink-0",
+                    step: "any",
+                    min: "{min}",
+                    max: "{max}",
+                    initial_value: category.peek().values.get(idx).and_then(|v| v.first()).map(|v| v.to_string()).unwrap_or_default(),
+                    oninput: move |evt| {
+                        let value = evt.value().trim().replace(',', ".").parse::<f64>().ok();
+                        SURVEY.with_mut(|survey| {
+                            if let Some(cat) = survey.categories.get_mut(&category.read().id) {
+                                let rows = cat.results.rows();
+                                cat.values.resize(rows, vec![]);
+                                if let Some(values) = cat.values.get_mut(idx) {
+                                    *values = value.into_iter().collect();
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RenderScaleCategory(category: ReadSignal<SurveyRecordCategory>) -> Element {
+    let (min, max) = SurveyCategoryKind::scale_range(category.read().min, category.read().max);
+
+    rsx! {
+        div {
+            class: "flex w-full justify-between text-sm text-base-content/60 px-4 pt-4",
+            span { { t!("survey-scale-min", value = min) } }
+            span { { t!("survey-scale-max", value = max) } }
+        }
+        for (question_idx, (_question_id, question)) in category.read().questions.iter().enumerate() {
+            div {
+                class: "flex w-full text-pretty items-center font-medium p-4",
+                "{question.name}"
+            }
+            div {
+                class: "join flex w-full px-4",
+                for (col, point) in (min..=max).enumerate() {
+                    input {
+                        key: "{_question_id}{point}",
+                        r#type: "radio",
+                        class: "join-item btn flex-1 checked:btn-success",
+                        name: "{_question_id}",
+                        aria_label: "{point}",
+                        checked: *category.read().results.get(question_idx, col) > 0,
+                        onchange: move |_| {
+                            SURVEY.with_mut(|survey| {
+                                if let Some(cat) = survey.categories.get_mut(&category.read().id) {
+                                    cat.results.fill_row(question_idx, 0);
+                                    cat.results.set(question_idx, col, 1)
+                                }
+                            })
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RenderRankingCategory(category: ReadSignal<SurveyRecordCategory>) -> Element {
+    // Start from the order defined by the author so an untouched ranking is still valid.
+    use_hook(move || {
+        SURVEY.with_mut(|survey| {
+            if let Some(cat) = survey.categories.get_mut(&category.peek().id) {
+                for row in 0..cat.results.rows() {
+                    if cat.results.get_row(row).iter().all(|v| **v == 0) {
+                        cat.results.set(row, row, 1)
+                    }
+                }
+            }
+        })
+    });
+
+    let category_guard = category.read();
+    let items = category_guard.questions.values().collect::<Vec<_>>();
+    let position = |row: usize| {
+        category_guard
+            .results
+            .get_row(row)
+            .iter()
+            .position(|v| **v > 0)
+            .unwrap_or(row)
+    };
+    let mut order = (0..items.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&row| position(row));
+
+    let swap_action = move |a: usize, b: usize| {
+        SURVEY.with_mut(|survey| {
+            if let Some(cat) = survey.categories.get_mut(&category.read().id) {
+                let pos_a = cat.results.get_row(a).iter().position(|v| **v > 0).unwrap_or(a);
+                let pos_b = cat.results.get_row(b).iter().position(|v| **v > 0).unwrap_or(b);
+                cat.results.fill_row(a, 0);
+                cat.results.fill_row(b, 0);
+                cat.results.set(a, pos_b, 1);
+                cat.results.set(b, pos_a, 1);
+            }
+        })
+    };
+
+    rsx! {
+        div {
+            class: "flex w-full text-sm text-base-content/60 px-4 pt-4",
+            { t!("survey-ranking-hint") }
+        }
+        ul {
+            class: "list w-full",
+            for (pos, row) in order.iter().copied().enumerate() {
+                li {
+                    key: "{items[row].id}",
+                    class: "list-row flex w-full rounded-none items-center gap-2 p-4",
+                    div { class: "badge badge-lg badge-info", "{pos + 1}" }
+                    div { class: "flex w-full text-pretty items-center", "{items[row].name}" }
+                    button {
+                        class: "btn btn-square btn-ghost",
+                        disabled: pos == 0,
+                        onclick: {
+                            let prev = order.get(pos.wrapping_sub(1)).copied();
+                            move |_| if let Some(prev) = prev { swap_action(row, prev) }
+                        },
+                        i { class: "bi bi-arrow-up" }
+                    }
+                    button {
+                        class: "btn btn-square btn-ghost",
+                        disabled: pos + 1 == order.len(),
+                        onclick: {
+                            let next = order.get(pos + 1).copied();
+                            move |_| if let Some(next) = next { swap_action(row, next) }
+                        },
+                        i { class: "bi bi-arrow-down" }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn RenderControls() -> Element {
     let navigator = use_navigator();
     let mut current = CURRENT.signal();
-    let category_count = SURVEY.peek().categories.len();
+    let survey = SURVEY.read();
+    let visible = survey.visible_categories(&survey);
+    let visible_ids = survey
+        .categories
+        .keys()
+        .enumerate()
+        .filter(|(_, id)| visible.contains(*id))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    let previous = visible_ids.iter().rev().find(|&&idx| idx < current()).copied();
+    let next = visible_ids.iter().find(|&&idx| idx > current()).copied();
+    let blocked = survey
+        .categories
+        .get_index(current())
+        .is_some_and(|(_, c)| c.required && !c.is_complete());
 
     rsx! {
         div {
             class: "flex shrink-0 w-full items-center justify-between px-8 pt-10",
             button {
-                class: format!("btn btn-lg btn-primary {class}" , class = if current() == 0 { "btn-disabled" } else { "" }),
-                onclick: move |_| if current() > 0 {
-                    current.set(current() - 1);
+                class: format!("btn btn-lg btn-primary {class}" , class = if previous.is_none() { "btn-disabled" } else { "" }),
+                onclick: move |_| if let Some(previous) = previous {
+                    current.set(previous);
                         document::eval(r#"window.scrollToTop();"#);
                 },
                 { t!("previous") }
             }
-            if current() + 1 < category_count {
+            if let Some(next) = next {
                 button {
-                    class: "btn btn-lg btn-primary",
-                    onclick: move |_| {
-                        current.set(current() + 1);
+                    class: format!("btn btn-lg btn-primary {class}", class = if blocked { "btn-disabled" } else { "" }),
+                    onclick: move |_| if !blocked {
+                        current.set(next);
                         document::eval(r#"window.scrollToTop();"#);
                     },
                     { t!("next") }
                 }
             } else {
                 button {
-                    class: "btn btn-lg btn-success",
-                    onclick: move |_| { navigator.push(Route::SurveyFinish {}); },
+                    class: format!("btn btn-lg btn-success {class}", class = if blocked { "btn-disabled" } else { "" }),
+                    onclick: move |_| if !blocked { navigator.push(Route::SurveyFinish {}); },
                     { t!("finish") }
                 }
             }
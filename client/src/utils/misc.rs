@@ -10,4 +10,17 @@ pub fn close_window() {
         document::eval("window.close();");
         document::eval("window.location.href = window.location.origin;");
     });
-}
\ No newline at end of file
+}
+
+pub fn encode_query_value(value: impl AsRef<str>) -> String {
+    let mut out = String::with_capacity(value.as_ref().len());
+    for b in value.as_ref().bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}
@@ -12,21 +12,226 @@ absent = Відсутні
 absent-uncertified-students = Відсутні та неатестовані
 acquainted = Прийнято
 add = Додати
+add-assembly-rule = Додати правило відбору питань
+add-quiz = Додати тест
 add-student = Додати курсанта
 administrator = Адміністратор
 alert = Увага
+all-quizzes = Усі тести
+all-respondents = Усі респонденти
+all-units = Усі підрозділи
+analytics = Аналітика
 answer-correct-error = Повинна бути якнайменше одна правильна відповідь
+answer-not-found = Відповідь не знайдено
 answer-placeholder = Введіть відповідь
+answer-sheet-dialog-title = Оберіть скан бланку відповідей
+answer-sheet-not-recognized = Бланк відповідей не розпізнано
+answer-sheet-overflow = Забагато питань для бланку відповідей
+answer-sheet-recognized = Бланк відповідей розпізнано
+answer-sheet-too-many-answers = Забагато варіантів відповіді для бланку відповідей
+answer-sheet-wrong-task = Бланк відповідей належить іншому завданню
 answers-count-error = Повинно бути якнайменше дві відповіді з текстом чи зображенням
 app-title = Ситема оцінювання MAES
 app-workspace-title = Система оцінювання MAES | { $version } | { $username }
+assembly-rules = Правила відбору питань
+attempt-review = Перегляд спроби
+attempts = Спроби
 attempts-exceeded = Перевищено кількість спроб
+average-rank = Середнє місце
+average-score = Середній результат, %
+average-score-trend = Динаміка середнього результату, %
+back = Назад
 bad-request = Запит не вдалося обробити, оскільки він некоректний
 begin = Розпочати
 cancel = Скасувати
+cancel-excuse = Скасувати звільнення
 cannot-delete-self = Неможливо видалити свій обліковий запис
 categories = Категорії
+check-answer = Перевірити
+clear-override = Скасувати зміну
+closes-at = Завершення
+closes-in = Завершиться через { $time }
+column-mapping = Відповідність стовпців
+column-number = Стовпець { $number }
+date = Дата
+deadline = Кінцевий термін
+deadline-passed = Кінцевий термін виконання минув
+display-conditions = Умови показу
+display-conditions-hint = Категорію буде показано лише тоді, коли обрано всі зазначені відповіді
+entities-split-failed = Неможливо розділити
+excuse = Звільнити від складання
+excused = Звільнений
+explanation = Пояснення
+explanation-placeholder = Чому ця відповідь правильна
+export-package-dialog-title = Експорт пакета
+file-read-error = Не вдалося прочитати файл
+file-write-error = Не вдалося записати файл
+first-row-header = Перший рядок містить заголовки
+generate = Згенерувати
+grade-overridden = Оцінку змінено вручну: { $reason } ({ $user })
+gradebook = Журнал оцінок
+gradebook-weights = Вагові коефіцієнти
+grant-attempt = Додати спробу
+grant-time = Додати 5 хвилин
+image-read-error = Не вдалося прочитати зображення
+import-archive-too-large = Архів завеликий або містить підозріло стиснуті файли
+import-choice-default = За замовчуванням
+import-created = Нових: { $count }
+import-kind-json = Звіти
+import-kind-quiz = Тести
+import-kind-qz = Результати тестування
+import-kind-sr = Результати опитування
+import-kind-students = Курсанти
+import-kind-survey = Опитування
+import-kind-users = Користувачі
+import-kind-workspace = Робочий простір
+import-new-workspace = Робочий простір буде створено
+import-not-found = Підготовлений імпорт не знайдено
+import-package-dialog-title = Імпорт пакета
+import-preview = Імпорт: { $workspace }
+import-skipped = Пропущено: { $count }
+import-status-added = нове
+import-status-older = старіше
+import-status-unchanged = без змін
+import-status-updated = оновлене
+import-students = Імпорт курсантів
+import-unresolved = Підрозділ не знайдено: { $count }
+import-updated = Оновлено: { $count }
+import-uploaded = Файл передано на сервер, імпорт триває
+invalid-range = Мінімальне значення має бути меншим за максимальне
+keep-both = Залишити обидва
+keep-local = Залишити поточне
+keywords-placeholder = Ключові слова через кому
+match-strategy = Перевірка відповіді
+match-strategy-combined = Комбінована
+match-strategy-exact = Точний збіг
+match-strategy-fuzzy = Нечіткий збіг
+match-strategy-semantic = Семантична схожість
+max-selections = Максимум обраних варіантів (0 — без обмежень)
+max-value = Максимум
+mean = Середнє
+median = Медіана
+min-segment-size = Мінімальний розмір сегмента для звітів
+min-value = Мінімум
+no-answer = Відповідь відсутня
+no-attempts = Спроби відсутні
+no-responses = Відповідей немає
+no-wrong-answers = Помилок немає
+opens-at = Початок
+opens-in = Розпочнеться через { $time }
+override-grade = Змінити оцінку
+override-reason = Підстава
+override-reason-required = Вкажіть підставу для зміни оцінки
+package-dialog-filter = Пакет MAES
+package-imported = Імпортовано елементів: { $count }
+package-node-required = Оберіть розділ для імпорту
+pass-rate = Успішність
+period-month = Місяць
+period-quarter = Квартал
+period-week = Тиждень
+practice = Тренування
+practice-attempts = Тренувальних спроб / останній результат
+practice-correct = Правильно
+practice-incorrect = Неправильно
+practice-last-score = Останній результат: { $score }% (спроб: { $count })
+practice-mode = Режим тренування
+practice-mode-hint = Курсант одразу бачить правильність відповідей, результати не впливають на оцінку
+practice-only = Перевірка відповідей доступна лише в режимі тренування
+practice-reference = Еталонна відповідь: { $answer }
+preview = Попередній перегляд
+question-not-found = Питання не знайдено
+quiz-booklets-or-answer-sheets = Білети з питаннями / бланки відповідей
+quiz-open-answer-hint = Відповідь впишіть у бланк відповідей
+quiz-open-answer-settings = Відкрита відповідь
+quiz-regrade = Переоцінювання
+quiz-regrade-apply = Переоцінити
+quiz-regrade-empty = Оцінки не змінюються
+quiz-regrade-refresh = Використати ключ відповідей з поточного тесту
+quiz-regrade-title = Переоцінювання результатів
+quiz-review = Перевірка відповідей
+quiz-review-accepted = Зараховано: { $name }
+quiz-review-all = Усі відкриті відповіді
+quiz-review-empty = Немає відповідей для перевірки
+quiz-review-rejected = Не зараховано: { $name }
+quiz-review-title = Перевірка відкритих відповідей
+quiz-variant = Варіант { $variant }
+quiz-variant-entry = Введення бланків
+quiz-variant-entry-title = Введення відповідей з бланків
+quiz-variants = Паперові варіанти
+quiz-variants-count = Кількість варіантів
+quiz-variants-empty = Варіанти ще не згенеровано
+quiz-variants-generated = Варіанти згенеровано
+quiz-variants-title = Паперові варіанти тесту
+remove-student-message = Ви впевнені, що бажаєте вилучити "{ $name }" із завдання?
+reopen = Відновити
+reopen-report-message = Повернути звіт до активних завдань?
+report-reopened = Завдання відновлено
+reports-split = Звіт розділено за підрозділами
+required-category = Обовʼязкова категорія
+required-keywords = Обов'язкові ключові слова
+reset-attempts = Скинути спроби
+reset-attempts-message = Скинути спроби та результати курсанта "{ $name }"?
+responses = Відповіді
+results-count = Результатів
+review-after-close = Після завершення
+review-mode = Результати для курсантів
+review-mode-answers = Помилки з правильними відповідями
+review-mode-categories = Оцінки за категоріями
+review-mode-hidden = Не показувати
+review-mode-score = Лише оцінка
+review-not-available = Перегляд результатів недоступний
+roster-dialog-filter = Таблиці (CSV, XLSX)
+roster-empty = Файл не містить даних
+roster-export-dialog-title = Експорт списку курсантів
+roster-import-dialog-title = Імпорт списку курсантів
+scan = Сканувати
+segment-categories-suppressed = Категорії, на які відповіли менше { $size } респондентів сегмента, приховано
+segment-dimension = Розріз результатів
+segment-dimension-hint = Відповіді на цю категорію дозволяють порівнювати результати за групами без ідентифікації респондентів
+segment-stamp = Сегмент: { $name }
+segment-suppressed = { $name } (менше { $size } відповідей)
+select-folder-first = Оберіть спочатку розділ
+select-option = Оберіть варіант
+select-student = Оберіть курсанта
+similarity-busy = Сервер перевірки відповідей перевантажений, спробуйте ще раз
+similarity-timeout = Перевірка відповідей триває надто довго, спробуйте ще раз
+split = Розділити
 stats = Статистика
+storage-already-active = Це сховище вже використовується
+storage-not-persistent = Дані не можна переносити в сховище, яке не зберігається на диску
+survey-invalid-ranking = Некоректне ранжування
+survey-malformed = Некоректні дані опитування
+survey-max-selections = Оберіть не більше { $max }
+survey-required-missing = Дайте відповідь на всі обовʼязкові питання
+survey-response-too-long = Відповідь занадто довга
+survey-too-many-selections = Обрано забагато варіантів
+survey-value-out-of-range = Значення поза допустимими межами
+survey-category-total = Відповідей: { $total }
+std-dev = Стандартне відхилення
+survey-kind-choice = Вибір варіантів
+survey-kind-choice-hint = Один або кілька варіантів відповіді, або матриця питань і відповідей
+survey-kind-numeric = Число
+survey-kind-numeric-hint = Числова відповідь на кожне питання з необовʼязковими межами
+survey-kind-ranking = Ранжування
+survey-kind-ranking-hint = Упорядкування варіантів від найважливішого до найменш важливого
+survey-kind-scale = Шкала
+survey-kind-scale-hint = Оцінка кожного твердження за шкалою (наприклад, шкала Лайкерта)
+survey-kind-text = Вільна відповідь
+survey-kind-text-hint = Текстові відповіді зберігаються анонімно та окремо від підрахунків
+survey-ranking-hint = Упорядкуйте варіанти від найважливішого до найменш важливого
+survey-scale-max = { $value } — повністю згоден
+survey-scale-min = { $value } — зовсім не згоден
+survey-text-placeholder = Ваша відповідь
+wrong-answers = Помилки
+total = Разом
+transcript = Особова картка успішності
+weight = Вага
+weighted-average = Середньозважена оцінка:
+student-excused = Курсанта звільнено від складання
+task-closed = Завдання завершено
+task-not-open = Завдання ще не розпочато
+task-status-closed = Час виконання минув
+task-window-invalid = Час завершення має бути пізніше часу початку
 category = Категорія
 category-placeholder = Введіть назву категорії
 checklists = Чеклисти
@@ -65,11 +270,17 @@ delete-user-message = Ви впевнені, що бажаєте видалит
 delete-workspace-message = Ви впевнені, що бажаєте видалити робочій простір "{ $name }"?
 deserialize-error = Помилка десеріалізації
 device-connected = Підключився: { $name }
+difficulty = Складність
+difficulty-any = Будь-яка
+difficulty-easy = Легка
+difficulty-hard = Складна
+difficulty-medium = Середня
 download = Зберегти
 edit = Редагувати
 edit-report = Редагування звіту
 entity-not-found = За запитом нічого не знайдено
 error = Помилка
+exclude-tags = Виключити теги
 export = Експорт
 export-success = Файл збережено
 export-failed = Помилка збереження файлу
@@ -114,6 +325,7 @@ internal-server-error = Внутрішня помилка серверу
 invalid-credentials = Невірний пароль
 invalid-payload = Неприпустимий вміст
 instruction = Інструкція
+language-not-found = Обрану мову не знайдено
 loading = Завантаження
 loading-resources = Завантаження ресурсів...
 login = логін
@@ -149,6 +361,10 @@ questions-count-error = Повинно бути якнайменше одне п
 quiz = Тест
 quiz-answers-settings = Відповіді
 quiz-category-settings = Категорія
+quiz-language-default = Основна мова
+quiz-language-select = Оберіть мову тестування
+quiz-languages-placeholder = Мовні варіанти через кому, напр. EN, PL
+quiz-languages-settings = Мовні варіанти
 quiz-navigator = Структура тесту
 quiz-placeholder = Введіть назву тесту
 quiz-question-settings = Питання
@@ -189,9 +405,15 @@ stat-total = За списком:
 stat-in-fact = За фактом:
 stat-certified = Атестовано:
 stat-uncertified = Не атестовано:
+storage-file = Файли
+storage-migrate = Перенести дані
+storage-migrated = Перенесено робочих просторів: { $workspaces }, записів: { $records }
+storage-settings = Сховище даних
+storage-sqlite = SQLite
 student = Курсант
 students = Курсанти
 student-not-found = Курсанта не знайдено
+students-imported = Імпортовано курсантів: нових { $created }, оновлено { $updated }
 success = Успіх
 supervisor = Керівник
 supervisor-sign = Керівник: _____________________________________________________
@@ -219,6 +441,10 @@ survey-tickets = Білети
 survey-tickets-title = Білети анонімного опитування
 surveys = Опитування
 surveys-navigator = Структура опитувань
+tags = Теги
+tags-placeholder = Введіть теги через кому
+take-incoming = Взяти імпортоване
+target-unit = Підрозділ за замовчуванням
 task = Завдання
 task-created = Завдання створене
 task-inspector = Панель керування
@@ -272,11 +498,14 @@ uncertified = Неатестовані
 uncertified-placeholder = -
 updated = оновлено
 upload = Завантажити
+upload-empty = Файл не передано
+upload-too-large = Файл завеликий
 user = Користувач
 user-already-exists = Користувач з таким логіном вже існує
 username = прізвище ім'я та по-батькові
 users = Користувачі
 validate-images = Виправити
+variant-not-found = Варіант не знайдено
 warning = Попередження
 wifi = Wi-Fi
 wifi-ap-active = Мережа Wi-Fi
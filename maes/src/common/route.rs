@@ -26,6 +26,10 @@ pub enum Route {
     Reports {},
     #[route("/students")]
     Students {},
+    #[route("/grades")]
+    Grades {},
+    #[route("/dashboard")]
+    Dashboard {},
     #[route("/workspace/quizzes/:quiz_id")]
     QuizManager { quiz_id: String },
     #[route("/workspace/surveys/:survey_id")]
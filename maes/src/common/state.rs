@@ -9,9 +9,14 @@ pub fn app_data_path() -> PathBuf {
 }
 
 static LOCALHOST: LazyLock<String> = LazyLock::new(|| {
-    let (_scheme, _host, port) = parse_scheme_host_port(&ConfigService::read().server.host)
+    let config = ConfigService::read();
+    let (scheme, host, port) = parse_scheme_host_port(&config.server.host)
         .unwrap_or(("".to_string(), "".to_string(), 4583));
-    format!("http://localhost:{port}")
+    if config.server.remote && !host.is_empty() {
+        format!("{scheme}://{host}:{port}")
+    } else {
+        format!("http://localhost:{port}")
+    }
 });
 
 pub fn localhost() -> String {
@@ -25,6 +25,18 @@ pub fn add_image_dialog(entity: impl Into<String>, id: impl Into<String>, on_suc
             }
         })
         .ok();
+        if config.server.remote {
+            match ClientService::upload_file(
+                format!("/api/v1/manager/images/upload/{entity_id}/{id}"),
+                path.path(),
+            )
+            .await
+            {
+                Ok(_) => on_success.call(()),
+                Err(e) => ToastService::error(t!(e.to_string())),
+            }
+            return;
+        }
         api_call!(
             POST,
             format!(
@@ -39,3 +51,94 @@ pub fn add_image_dialog(entity: impl Into<String>, id: impl Into<String>, on_suc
         )
     });
 }
+
+pub fn scan_answer_sheet_dialog(task: impl Into<String>, on_success: Callback<QuizVariantSheet>) {
+    let task_id = task.into();
+
+    spawn(async move {
+        let config = ConfigService::read();
+        let Some(path) = rfd::AsyncFileDialog::new()
+            .set_title(t!("answer-sheet-dialog-title"))
+            .set_directory(&config.recent.images)
+            .add_filter(
+                t!("image-dialog-filter"),
+                &["jpeg", "png", "bmp", "jpg", "webp"],
+            )
+            .pick_file()
+            .await
+        else {
+            return;
+        };
+        ConfigService::with_mut(|config| {
+            if let Some(path) = path.path().parent() {
+                config.recent.images = path.to_path_buf()
+            }
+        })
+        .ok();
+        match ClientService::upload_file_with_json::<QuizVariantSheet>(
+            format!("/api/v1/tasks/variants/{task_id}/scan"),
+            path.path(),
+        )
+        .await
+        {
+            Ok(sheet) => on_success.call(sheet),
+            Err(e) => ToastService::error(t!(e.to_string())),
+        }
+    });
+}
+
+pub fn import_roster_dialog(on_success: Callback<Vec<Vec<String>>>) {
+    spawn(async move {
+        let config = ConfigService::read();
+        let Some(path) = rfd::AsyncFileDialog::new()
+            .set_title(t!("roster-import-dialog-title"))
+            .set_directory(&config.recent.import)
+            .add_filter(
+                t!("roster-dialog-filter"),
+                &["csv", "txt", "xlsx", "xlsm", "xls", "ods"],
+            )
+            .pick_file()
+            .await
+        else {
+            return;
+        };
+        ConfigService::with_mut(|config| {
+            if let Some(path) = path.path().parent() {
+                config.recent.import = path.to_path_buf()
+            }
+        })
+        .ok();
+        match Roster::read_rows(path.path()) {
+            Ok(rows) if rows.is_empty() => ToastService::error(t!("roster-empty")),
+            Ok(rows) => on_success.call(rows),
+            Err(e) => ToastService::error(t!(e.to_string())),
+        }
+    });
+}
+
+pub fn export_roster_dialog(rows: Vec<Vec<String>>) {
+    spawn(async move {
+        let config = ConfigService::read();
+        let Some(path) = rfd::AsyncFileDialog::new()
+            .set_title(t!("roster-export-dialog-title"))
+            .set_directory(&config.recent.export)
+            .set_can_create_directories(true)
+            .set_file_name(format!("{}.xlsx", t!("students")))
+            .add_filter(t!("roster-dialog-filter"), &["xlsx", "csv"])
+            .save_file()
+            .await
+        else {
+            return;
+        };
+        ConfigService::with_mut(|config| {
+            if let Some(path) = path.path().parent() {
+                config.recent.export = path.to_path_buf()
+            }
+        })
+        .ok();
+        match Roster::write_rows(path.path(), &rows) {
+            Ok(_) => ToastService::success(t!("export-success")),
+            Err(_) => ToastService::error(t!("export-failed")),
+        }
+    });
+}
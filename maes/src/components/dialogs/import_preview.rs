@@ -0,0 +1,262 @@
+use crate::{prelude::*, services::*};
+use ::std::collections::HashMap;
+
+const KIND_ORDER: [&str; 8] = [
+    "workspace",
+    "users",
+    "students",
+    "quiz",
+    "survey",
+    "qz",
+    "sr",
+    "json",
+];
+
+#[derive(Default, Copy, Clone)]
+pub struct ImportPreviewDialog {
+    pub is_visible: Signal<bool>,
+    preview: Signal<ImportPreview>,
+}
+
+impl ImportPreviewDialog {
+    pub fn open(&mut self, preview: ImportPreview) {
+        self.preview.set(preview);
+        self.is_visible.set(true);
+    }
+
+    pub fn close(&mut self) {
+        self.preview.set(ImportPreview::default());
+        self.is_visible.set(false);
+    }
+}
+
+pub fn use_init_import_preview_dialog() -> ImportPreviewDialog {
+    use_context_provider(ImportPreviewDialog::default)
+}
+
+pub fn use_import_preview_dialog() -> ImportPreviewDialog {
+    use_context()
+}
+
+#[component]
+pub fn ImportPreviewDialogContainer() -> Element {
+    let dialog = use_import_preview_dialog();
+
+    if !(dialog.is_visible)() {
+        return rsx! {};
+    };
+
+    rsx! {
+        ImportPreviewForm {}
+    }
+}
+
+#[component]
+fn ImportPreviewForm() -> Element {
+    let mut dialog = use_import_preview_dialog();
+    let mut resolution = use_signal(ImportResolution::default);
+    let preview = dialog.preview.read().clone();
+
+    let mut groups = Vec::<(String, Vec<ImportDiffItem>)>::new();
+    for item in preview.items.iter() {
+        match groups.iter_mut().find(|(kind, _)| kind == &item.kind) {
+            Some((_, items)) => items.push(item.clone()),
+            None => groups.push((item.kind.clone(), vec![item.clone()])),
+        }
+    }
+    groups.sort_by_key(|(kind, _)| {
+        KIND_ORDER
+            .iter()
+            .position(|k| k == kind)
+            .unwrap_or(KIND_ORDER.len())
+    });
+
+    let stage_id = preview.id.clone();
+    let apply_action = move |evt: MouseEvent| {
+        evt.prevent_default();
+        api_call!(
+            POST,
+            format!("/api/v1/exchange/import/staged/{stage_id}"),
+            resolution(),
+            on_success = move || {
+                ToastService::success(t!("import-success"));
+                dialog.close();
+            }
+        )
+    };
+
+    let stage_id = preview.id.clone();
+    let cancel_action = move |evt: MouseEvent| {
+        evt.stop_propagation();
+        evt.prevent_default();
+        api_call!(
+            DELETE,
+            format!("/api/v1/exchange/import/staged/{stage_id}"),
+        );
+        dialog.close();
+    };
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box flex flex-col gap-5 max-w-3xl max-h-[90vh]",
+                onclick: |evt| evt.stop_propagation(),
+                h3 {
+                    class: "text-lg font-semibold text-accent",
+                    { t!("import-preview", workspace = preview.workspace.clone()) }
+                }
+                if !preview.exists {
+                    div { class: "text-sm text-base-content/60", { t!("import-new-workspace") } }
+                }
+
+                div {
+                    class: "flex flex-col gap-3 overflow-y-auto",
+                    for (kind, items) in groups {
+                        ImportPreviewGroup {
+                            key: "{kind}",
+                            kind: kind.clone(),
+                            items,
+                            resolution,
+                        }
+                    }
+                }
+
+                div {
+                    class: "flex justify-end gap-2 mt-3",
+                    button {
+                        class: "btn btn-ghost",
+                        onclick: cancel_action,
+                        { t!("cancel") }
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        onclick: apply_action,
+                        { t!("import") }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ImportPreviewGroup(
+    kind: String,
+    items: Vec<ImportDiffItem>,
+    resolution: Signal<ImportResolution>,
+) -> Element {
+    let allow_copy = kind != "workspace" && kind != "users";
+    let kind_choice = resolution.read().kinds.get(&kind).copied();
+    let choices = resolution
+        .read()
+        .items
+        .iter()
+        .map(|(k, v)| (k.clone(), *v))
+        .collect::<HashMap<_, _>>();
+
+    rsx! {
+        fieldset {
+            class: "fieldset flex flex-col p-4 border border-base-300 rounded-(--radius-box) gap-1",
+            legend {
+                class: "fieldset-legend",
+                { t!(format!("import-kind-{kind}")) }
+            }
+            ImportChoiceSelect {
+                value: kind_choice,
+                allow_copy,
+                on_change: {
+                    let kind = kind.clone();
+                    move |choice: Option<ImportChoice>| {
+                        resolution.with_mut(|r| {
+                            r.items.retain(|key, _| !key.starts_with(&format!("{kind}/")));
+                            match choice {
+                                Some(choice) => r.kinds.insert(kind.clone(), choice),
+                                None => r.kinds.remove(&kind),
+                            };
+                        })
+                    }
+                }
+            }
+            ul {
+                class: "list text-sm",
+                for item in items {
+                    li {
+                        key: "{item.id}",
+                        class: "list-row py-1 rounded-none items-center",
+                        div {
+                            class: format!("badge badge-sm {}", match item.status {
+                                ImportDiffStatus::Added => "badge-success",
+                                ImportDiffStatus::Updated => "badge-info",
+                                ImportDiffStatus::Older => "badge-warning",
+                                ImportDiffStatus::Unchanged => "badge-ghost",
+                            }),
+                            { t!(format!("import-status-{}", item.status.as_str())) }
+                        }
+                        div { class: "list-col-grow truncate font-semibold", "{item.name}" }
+                        if item.status != ImportDiffStatus::Unchanged {
+                            ImportChoiceSelect {
+                                value: choices
+                                    .get(&format!("{}/{}", item.kind, item.id))
+                                    .copied()
+                                    .or(kind_choice)
+                                    .or(Some(item.status.default_choice())),
+                                allow_copy: allow_copy && item.status != ImportDiffStatus::Added,
+                                small: true,
+                                on_change: {
+                                    let key = format!("{}/{}", item.kind, item.id);
+                                    move |choice: Option<ImportChoice>| {
+                                        resolution.with_mut(|r| {
+                                            match choice {
+                                                Some(choice) => r.items.insert(key.clone(), choice),
+                                                None => r.items.remove(&key),
+                                            };
+                                        })
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ImportChoiceSelect(
+    value: Option<ImportChoice>,
+    allow_copy: bool,
+    #[props(default)] small: bool,
+    on_change: EventHandler<Option<ImportChoice>>,
+) -> Element {
+    let mut choices = vec![ImportChoice::KeepLocal, ImportChoice::TakeIncoming];
+    if allow_copy {
+        choices.push(ImportChoice::KeepBoth);
+    }
+
+    rsx! {
+        select {
+            class: if small { "select select-sm w-48" } else { "select w-full" },
+            onchange: move |evt| {
+                let value = evt.value();
+                on_change.call((!value.is_empty()).then(|| ImportChoice::from(value)))
+            },
+            if !small {
+                option {
+                    value: "",
+                    selected: value.is_none(),
+                    { t!("import-choice-default") }
+                }
+            }
+            for choice in choices {
+                option {
+                    key: "{choice.as_str()}",
+                    value: "{choice.as_str()}",
+                    selected: value == Some(choice),
+                    { t!(choice.as_str()) }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{prelude::*, services::*};
+
+const PREVIEW_ROWS: usize = 5;
+
+#[derive(Default, Copy, Clone)]
+pub struct ImportStudentsDialog {
+    pub is_visible: Signal<bool>,
+    node: Signal<Option<String>>,
+    tree: Signal<Vec<TreeNode>>,
+    rows: Signal<Vec<Vec<String>>>,
+    on_confirm: Signal<Option<Callback<ImportStudentsSummary>>>,
+}
+
+impl ImportStudentsDialog {
+    pub fn open(
+        &mut self,
+        node: String,
+        tree: Vec<TreeNode>,
+        rows: Vec<Vec<String>>,
+        on_confirm: Option<Callback<ImportStudentsSummary>>,
+    ) {
+        self.node.set(Some(node));
+        self.tree.set(tree);
+        self.rows.set(rows);
+        self.on_confirm.set(on_confirm);
+        self.is_visible.set(true);
+    }
+
+    pub fn close(&mut self) {
+        self.node.set(None);
+        self.tree.set(vec![]);
+        self.rows.set(vec![]);
+        self.on_confirm.set(None);
+        self.is_visible.set(false);
+    }
+}
+
+pub fn use_init_import_students_dialog() -> ImportStudentsDialog {
+    use_context_provider(ImportStudentsDialog::default)
+}
+
+pub fn use_import_students_dialog() -> ImportStudentsDialog {
+    use_context()
+}
+
+#[component]
+pub fn ImportStudentsDialogContainer() -> Element {
+    let dialog = use_import_students_dialog();
+
+    if !(dialog.is_visible)() {
+        return rsx! {};
+    };
+
+    rsx! {
+        ImportStudentsForm {}
+    }
+}
+
+#[component]
+fn ImportStudentsForm() -> Element {
+    let mut dialog = use_import_students_dialog();
+    let tree = dialog.tree;
+    let rows = dialog.rows;
+    let columns = rows.read().iter().map(Vec::len).max().unwrap_or_default();
+
+    let mut header = use_signal(|| {
+        let header_name = t!("student");
+        rows.read()
+            .first()
+            .is_some_and(|row| row.iter().any(|cell| cell == &header_name))
+    });
+    let mut rank_column = use_signal(|| (columns > 1).then_some(0));
+    let mut name_column = use_signal(|| Some(if columns > 1 { 1 } else { 0 }));
+    let mut unit_column = use_signal(|| (columns > 2).then_some(2));
+    let mut node = use_signal(|| (dialog.node)().unwrap_or_default());
+    let mut summary = use_signal(|| None::<ImportStudentsSummary>);
+
+    let build_payload = move |dry_run: bool| {
+        let cell = |row: &Vec<String>, column: Option<usize>| {
+            column
+                .and_then(|idx| row.get(idx))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let students = rows
+            .read()
+            .iter()
+            .skip(if header() { 1 } else { 0 })
+            .map(|row| ImportStudentPayload {
+                rank: cell(row, rank_column()),
+                name: cell(row, name_column()).unwrap_or_default(),
+                path: cell(row, unit_column()).unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+        ImportStudentsPayload {
+            node: node(),
+            dry_run,
+            students,
+        }
+    };
+
+    let preview_action = move |evt: MouseEvent| {
+        evt.prevent_default();
+        api_fetch!(
+            POST,
+            "/api/v1/students/import",
+            build_payload(true),
+            on_success = move |body: ImportStudentsSummary| summary.set(Some(body))
+        )
+    };
+
+    let import_action = move |evt: MouseEvent| {
+        evt.prevent_default();
+        if name_column().is_none() {
+            ToastService::error(t!("missing-fields"));
+            return;
+        }
+        api_fetch!(
+            POST,
+            "/api/v1/students/import",
+            build_payload(false),
+            on_success = move |body: ImportStudentsSummary| {
+                ToastService::success(t!(
+                    "students-imported",
+                    created = body.created,
+                    updated = body.updated
+                ));
+                if let Some(on_confirm) = (dialog.on_confirm)() {
+                    on_confirm.call(body);
+                }
+                dialog.close();
+            }
+        )
+    };
+
+    let column_labels = (0..columns)
+        .map(|idx| {
+            rows.read()
+                .first()
+                .filter(|_| header())
+                .and_then(|row| row.get(idx))
+                .filter(|cell| !cell.is_empty())
+                .cloned()
+                .unwrap_or_else(|| t!("column-number", number = idx + 1))
+        })
+        .collect::<Vec<_>>();
+
+    let mut nodes = tree
+        .read()
+        .iter()
+        .map(|n| (n.id.clone(), tree.read().node_path(&n.id)))
+        .collect::<Vec<_>>();
+    nodes.sort_unstable_by(|a, b| a.1.cmp(&b.1));
+
+    let preview = build_payload(true)
+        .students
+        .into_iter()
+        .take(PREVIEW_ROWS)
+        .collect::<Vec<_>>();
+
+    rsx! {
+        dialog {
+            class: "modal modal-open",
+            div {
+                class: "modal-box flex flex-col gap-5 max-w-3xl",
+                onclick: |evt| evt.stop_propagation(),
+                h3 {
+                    class: "text-lg font-semibold text-accent",
+                    { t!("import-students") }
+                }
+
+                fieldset {
+                    class: "fieldset flex flex-col p-4 border border-base-300 rounded-(--radius-box) gap-3",
+                    legend {
+                        class: "fieldset-legend",
+                        i { class: "bi bi-layout-three-columns mr-1" }
+                        { t!("column-mapping") }
+                    }
+                    label {
+                        class: "label text-sm",
+                        input {
+                            r#type: "checkbox",
+                            class: "checkbox checkbox-sm",
+                            checked: header(),
+                            onchange: move |evt| {
+                                header.set(evt.checked());
+                                summary.set(None);
+                            }
+                        }
+                        { t!("first-row-header") }
+                    }
+                    div {
+                        class: "grid grid-cols-3 gap-3",
+                        ImportColumnSelect {
+                            label: t!("rank"),
+                            columns: column_labels.clone(),
+                            value: rank_column(),
+                            on_change: move |value| {
+                                rank_column.set(value);
+                                summary.set(None);
+                            }
+                        }
+                        ImportColumnSelect {
+                            label: t!("username"),
+                            columns: column_labels.clone(),
+                            value: name_column(),
+                            on_change: move |value| {
+                                name_column.set(value);
+                                summary.set(None);
+                            }
+                        }
+                        ImportColumnSelect {
+                            label: t!("unit"),
+                            columns: column_labels.clone(),
+                            value: unit_column(),
+                            on_change: move |value| {
+                                unit_column.set(value);
+                                summary.set(None);
+                            }
+                        }
+                    }
+                    label {
+                        class: "floating-label mt-5 lowercase",
+                        span { { t!("target-unit") } }
+                        select {
+                            class: "select w-full",
+                            onchange: move |evt| {
+                                node.set(evt.value());
+                                summary.set(None);
+                            },
+                            for (id, path) in nodes {
+                                option {
+                                    key: "{id}",
+                                    value: "{id}",
+                                    selected: id == node(),
+                                    "{path}"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                ul {
+                    class: "list text-sm",
+                    for (idx, student) in preview.into_iter().enumerate() {
+                        li {
+                            key: "{idx}",
+                            class: "list-row py-1 rounded-none",
+                            div { class: "w-24 truncate text-base-content/60", { student.rank.unwrap_or_default() } }
+                            div { class: "list-col-grow truncate font-semibold", "{student.name}" }
+                            div { class: "w-48 truncate text-base-content/60", "{student.path}" }
+                        }
+                    }
+                }
+
+                if let Some(summary) = summary() {
+                    div {
+                        class: "flex flex-wrap gap-2",
+                        div { class: "badge badge-success", { t!("import-created", count = summary.created) } }
+                        div { class: "badge badge-info", { t!("import-updated", count = summary.updated) } }
+                        div { class: "badge badge-ghost", { t!("import-skipped", count = summary.skipped) } }
+                        if summary.unresolved > 0 {
+                            div { class: "badge badge-error", { t!("import-unresolved", count = summary.unresolved) } }
+                        }
+                    }
+                }
+
+                div {
+                    class: "flex justify-end gap-2 mt-3",
+                    button {
+                        class: "btn btn-ghost",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            evt.prevent_default();
+                            dialog.close();
+                        },
+                        { t!("cancel") }
+                    }
+                    button {
+                        class: "btn btn-soft btn-info",
+                        onclick: preview_action,
+                        { t!("preview") }
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        onclick: import_action,
+                        { t!("import") }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ImportColumnSelect(
+    label: String,
+    columns: Vec<String>,
+    value: Option<usize>,
+    on_change: EventHandler<Option<usize>>,
+) -> Element {
+    rsx! {
+        label {
+            class: "floating-label mt-5 lowercase",
+            span { "{label}" }
+            select {
+                class: "select w-full",
+                onchange: move |evt| on_change.call(evt.value().parse::<usize>().ok()),
+                option {
+                    value: "",
+                    selected: value.is_none(),
+                    "—"
+                }
+                for (idx, column) in columns.into_iter().enumerate() {
+                    option {
+                        key: "{idx}",
+                        value: "{idx}",
+                        selected: value == Some(idx),
+                        "{column}"
+                    }
+                }
+            }
+        }
+    }
+}
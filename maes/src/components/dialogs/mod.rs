@@ -3,12 +3,16 @@ mod input;
 mod dialog;
 mod create_user;
 mod add_student;
+mod import_students;
+mod import_preview;
 mod file;
 
 pub use self::{
     create_workspace::*,
     create_user::*,
     add_student::*,
+    import_students::*,
+    import_preview::*,
     input::*,
     dialog::*,
     file::*,
@@ -4,6 +4,7 @@ use crate::{
     prelude::*,
     services::*,
 };
+use ::std::collections::HashMap;
 
 #[component]
 pub fn QuizEditorQuestion(
@@ -28,8 +29,11 @@ pub fn QuizEditorQuestion(
         .and_then(|c| c.questions.get(&question_id_val))
         .unwrap_or(&default_question);
 
+    let languages = quiz_guard.languages.clone();
     let mut answers = use_signal(|| question.answers.clone());
     let mut has_img = use_signal(|| question.img);
+    let mut is_open = use_signal(|| question.open.is_some());
+    let open_answer = question.open_answer();
 
     let create_action = Callback::new(move |_| {
         let id = safe_nanoid!();
@@ -51,6 +55,8 @@ pub fn QuizEditorQuestion(
         let question_id_guard = question_id.read();
         let (
             Some(name),
+            Some(difficulty),
+            Some(tags),
             Some(answer_ids),
             Some(answer_names),
             Some(answer_correct),
@@ -58,6 +64,8 @@ pub fn QuizEditorQuestion(
         ) = form_values!(
             evt,
             "name",
+            "difficulty",
+            "tags",
             ["answer_id"],
             ["answer_name"],
             ["answer_correct"],
@@ -68,18 +76,51 @@ pub fn QuizEditorQuestion(
             return;
         };
 
+        let languages = quiz.read().languages.clone();
+        let translations = languages
+            .iter()
+            .filter_map(|lang| {
+                evt.get_value(&format!("translation_{lang}"))
+                    .filter(|v| !v.is_empty())
+                    .map(|v| (lang.clone(), v))
+            })
+            .collect::<HashMap<String, String>>();
+        let answer_translations = languages
+            .iter()
+            .map(|lang| {
+                let values = evt
+                    .get_values(&format!("answer_translation_{lang}"))
+                    .unwrap_or_default();
+                (lang.clone(), values)
+            })
+            .collect::<Vec<_>>();
+
+        let open = is_open().then(|| QuizOpenAnswer {
+            strategy: QuizMatchStrategy::from(evt.get_value("match_strategy").unwrap_or_default()),
+            keywords: split_tags(evt.get_value("keywords").unwrap_or_default()),
+        });
         let correct = extract_form_checkboxes(&answer_correct);
         let answers = answer_ids
             .into_iter()
             .zip(answer_names)
             .zip(correct)
             .zip(answer_img)
-            .filter(|(((_id, name), _), img)| !name.is_empty() || img == "true")
-            .map(|(((id, name), correct), img)| QuizAnswer {
+            .enumerate()
+            .filter(|(_, (((_id, name), _), img))| !name.is_empty() || img == "true")
+            .map(|(idx, (((id, name), correct), img))| QuizAnswer {
                 id,
                 name,
                 img: img == "true",
-                correct,
+                correct: correct || open.is_some(),
+                translations: answer_translations
+                    .iter()
+                    .filter_map(|(lang, values)| {
+                        values
+                            .get(idx)
+                            .filter(|v| !v.is_empty())
+                            .map(|v| (lang.clone(), v.clone()))
+                    })
+                    .collect(),
             })
             .collect::<Vec<_>>();
 
@@ -87,7 +128,7 @@ pub fn QuizEditorQuestion(
             ToastService::error(t!("answers-count-error"));
             return;
         }
-        if !answers.iter().any(|a| a.correct) {
+        if open.is_none() && !answers.iter().any(|a| a.correct) {
             ToastService::error(t!("answer-correct-error"));
             return;
         }
@@ -103,6 +144,11 @@ pub fn QuizEditorQuestion(
             name,
             img: has_img(),
             answers,
+            translations,
+            tags: split_tags(tags),
+            difficulty: QuizDifficulty::from(difficulty),
+            open,
+            explanation: evt.get_value("explanation").unwrap_or_default(),
         };
 
         let on_success = move |body: QuizQuestion| {
@@ -116,6 +162,11 @@ pub fn QuizEditorQuestion(
                     question.name = body.name;
                     question.img = body.img;
                     question.answers = body.answers;
+                    question.translations = body.translations;
+                    question.tags = body.tags;
+                    question.difficulty = body.difficulty;
+                    question.open = body.open;
+                    question.explanation = body.explanation;
                 } else {
                     selected.set(QuizManagerAction::Question(category_id(), body.id.clone()));
                     category.questions.insert(body.id.clone(), body);
@@ -203,13 +254,27 @@ pub fn QuizEditorQuestion(
                 }
                 div {
                     class: "flex w-full gap-4",
-                    TextArea {
-                        class: "min-h-10",
-                        name: "name",
-                        required: true,
-                        minlength: 3,
-                        placeholder: t!("question-placeholder"),
-                        initial_value: "{question.name}",
+                    div {
+                        class: "flex flex-col lg:flex-row w-full gap-2",
+                        TextArea {
+                            class: "min-h-10",
+                            name: "name",
+                            required: true,
+                            minlength: 3,
+                            placeholder: t!("question-placeholder"),
+                            initial_value: "{question.name}",
+                            label: if languages.is_empty() { String::new() } else { t!("quiz-language-default") },
+                        }
+                        for lang in languages.iter() {
+                            TextArea {
+                                key: "{lang}",
+                                class: "min-h-10",
+                                name: format!("translation_{lang}"),
+                                placeholder: t!("question-placeholder"),
+                                initial_value: question.translations.get(lang).cloned().unwrap_or_default(),
+                                label: "{lang}",
+                            }
+                        }
                     }
                     if is_admin {
                         div {
@@ -231,6 +296,87 @@ pub fn QuizEditorQuestion(
                         }
                     }
                 }
+                div {
+                    class: "grid grid-cols-1 lg:grid-cols-3 w-full gap-2",
+                    Select {
+                        name: "difficulty",
+                        label: rsx! { span { i { class: "bi bi-speedometer2 mr-1" } { t!("difficulty") } } },
+                        for difficulty in [QuizDifficulty::Easy, QuizDifficulty::Medium, QuizDifficulty::Hard] {
+                            option {
+                                key: "{difficulty}",
+                                selected: difficulty == question.difficulty,
+                                value: "{difficulty}",
+                                { t!(format!("difficulty-{difficulty}")) }
+                            }
+                        }
+                    }
+                    TextInputComponent {
+                        class: "mt-5 lg:col-span-2",
+                        name: "tags",
+                        maxlength: 200,
+                        placeholder: t!("tags-placeholder"),
+                        initial_value: question.tags.join(", "),
+                        label: rsx! { span { i { class: "bi bi-tags mr-1" } { t!("tags") } } },
+                    }
+                }
+            }
+
+            fieldset {
+                class: "fieldset p-2",
+                legend {
+                    class: "fieldset-legend text-sm text-primary",
+                    i { class: "bi bi-input-cursor-text" }
+                    { t!("quiz-open-answer-settings") }
+                    input {
+                        r#type: "checkbox",
+                        class: "toggle toggle-sm checked:toggle-accent ml-2",
+                        checked: is_open(),
+                        onchange: move |evt| is_open.set(evt.checked()),
+                    }
+                }
+                if is_open() {
+                    div {
+                        class: "grid grid-cols-1 lg:grid-cols-3 w-full gap-2",
+                        Select {
+                            name: "match_strategy",
+                            label: rsx! { span { i { class: "bi bi-bullseye mr-1" } { t!("match-strategy") } } },
+                            for strategy in [QuizMatchStrategy::Exact, QuizMatchStrategy::Fuzzy, QuizMatchStrategy::Semantic, QuizMatchStrategy::Combined] {
+                                option {
+                                    key: "{strategy}",
+                                    selected: strategy == open_answer.strategy,
+                                    value: "{strategy}",
+                                    { t!(format!("match-strategy-{strategy}")) }
+                                }
+                            }
+                        }
+                        TextInputComponent {
+                            class: "mt-5 lg:col-span-2",
+                            name: "keywords",
+                            maxlength: 200,
+                            placeholder: t!("keywords-placeholder"),
+                            initial_value: open_answer.keywords.join(", "),
+                            label: rsx! { span { i { class: "bi bi-key mr-1" } { t!("required-keywords") } } },
+                        }
+                    }
+                }
+            }
+
+            fieldset {
+                class: "fieldset p-2",
+                legend {
+                    class: "fieldset-legend text-sm text-primary",
+                    i { class: "bi bi-lightbulb" }
+                    { t!("explanation") }
+                }
+                TextArea {
+                    class: "min-h-10",
+                    name: "explanation",
+                    required: false,
+                    minlength: 0,
+                    maxlength: 3000,
+                    placeholder: t!("explanation-placeholder"),
+                    initial_value: "{question.explanation}",
+                }
             }
 
             fieldset {
@@ -262,7 +408,7 @@ pub fn QuizEditorQuestion(
                             input { r#type: "hidden", name: "answer_id", value: "{id}" }
                                 input {
                                     r#type: "checkbox",
-                                    class: "checkbox checked:checkbox-success rounded-sm",
+                                    class: if is_open() { "hidden" } else { "checkbox checked:checkbox-success rounded-sm" },
                                     name: "answer_correct",
                                     value: true,
                                     initial_checked: answer.correct
@@ -281,13 +427,25 @@ pub fn QuizEditorQuestion(
                                         }
                                     }
                                 }
-                                TextArea {
-                                    class: "min-h-10",
-                                    name: "answer_name",
-                                    required: false,
-                                    minlength: 0,
-                                    placeholder: t!("answer-placeholder"),
-                                    initial_value: "{answer.name}",
+                                div {
+                                    class: "flex flex-col lg:flex-row w-full gap-2",
+                                    TextArea {
+                                        class: "min-h-10",
+                                        name: "answer_name",
+                                        required: false,
+                                        minlength: 0,
+                                        placeholder: t!("answer-placeholder"),
+                                        initial_value: "{answer.name}",
+                                    }
+                                    for lang in languages.iter() {
+                                        TextArea {
+                                            key: "{id}{lang}",
+                                            class: "min-h-10",
+                                            name: format!("answer_translation_{lang}"),
+                                            placeholder: t!("answer-placeholder"),
+                                            initial_value: answer.translations.get(lang).cloned().unwrap_or_default(),
+                                        }
+                                    }
                                 }
                             }
                             if is_admin {
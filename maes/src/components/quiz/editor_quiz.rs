@@ -1,4 +1,5 @@
 use crate::{components::inputs::*, prelude::*, services::*};
+use ::std::collections::HashSet;
 
 #[component]
 pub fn QuizEditorQuiz() -> Element {
@@ -24,6 +25,7 @@ pub fn QuizEditorQuiz() -> Element {
             Some(grade_b),
             Some(grade_c),
             Some(grade_similarity),
+            Some(languages),
         ) = form_values!(
             evt,
             "name",
@@ -32,12 +34,19 @@ pub fn QuizEditorQuiz() -> Element {
             "grade_a",
             "grade_b",
             "grade_c",
-            "grade_similarity"
+            "grade_similarity",
+            "languages"
         )
         else {
             ToastService::error(t!("missing-fields"));
             return;
         };
+        let mut seen = HashSet::new();
+        let languages = languages
+            .split(',')
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty() && seen.insert(l.clone()))
+            .collect::<Vec<_>>();
         api_fetch!(
             PATCH,
             format!("/api/v1/manager/quizzes/{quiz_id}", quiz_id = quiz_guard.id),
@@ -52,6 +61,7 @@ pub fn QuizEditorQuiz() -> Element {
                     c: grade_c.parse::<usize>().unwrap_or(25),
                     similarity: grade_similarity.parse::<usize>().unwrap_or(75),
                 },
+                languages,
                 categories: vec![],
             },
             on_success = move |body: Quiz| {
@@ -61,6 +71,7 @@ pub fn QuizEditorQuiz() -> Element {
                     q.attempts = body.attempts;
                     q.duration = body.duration;
                     q.grade = body.grade;
+                    q.languages = body.languages;
                 });
                 ToastService::success(t!("saved"))
             },
@@ -267,6 +278,21 @@ pub fn QuizEditorQuiz() -> Element {
                     }
                 }
             }
+            fieldset {
+                class: "fieldset p-2 text-sm",
+                legend {
+                    class: "fieldset-legend text-sm text-primary",
+                    i { class: "bi bi-translate" }
+                    { t!("quiz-languages-settings") }
+                }
+                TextInputComponent {
+                    class: "w-full",
+                    name: "languages",
+                    maxlength: 100,
+                    placeholder: t!("quiz-languages-placeholder"),
+                    initial_value: quiz_guard.languages.join(", "),
+                }
+            }
             fieldset {
                 //                class: "fieldset p-4 border border-base-300 text-sm rounded-(--radius-box)",
                 class: "fieldset p-2 text-sm",
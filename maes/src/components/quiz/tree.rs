@@ -66,6 +66,7 @@ pub fn QuizTree() -> Element {
                 attempts: quiz_guard.attempts,
                 duration: quiz_guard.duration,
                 grade: quiz_guard.grade.clone(),
+                languages: quiz_guard.languages.clone(),
                 categories: categories.clone(),
             },
             on_success = move |_body: Quiz| {
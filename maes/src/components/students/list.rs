@@ -5,7 +5,9 @@ use ::std::collections::HashSet;
 pub fn StudentsList() -> Element {
     let selected = use_context::<Signal<SelectedItem>>();
     let mut students = use_context_provider(|| Signal::new(Vec::<Student>::new()));
+    let tree = use_context::<Signal<Vec<TreeNode>>>();
     let mut add_student_dialog = use_context::<AddStudentDialog>();
+    let mut import_students_dialog = use_context::<ImportStudentsDialog>();
 
     use_effect(move || {
         api_fetch!(
@@ -37,6 +39,40 @@ pub fn StudentsList() -> Element {
         );
     });
 
+    let import_action = Callback::new(move |_| {
+        if selected.read().id.is_empty() {
+            ToastService::error(t!("select-node-first"));
+            return;
+        }
+        import_roster_dialog(Callback::new(move |rows: Vec<Vec<String>>| {
+            import_students_dialog.open(
+                selected.read().id.clone(),
+                tree(),
+                rows,
+                Some(Callback::new(move |_| {
+                    api_fetch!(
+                        GET,
+                        format!("/api/v1/students/{node}", node = selected.read().id),
+                        on_success = move |body: Vec<Student>| students.set(body),
+                    );
+                })),
+            )
+        }))
+    });
+
+    let export_action = Callback::new(move |_| {
+        let tree_guard = tree.read();
+        let mut rows = vec![vec![t!("rank"), t!("student"), t!("unit")]];
+        rows.extend(students.read().iter().map(|s| {
+            vec![
+                s.rank.clone().unwrap_or_default(),
+                s.name.clone(),
+                tree_guard.node_path(&s.node),
+            ]
+        }));
+        export_roster_dialog(rows)
+    });
+
     let clear_action = Callback::new(move |_| {
         let callback = Callback::new(move |_| {
             api_call!(
@@ -85,6 +121,20 @@ pub fn StudentsList() -> Element {
                         i { class: "bi bi-clipboard" }
                     }
                 }
+                li {
+                    button {
+                        class: "hover:text-info",
+                        onclick: import_action,
+                        i { class: "bi bi-file-earmark-arrow-up" }
+                    }
+                }
+                li {
+                    button {
+                        class: "hover:text-info",
+                        onclick: export_action,
+                        i { class: "bi bi-file-earmark-arrow-down" }
+                    }
+                }
                 li {
                     button {
                         class: "hover:text-error",
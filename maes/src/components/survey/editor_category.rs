@@ -18,6 +18,28 @@ pub fn SurveyEditorCategory(category_id: ReadSignal<String>) -> Element {
 
     let mut answers = use_signal(|| category.answers.clone());
     let mut questions = use_signal(|| category.questions.clone());
+    let mut kind = use_signal(|| category.kind);
+    let mut conditions = use_signal(|| category.conditions.clone());
+
+    // Conditions may only refer to choice categories shown earlier.
+    let condition_options = survey_guard
+        .categories
+        .values()
+        .take_while(|c| c.id != *category_id.read())
+        .filter(|c| c.kind == SurveyCategoryKind::Choice)
+        .flat_map(|c| {
+            let items = if c.answers.is_empty() { &c.questions } else { &c.answers };
+            items.values().map(|item| {
+                (
+                    SurveyCondition {
+                        category: c.id.clone(),
+                        option: item.id.clone(),
+                    },
+                    format!("{}: {}", c.name, item.name),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
 
     let create_answer_action = Callback::new(move |_| {
         let count = if answers.read().is_empty() { 2 } else { 1 };
@@ -93,11 +115,31 @@ pub fn SurveyEditorCategory(category_id: ReadSignal<String>) -> Element {
             survey_id, category_id_guard
         );
 
+        let kind = SurveyCategoryKind::from(evt.get_value("kind").unwrap_or_default());
+        let (min, max) = match kind {
+            SurveyCategoryKind::Numeric | SurveyCategoryKind::Scale => {
+                (evt.get_parsed_value::<i64>("min"), evt.get_parsed_value::<i64>("max"))
+            }
+            _ => (None, None),
+        };
+
         let payload = UpdateSurveyCategoryPayload {
             name,
             order: order.parse::<usize>().unwrap_or(0),
             answers,
             questions,
+            kind,
+            min,
+            max,
+            conditions: conditions
+                .read()
+                .iter()
+                .filter(|c| !c.option.is_empty())
+                .cloned()
+                .collect(),
+            required: evt.get_value("required").is_some(),
+            max_selections: evt.get_parsed_value::<usize>("max_selections").filter(|&max| max > 0),
+            segment: evt.get_value("segment").is_some(),
         };
 
         let on_success = move |body: SurveyCategory| {
@@ -113,6 +155,13 @@ pub fn SurveyEditorCategory(category_id: ReadSignal<String>) -> Element {
                     category.order = body.order;
                     category.answers = body.answers;
                     category.questions = body.questions;
+                    category.kind = body.kind;
+                    category.min = body.min;
+                    category.max = body.max;
+                    category.conditions = body.conditions;
+                    category.required = body.required;
+                    category.max_selections = body.max_selections;
+                    category.segment = body.segment;
                 }
             });
             ToastService::success(t!("saved"));
@@ -190,10 +239,154 @@ pub fn SurveyEditorCategory(category_id: ReadSignal<String>) -> Element {
                     }
                     div { { t!("sort-order") } }
                 }
+                select {
+                    class: "select w-full mt-2",
+                    name: "kind",
+                    onchange: move |evt| kind.set(SurveyCategoryKind::from(evt.value())),
+                    for value in [SurveyCategoryKind::Choice, SurveyCategoryKind::Text, SurveyCategoryKind::Numeric, SurveyCategoryKind::Scale, SurveyCategoryKind::Ranking] {
+                        option {
+                            key: "{value}",
+                            value: "{value}",
+                            selected: value == kind(),
+                            { t!(format!("survey-kind-{value}")) }
+                        }
+                    }
+                }
+                div {
+                    class: "text-xs text-base-content/60",
+                    { t!(format!("survey-kind-{kind}-hint", kind = kind())) }
+                }
+                if matches!(kind(), SurveyCategoryKind::Numeric | SurveyCategoryKind::Scale) {
+                    div {
+                        class: "grid grid-cols-[max-content_1fr_max-content_1fr] mt-2 gap-4 text-sm items-center",
+                        div { { t!("min-value") } }
+                        TextInputComponent {
+                            class: "text-base-content min-w-10",
+                            r#type: "number",
+                            name: "min",
+                            min: -1_000_000,
+                            max: 1_000_000,
+                            required: kind() == SurveyCategoryKind::Scale,
+                            initial_value: category.min.map(|v| v.to_string()).unwrap_or_else(|| if kind() == SurveyCategoryKind::Scale { "1".into() } else { String::new() }),
+                        }
+                        div { { t!("max-value") } }
+                        TextInputComponent {
+                            class: "text-base-content min-w-10",
+                            r#type: "number",
+                            name: "max",
+                            min: -1_000_000,
+                            max: 1_000_000,
+                            required: kind() == SurveyCategoryKind::Scale,
+                            initial_value: category.max.map(|v| v.to_string()).unwrap_or_else(|| if kind() == SurveyCategoryKind::Scale { "5".into() } else { String::new() }),
+                        }
+                    }
+                }
+                if kind() == SurveyCategoryKind::Choice && answers.read().is_empty() {
+                    div {
+                        class: "grid grid-cols-[max-content_1fr] mt-2 gap-4 text-sm items-center",
+                        div {
+                            TextInputComponent {
+                                class: "text-base-content min-w-10",
+                                r#type: "number",
+                                name: "max_selections",
+                                min: 0,
+                                max: 30,
+                                initial_value: category.max_selections.map(|v| v.to_string()).unwrap_or_default(),
+                            }
+                        }
+                        div { { t!("max-selections") } }
+                    }
+                }
+                label {
+                    class: "label text-sm mt-2",
+                    input {
+                        r#type: "checkbox",
+                        class: "toggle toggle-sm checked:toggle-accent",
+                        name: "required",
+                        checked: category.required,
+                    }
+                    { t!("required-category") }
+                }
+                if kind() == SurveyCategoryKind::Choice && questions.read().is_empty() {
+                    label {
+                        class: "label text-sm mt-2",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-sm checked:toggle-accent",
+                            name: "segment",
+                            checked: category.segment,
+                        }
+                        { t!("segment-dimension") }
+                    }
+                    div {
+                        class: "text-xs text-base-content/60",
+                        { t!("segment-dimension-hint") }
+                    }
+                }
             }
 
             fieldset {
                 class: "fieldset p-2",
+                legend {
+                    class: "fieldset-legend text-sm text-primary",
+                    i { class: "bi bi-signpost-split" }
+                    { t!("display-conditions") }
+                    if is_admin && !condition_options.is_empty() {
+                        button {
+                            class: "btn btn-xs ml-2",
+                            onclick: move |event| {
+                                event.stop_propagation();
+                                event.prevent_default();
+                                conditions.write().push(SurveyCondition::default())
+                            },
+                            i { class: "bi bi-plus-lg" }
+                        }
+                    }
+                }
+                div {
+                    class: "text-xs text-base-content/60",
+                    { t!("display-conditions-hint") }
+                }
+                for (idx, condition) in conditions.read().iter().enumerate() {
+                    div {
+                        key: "{idx}{condition.option}",
+                        class: "flex w-full gap-2 items-center group",
+                        select {
+                            class: "select w-full",
+                            onchange: move |evt| {
+                                let value = evt.value();
+                                let (category, option) = value.split_once('/').unwrap_or_default();
+                                if let Some(c) = conditions.write().get_mut(idx) {
+                                    c.category = category.to_string();
+                                    c.option = option.to_string();
+                                }
+                            },
+                            option { value: "", disabled: true, selected: condition.option.is_empty(), { t!("select-option") } }
+                            for (value, label) in condition_options.iter() {
+                                option {
+                                    key: "{value.category}/{value.option}",
+                                    value: "{value.category}/{value.option}",
+                                    selected: value == condition,
+                                    "{label}"
+                                }
+                            }
+                        }
+                        if is_admin {
+                            button {
+                                class: "btn hover:btn-error btn-square",
+                                onclick: move |evt| {
+                                    evt.prevent_default();
+                                    conditions.write().remove(idx);
+                                },
+                                i { class: "bi bi-trash text-lg" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            fieldset {
+                class: format!("fieldset p-2 {class}", class = if kind() == SurveyCategoryKind::Choice { "" } else { "hidden" }),
                 legend {
                     class: "fieldset-legend text-sm text-primary",
                     i { class: "bi bi-ui-checks" }
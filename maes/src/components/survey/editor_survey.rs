@@ -23,11 +23,13 @@ pub fn SurveyEditorSurvey() -> Element {
                 name,
                 node: survey_guard.node.clone(),
                 categories: vec![],
+                min_segment_size: evt.get_parsed_value::<usize>("min_segment_size"),
             },
             on_success = move |body: Survey| {
                 survey.with_mut(|s| {
                     s.name = body.name;
                     s.node = body.node;
+                    s.min_segment_size = body.min_segment_size;
                 });
                 ToastService::success(t!("saved"))
             },
@@ -92,6 +94,20 @@ pub fn SurveyEditorSurvey() -> Element {
                     placeholder: t!("survey-placeholder"),
                     initial_value: "{survey_guard.name}",
                 }
+                div {
+                    class: "grid grid-cols-[max-content_1fr] mt-2 gap-4 text-sm items-center",
+                    div {
+                        TextInputComponent {
+                            class: "text-lg text-base-content min-w-10",
+                            r#type: "number",
+                            name: "min_segment_size",
+                            min: 1,
+                            max: 100,
+                            initial_value: survey_guard.min_segment_size.unwrap_or(DEFAULT_MIN_SEGMENT_SIZE).to_string(),
+                        }
+                    }
+                    div { { t!("min-segment-size") } }
+                }
             }
         }
     }
@@ -54,6 +54,7 @@ pub fn SurveyTree() -> Element {
                 name: survey_guard.name.clone(),
                 node: survey_guard.node.clone(),
                 categories: categories.clone(),
+                min_segment_size: None,
             },
             on_success = move |_body: Survey| {
                 survey.with_mut(|q| {
@@ -3,6 +3,7 @@ use crate::{
     prelude::*,
     window::*,
 };
+use ::chrono::Utc;
 use ::std::time::Duration;
 
 #[component]
@@ -56,10 +57,28 @@ fn RenderTaskItem(task: ReadSignal<Task>) -> Element {
     let mut kind = use_context::<Signal<EntityKind>>();
     let mut selected = use_context::<Signal<SelectedItem>>();
     let mut tasks = use_context::<Signal<Vec<Task>>>();
+    let mut now = use_signal(|| Utc::now().timestamp());
     let task_guard = task.read();
 
     let is_selected = task_guard.id == selected.read().id;
 
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            now.set(Utc::now().timestamp())
+        }
+    });
+
+    let schedule = match task_guard.status(now()) {
+        TaskStatus::Scheduled => task_guard.opens_at.map(|opens_at| {
+            ("badge-info", t!("opens-in", time = format_countdown(opens_at - now())))
+        }),
+        TaskStatus::Open => task_guard.closes_at.map(|closes_at| {
+            ("badge-warning", t!("closes-in", time = format_countdown(closes_at - now())))
+        }),
+        TaskStatus::Closed => Some(("badge-error", t!("task-status-closed"))),
+    };
+
     let delete_action = {
         let callback = Callback::new(move |_| {
             let task_guard = task.peek();
@@ -158,6 +177,42 @@ fn RenderTaskItem(task: ReadSignal<Task>) -> Element {
         }
     });
 
+    let variants_report_action = Callback::new(move |_| {
+        WindowManager::open_window(
+            t!("quiz-variants-title"),
+            WindowKind::QuizVariants {
+                task: task.read().id.clone(),
+            },
+        )
+    });
+
+    let variant_entry_action = Callback::new(move |_| {
+        WindowManager::open_window(
+            t!("quiz-variant-entry-title"),
+            WindowKind::QuizVariantEntry {
+                task: task.read().id.clone(),
+            },
+        )
+    });
+
+    let review_action = Callback::new(move |_| {
+        WindowManager::open_window(
+            t!("quiz-review-title"),
+            WindowKind::QuizReview {
+                task: task.read().id.clone(),
+            },
+        )
+    });
+
+    let regrade_action = Callback::new(move |_| {
+        WindowManager::open_window(
+            t!("quiz-regrade-title"),
+            WindowKind::QuizRegrade {
+                task: task.read().id.clone(),
+            },
+        )
+    });
+
     let dummy_callback = Callback::new(move |evt: MouseEvent| {
         evt.prevent_default();
         evt.stop_propagation();
@@ -170,6 +225,10 @@ fn RenderTaskItem(task: ReadSignal<Task>) -> Element {
                 (t!("finish"), "bi bi-flag", finish_action, is_report_action_disabled, true),
                 (t!("report"), "bi bi-file-earmark-text", report_action, is_report_action_disabled),
                 (t!("quiz-tickets"), "bi bi-ticket", tickets_report_action),
+                (t!("quiz-variants"), "bi bi-journal-text", variants_report_action),
+                (t!("quiz-variant-entry"), "bi bi-ui-radios-grid", variant_entry_action),
+                (t!("quiz-review"), "bi bi-clipboard-check", review_action, is_report_action_disabled),
+                (t!("quiz-regrade"), "bi bi-arrow-repeat", regrade_action, is_report_action_disabled),
                 (t!("instruction"), "bi bi-wifi", wifi_report_action, false, true),
                 (t!("delete"), "bi bi-trash", delete_action),
             ])
@@ -214,6 +273,12 @@ fn RenderTaskItem(task: ReadSignal<Task>) -> Element {
                 class: "flex flex-col justify-center my-3 gap-1",
                 div { class: "font-semibold", "{task_guard.name}" }
                 div { class: "text-xs text-base-content/60", "{task_guard.path}" }
+                if task_guard.practice {
+                    div { class: "badge badge-sm badge-accent", { t!("practice") } }
+                }
+                if let Some((class, text)) = schedule {
+                    div { class: "badge badge-sm {class}", "{text}" }
+                }
             }
             div {
                 class: "flex items-center justify-center",
@@ -3,8 +3,14 @@ mod inspector;
 mod quiz_inspector;
 mod survey_inspector;
 mod cards;
+mod variant_entry;
+mod review;
+mod regrade;
 
 pub use self::{
     list::*,
     inspector::*,
+    variant_entry::*,
+    review::*,
+    regrade::*,
 };
\ No newline at end of file
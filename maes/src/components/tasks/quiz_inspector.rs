@@ -1,7 +1,9 @@
 use super::cards::*;
-use crate::{prelude::*, components::widgets::*, window::*};
+use crate::{prelude::*, components::{dialogs::*, widgets::*}, services::*, window::*};
 use ::shared::models::*;
-use ::std::time::Duration;
+use ::std::{collections::HashMap, time::Duration};
+
+const EXTRA_TIME_STEP: i64 = 300;
 
 #[component]
 pub fn QuizInspector() -> Element {
@@ -11,6 +13,37 @@ pub fn QuizInspector() -> Element {
     let mut quiz = use_context_provider(|| Signal::new(QuizRecord::default()));
     let mut search_pattern = use_signal(String::new);
     let mut pinned = use_signal(|| false);
+    let mut adding = use_signal(|| false);
+    let mut roster = use_signal(Vec::<Student>::new);
+
+    use_effect(move || {
+        if !adding() {
+            return;
+        }
+        api_fetch!(
+            GET,
+            "/api/v1/students",
+            on_success = move |body: Vec<Student>| roster.set(body)
+        );
+    });
+
+    let add_action = move |student_id: String| {
+        api_fetch!(
+            POST,
+            format!("/api/v1/tasks/students/{id}", id = quiz.peek().id),
+            AddQuizStudentsPayload {
+                students: vec![student_id],
+            },
+            on_success = move |body: Vec<QuizRecordStudent>| {
+                quiz.with_mut(|quiz| {
+                    for student in body {
+                        quiz.students.insert(student.id.clone(), student);
+                    }
+                });
+                ToastService::success(t!("saved"))
+            }
+        );
+    };
 
     use_future(move || async move {
         loop {
@@ -61,7 +94,7 @@ pub fn QuizInspector() -> Element {
             }
         }
         div {
-            class: "flex shrink-0 px-3 py-4",
+            class: "flex shrink-0 px-3 py-4 items-center",
             label {
                 class: "w-full input input-sm items-center gap-2",
                 input {
@@ -75,21 +108,67 @@ pub fn QuizInspector() -> Element {
                 }
                 i { class: "bi bi-search bg-base-100/0 relative -right-0" }
             }
+            div {
+                class: "tooltip tooltip-left",
+                "data-tip": t!("add-student"),
+                button {
+                    class: format!(
+                        "btn btn-sm btn-ghost btn-square ml-1 {class}",
+                        class = if adding() { "text-success" } else { "hover:text-success" }
+                    ),
+                    onclick: move |_| adding.set(!adding()),
+                    i { class: "bi bi-person-plus text-lg" }
+                }
+            }
         }
+        RenderReviewSettings {}
         div {
             class: "flex-scrollable",
             ul {
                 class: "list w-full",
-                {
-                    let pat = search_pattern.read().to_lowercase();
-                    let quiz_guard = quiz.read();
-                    rsx! {
-                        for s in quiz_guard
-                            .students
-                            .values()
-                            .filter(|s| s.name.to_lowercase().contains(&pat))
-                        {
-                            RenderStudentItem { key: "{s.id}", student: s.clone() }
+                if adding() {
+                    {
+                        let pat = search_pattern.read().to_lowercase();
+                        let quiz_guard = quiz.read();
+                        rsx! {
+                            for s in roster
+                                .read()
+                                .iter()
+                                .filter(|s| !quiz_guard.students.contains_key(&s.id))
+                                .filter(|s| s.name.to_lowercase().contains(&pat))
+                                .cloned()
+                            {
+                                li {
+                                    key: "{s.id}",
+                                    class: "list-row rounded-none px-4 py-3 cursor-pointer hover:bg-base-200",
+                                    onclick: {
+                                        let id = s.id.clone();
+                                        move |_| add_action(id.clone())
+                                    },
+                                    div {
+                                        class: "list-col-grow flex flex-col justify-center gap-1",
+                                        div { class: "font-semibold", "{s.name}" }
+                                        if let Some(rank) = s.rank.clone() {
+                                            div { class: "text-xs text-base-content/60", "{rank}" }
+                                        }
+                                    }
+                                    i { class: "bi bi-plus-lg text-success" }
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    {
+                        let pat = search_pattern.read().to_lowercase();
+                        let quiz_guard = quiz.read();
+                        rsx! {
+                            for s in quiz_guard
+                                .students
+                                .values()
+                                .filter(|s| s.name.to_lowercase().contains(&pat))
+                            {
+                                RenderStudentItem { key: "{s.id}", student: s.clone() }
+                            }
                         }
                     }
                 }
@@ -100,11 +179,121 @@ pub fn QuizInspector() -> Element {
 
 #[component]
 fn RenderStudentItem(student: ReadSignal<QuizRecordStudent>) -> Element {
-    let quiz = use_context::<Signal<QuizRecord>>();
+    let mut quiz = use_context::<Signal<QuizRecord>>();
     let student_guard = student.read();
     let mut active = use_context::<Signal<QuizRecordStudent>>();
+    let mut editing = use_signal(|| false);
+    let mut context_menu = use_context_menu();
+    let mut dialog = use_dialog();
     let is_active = student_guard.id == active.read().id;
 
+    let mut update_action = move |payload: UpdateQuizStudentPayload| {
+        api_fetch!(
+            PATCH,
+            format!(
+                "/api/v1/tasks/students/{id}/{student_id}",
+                id = quiz.peek().id,
+                student_id = student.peek().id
+            ),
+            payload,
+            on_success = move |body: QuizRecordStudent| {
+                quiz.with_mut(|quiz| {
+                    if let Some(student) = quiz.students.get_mut(&body.id) {
+                        *student = body;
+                    }
+                });
+                ToastService::success(t!("saved"))
+            }
+        );
+    };
+
+    let reset_action = {
+        let callback = Callback::new(move |_| {
+            update_action(UpdateQuizStudentPayload {
+                reset: true,
+                ..Default::default()
+            })
+        });
+        Callback::new(move |_| {
+            let name = student.peek().name.clone();
+            dialog.warning(t!("reset-attempts-message", name = name), Some(callback))
+        })
+    };
+
+    let attempt_action = Callback::new(move |_| {
+        update_action(UpdateQuizStudentPayload {
+            extra_attempts: Some(student.peek().extra_attempts + 1),
+            ..Default::default()
+        })
+    });
+
+    let time_action = Callback::new(move |_| {
+        update_action(UpdateQuizStudentPayload {
+            extra_time: Some(student.peek().extra_time + EXTRA_TIME_STEP),
+            ..Default::default()
+        })
+    });
+
+    let excuse_action = Callback::new(move |_| {
+        update_action(UpdateQuizStudentPayload {
+            excused: Some(!student.peek().excused),
+            ..Default::default()
+        })
+    });
+
+    let remove_action = {
+        let callback = Callback::new(move |_| {
+            let student_id = student.peek().id.clone();
+            api_call!(
+                DELETE,
+                format!("/api/v1/tasks/students/{id}/{student_id}", id = quiz.peek().id),
+                on_success = move || {
+                    quiz.with_mut(|quiz| {
+                        quiz.students.shift_remove(&student_id);
+                    });
+                },
+            )
+        });
+        Callback::new(move |_| {
+            let name = student.peek().name.clone();
+            dialog.warning(t!("remove-student-message", name = name), Some(callback))
+        })
+    };
+
+    let ctx_menu = make_ctx_menu!(context_menu, [
+        (t!("reset-attempts"), "bi bi-arrow-counterclockwise", reset_action),
+        (t!("grant-attempt"), "bi bi-plus-circle", attempt_action),
+        (t!("grant-time"), "bi bi-stopwatch", time_action),
+        (
+            t!(if student_guard.excused { "cancel-excuse" } else { "excuse" }),
+            "bi bi-person-slash",
+            excuse_action,
+            false,
+            true
+        ),
+        (t!("delete"), "bi bi-trash", remove_action),
+    ]);
+
+    let mut deadline_action = move |deadline: Option<i64>| {
+        let student_id = student.peek().id.clone();
+        api_call!(
+            PATCH,
+            format!("/api/v1/tasks/deadlines/{id}", id = quiz.peek().id),
+            UpdateQuizDeadlinesPayload {
+                deadlines: HashMap::from([(student_id.clone(), deadline)]),
+            },
+            on_success = move || {
+                quiz.with_mut(|quiz| {
+                    if let Some(student) = quiz.students.get_mut(&student_id) {
+                        student.deadline = deadline;
+                    }
+                });
+                editing.set(false);
+                ToastService::success(t!("saved"))
+            }
+        );
+    };
+
     rsx! {
         li {
             class: format!(
@@ -112,16 +301,88 @@ fn RenderStudentItem(student: ReadSignal<QuizRecordStudent>) -> Element {
                 class = if is_active { "bg-base-300" } else { "" }
             ),
             onclick: move |_| active.set(student()),
+            oncontextmenu: ctx_menu,
             div {
                 class: "list-col-grow flex flex-col justify-center pl-4 my-3 gap-1",
                 div { class: "font-semibold", "{student_guard.name}" }
                 if let Some(rank) = student_guard.rank.clone() {
                     div { class: "text-xs text-base-content/60", "{rank}" }
                 }
+                if student_guard.excused || student_guard.extra_attempts > 0 || student_guard.extra_time > 0 {
+                    div {
+                        class: "flex flex-wrap gap-1",
+                        if student_guard.excused {
+                            div { class: "badge badge-xs badge-neutral", { t!("excused") } }
+                        }
+                        if student_guard.extra_attempts > 0 {
+                            div {
+                                class: "badge badge-xs badge-info",
+                                i { class: "bi bi-plus-circle" }
+                                "{student_guard.extra_attempts}"
+                            }
+                        }
+                        if student_guard.extra_time > 0 {
+                            div {
+                                class: "badge badge-xs badge-info",
+                                i { class: "bi bi-stopwatch" }
+                                { format!("+{}:{:02}", student_guard.extra_time / 60, student_guard.extra_time % 60) }
+                            }
+                        }
+                    }
+                }
+                if editing() {
+                    div {
+                        class: "flex flex-nowrap gap-1 items-center",
+                        onclick: move |evt| evt.stop_propagation(),
+                        input {
+                            class: "input input-xs",
+                            r#type: "datetime-local",
+                            value: student_guard.deadline.map(format_datetime_local).unwrap_or_default(),
+                            onchange: move |evt| {
+                                if let Some(deadline) = parse_datetime_local(evt.value()) {
+                                    deadline_action(Some(deadline))
+                                }
+                            }
+                        }
+                        button {
+                            class: "btn btn-xs btn-ghost btn-square hover:text-error",
+                            onclick: move |_| deadline_action(None),
+                            i { class: "bi bi-x-lg" }
+                        }
+                    }
+                } else if let Some(deadline) = student_guard.deadline {
+                    div {
+                        class: "text-xs text-base-content/60",
+                        i { class: "bi bi-alarm mr-1" }
+                        { format_datetime_local(deadline).replace('T', " ") }
+                    }
+                }
+            }
+            div {
+                class: "hidden group-hover:flex items-center justify-center w-12 cursor-pointer text-xl",
+                class: "hover:bg-primary hover:text-primary-content",
+                title: t!("deadline"),
+                onclick: move |evt| {
+                    evt.stop_propagation();
+                    editing.set(!editing())
+                },
+                i { class: "bi bi-alarm" }
             }
             div {
                 class: "flex group-hover:hidden items-center justify-center pr-4",
-                Rating { grade: student_guard.grade }
+                if quiz.read().practice {
+                    div {
+                        class: "badge badge-sm badge-ghost",
+                        title: t!("practice-attempts"),
+                        i { class: "bi bi-repeat" }
+                        {
+                            let score = student_guard.practice.last().map_or(0, |p| p.score);
+                            format!("{} · {score}%", student_guard.practice.len())
+                        }
+                    }
+                } else {
+                    Rating { grade: student_guard.final_grade() }
+                }
             }
             div {
                 class: "hidden group-hover:flex items-center justify-center w-12 cursor-pointer text-xl",
@@ -143,3 +404,71 @@ fn RenderStudentItem(student: ReadSignal<QuizRecordStudent>) -> Element {
         }
     }
 }
+
+#[component]
+fn RenderReviewSettings() -> Element {
+    let mut quiz = use_context::<Signal<QuizRecord>>();
+    let review = quiz.read().review.clone();
+
+    let update_action = move |review: QuizReviewSettings| {
+        api_call!(
+            PATCH,
+            format!("/api/v1/tasks/review-settings/{id}", id = quiz.peek().id),
+            UpdateQuizReviewPayload {
+                review: review.clone(),
+            },
+            on_success = move || {
+                quiz.with_mut(|quiz| quiz.review = review.clone());
+                ToastService::success(t!("saved"))
+            }
+        );
+    };
+
+    rsx! {
+        div {
+            class: "flex shrink-0 px-3 pb-3 gap-2 items-center",
+            div {
+                class: "tooltip tooltip-bottom w-full",
+                "data-tip": t!("review-mode"),
+                select {
+                    class: "select select-sm w-full",
+                    onchange: {
+                        let review = review.clone();
+                        move |evt: FormEvent| {
+                            update_action(QuizReviewSettings {
+                                mode: QuizReviewMode::from(evt.value()),
+                                ..review.clone()
+                            })
+                        }
+                    },
+                    for mode in [QuizReviewMode::Hidden, QuizReviewMode::Score, QuizReviewMode::Categories, QuizReviewMode::Answers] {
+                        option {
+                            key: "{mode}",
+                            value: "{mode}",
+                            selected: mode == review.mode,
+                            { t!(format!("review-mode-{mode}")) }
+                        }
+                    }
+                }
+            }
+            label {
+                class: "label text-sm shrink-0",
+                input {
+                    r#type: "checkbox",
+                    class: "toggle toggle-sm checked:toggle-accent",
+                    checked: review.after_close,
+                    onchange: {
+                        let review = review.clone();
+                        move |evt: FormEvent| {
+                            update_action(QuizReviewSettings {
+                                after_close: evt.checked(),
+                                ..review.clone()
+                            })
+                        }
+                    }
+                }
+                { t!("review-after-close") }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{prelude::*, services::*};
+
+#[component]
+pub fn QuizRegradePanel(task: ReadSignal<String>) -> Element {
+    let mut regrade = use_signal(|| Option::<QuizRegrade>::None);
+    let mut refresh = use_signal(|| true);
+    let mut applied = use_signal(|| false);
+
+    let mut regrade_action = move |dry_run: bool| {
+        api_fetch!(
+            POST,
+            format!("/api/v1/tasks/regrade/{id}", id = task()),
+            RegradeQuizPayload {
+                refresh: refresh(),
+                dry_run,
+                ..Default::default()
+            },
+            on_success = move |body: QuizRegrade| {
+                regrade.set(Some(body));
+                applied.set(!dry_run);
+                if !dry_run {
+                    ToastService::success(t!("saved"))
+                }
+            }
+        );
+    };
+
+    rsx! {
+        div {
+            class: "flex shrink-0 w-full min-h-0 p-1 gap-2 items-center",
+            ul {
+                class: "menu menu-horizontal p-0 m-0 text-base-content flex-nowrap items-center",
+                li {
+                    label {
+                        class: "label text-sm",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-sm checked:toggle-accent",
+                            checked: refresh(),
+                            onchange: move |evt| refresh.set(evt.checked())
+                        }
+                        { t!("quiz-regrade-refresh") }
+                    }
+                }
+                li {
+                    button {
+                        onclick: move |evt| {
+                            evt.prevent_default();
+                            regrade_action(true)
+                        },
+                        i { class: "bi bi-eye" }
+                        { t!("preview") }
+                    }
+                }
+                li {
+                    button {
+                        class: "hover:text-success",
+                        disabled: regrade.read().as_ref().is_none_or(|r| r.changes.is_empty()) || applied(),
+                        onclick: move |evt| {
+                            evt.prevent_default();
+                            regrade_action(false)
+                        },
+                        i { class: "bi bi-arrow-repeat" }
+                        { t!("quiz-regrade-apply") }
+                    }
+                }
+            }
+        }
+        div { class: "h-0.25 bg-base-300 mx-4 my-1" }
+
+        ul {
+            class: "list flex-scrollable",
+            if let Some(regrade) = regrade.read().as_ref() {
+                if regrade.changes.is_empty() {
+                    li {
+                        class: "flex w-full justify-center p-10 text-base-content/60",
+                        { t!("quiz-regrade-empty") }
+                    }
+                }
+                for change in regrade.changes.iter().cloned() {
+                    li {
+                        key: "{change.student}",
+                        class: "list-row rounded-none px-4 py-2 items-center",
+                        div {
+                            class: format!("badge {}", if change.grade_after >= change.grade_before { "badge-success" } else { "badge-error" }),
+                            "{change.grade_before} → {change.grade_after}"
+                        }
+                        div {
+                            class: "list-col-grow flex flex-col gap-1",
+                            div { class: "font-semibold", "{change.student_name}" }
+                            div {
+                                class: "text-sm text-base-content/60",
+                                { format!("{:?} → {:?}", change.results_before, change.results_after) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
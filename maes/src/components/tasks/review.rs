@@ -0,0 +1,119 @@
+use crate::{prelude::*, services::*};
+
+#[component]
+pub fn QuizReviewQueue(task: ReadSignal<String>) -> Element {
+    let mut items = use_signal(Vec::<QuizReviewItem>::new);
+    let mut show_all = use_signal(|| false);
+
+    use_effect(move || {
+        api_fetch!(
+            GET,
+            format!(
+                "/api/v1/tasks/review/{id}?all={all}",
+                id = task.read(),
+                all = show_all()
+            ),
+            on_success = move |body: Vec<QuizReviewItem>| items.set(body)
+        );
+    });
+
+    let mut review_action = move |idx: usize, accepted: bool| {
+        let Some(item) = items.read().get(idx).cloned() else {
+            return;
+        };
+        api_fetch!(
+            PATCH,
+            format!("/api/v1/tasks/review/{id}", id = task()),
+            ReviewQuizAnswerPayload {
+                student: item.student.clone(),
+                question: item.question.clone(),
+                accepted,
+            },
+            on_success = move |body: QuizRecordStudent| {
+                items.with_mut(|items| {
+                    if let Some(result) = body.open.get(&item.question)
+                        && let Some(item) = items.get_mut(idx)
+                    {
+                        item.result = result.clone();
+                    }
+                });
+                ToastService::success(t!("saved"))
+            }
+        );
+    };
+
+    rsx! {
+        div {
+            class: "flex shrink-0 w-full min-h-0 p-1 gap-2 items-center",
+            ul {
+                class: "menu menu-horizontal p-0 m-0 text-base-content flex-nowrap items-center",
+                li {
+                    label {
+                        class: "label text-sm",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-sm checked:toggle-accent",
+                            checked: show_all(),
+                            onchange: move |evt| show_all.set(evt.checked())
+                        }
+                        { t!("quiz-review-all") }
+                    }
+                }
+            }
+        }
+        div { class: "h-0.25 bg-base-300 mx-4 my-1" }
+
+        ul {
+            class: "list flex-scrollable",
+            if items.read().is_empty() {
+                li {
+                    class: "flex w-full justify-center p-10 text-base-content/60",
+                    { t!("quiz-review-empty") }
+                }
+            }
+            for (idx, item) in items.read().iter().cloned().enumerate() {
+                li {
+                    key: "{item.student}{item.question}",
+                    class: "list-row rounded-none px-4 py-2 items-center",
+                    div {
+                        class: format!("badge {}", if item.result.is_passed() { "badge-success" } else { "badge-error" }),
+                        "{item.result.similarity}%"
+                    }
+                    div {
+                        class: "list-col-grow flex flex-col gap-1",
+                        div { class: "font-semibold", "{item.student_name}" }
+                        div { class: "text-sm", "{item.question_name}" }
+                        div { class: "text-sm text-base-content/60", { item.accepted_answers.join(" / ") } }
+                        div { class: "italic", "{item.result.answer}" }
+                        if let Some(review) = &item.result.review {
+                            div {
+                                class: "text-xs text-base-content/60",
+                                i { class: "bi bi-person-check mr-1" }
+                                { t!(if review.accepted { "quiz-review-accepted" } else { "quiz-review-rejected" }, name = review.reviewed_by.clone()) }
+                            }
+                        }
+                    }
+                    div {
+                        class: "join",
+                        button {
+                            class: "btn btn-sm hover:btn-success join-item",
+                            onclick: move |evt| {
+                                evt.prevent_default();
+                                review_action(idx, true)
+                            },
+                            i { class: "bi bi-check-lg" }
+                        }
+                        button {
+                            class: "btn btn-sm hover:btn-error join-item",
+                            onclick: move |evt| {
+                                evt.prevent_default();
+                                review_action(idx, false)
+                            },
+                            i { class: "bi bi-x-lg" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{components::dialogs::*, prelude::*, services::*};
+use ::std::collections::{HashMap, HashSet};
+
+#[component]
+pub fn QuizVariantEntry(task: ReadSignal<String>) -> Element {
+    let mut quiz_rec = use_signal(QuizRecord::default);
+    let mut sheet = use_signal(QuizVariantSheet::default);
+    let quiz_rec_guard = quiz_rec.read();
+    let sheet_guard = sheet.read();
+
+    use_effect(move || {
+        api_fetch!(
+            GET,
+            format!(
+                "/api/v1/entities/payload/{kind}/{id}",
+                kind = EntityKind::QuizRecord,
+                id = task.read()
+            ),
+            on_success = move |body: QuizRecord| quiz_rec.set(body)
+        );
+    });
+
+    let mut select_student = move |student_id: String| {
+        let quiz_rec_guard = quiz_rec.read();
+        let variant = quiz_rec_guard
+            .students
+            .get_index_of(&student_id)
+            .filter(|_| !quiz_rec_guard.variants.is_empty())
+            .map(|idx| quiz_rec_guard.variants[idx % quiz_rec_guard.variants.len()].id)
+            .unwrap_or_default();
+        sheet.set(QuizVariantSheet {
+            task: quiz_rec_guard.id.clone(),
+            variant,
+            student: student_id,
+            answers: HashMap::new(),
+        });
+    };
+
+    let scan_action = move |evt: MouseEvent| {
+        evt.prevent_default();
+        scan_answer_sheet_dialog(
+            task(),
+            Callback::new(move |body: QuizVariantSheet| {
+                sheet.set(body);
+                ToastService::info(t!("answer-sheet-recognized"))
+            }),
+        )
+    };
+
+    let save_action = move |evt: MouseEvent| {
+        evt.prevent_default();
+        let payload = sheet.read().clone();
+        if payload.student.is_empty() || payload.variant == 0 {
+            ToastService::error(t!("missing-fields"));
+            return;
+        }
+        api_call!(
+            PATCH,
+            format!("/api/v1/tasks/variants/{id}", id = task()),
+            payload,
+            on_success = move || {
+                sheet.with_mut(|s| {
+                    s.student.clear();
+                    s.answers.clear();
+                });
+                ToastService::success(t!("saved"))
+            }
+        )
+    };
+
+    let variant = quiz_rec_guard
+        .variants
+        .iter()
+        .find(|v| v.id == sheet_guard.variant)
+        .cloned();
+
+    rsx! {
+        div {
+            class: "flex shrink-0 w-full min-h-0 p-1 gap-2 items-center",
+            select {
+                class: "select select-sm w-80",
+                onchange: move |evt| select_student(evt.value()),
+                option {
+                    value: "",
+                    selected: sheet_guard.student.is_empty(),
+                    { t!("select-student") }
+                }
+                for student in quiz_rec_guard.students.values() {
+                    option {
+                        key: "{student.id}",
+                        value: "{student.id}",
+                        selected: student.id == sheet_guard.student,
+                        "{student.name}"
+                    }
+                }
+            }
+            select {
+                class: "select select-sm w-40",
+                onchange: move |evt| {
+                    let variant = evt.value().parse::<usize>().unwrap_or_default();
+                    sheet.with_mut(|s| {
+                        s.variant = variant;
+                        s.answers.clear();
+                    })
+                },
+                for variant in quiz_rec_guard.variants.iter() {
+                    option {
+                        key: "{variant.id}",
+                        value: "{variant.id}",
+                        selected: variant.id == sheet_guard.variant,
+                        { t!("quiz-variant", variant = variant.id) }
+                    }
+                }
+            }
+            ul {
+                class: "menu menu-horizontal p-0 m-0 text-base-content flex-nowrap",
+                li {
+                    button {
+                        class: "hover:text-info",
+                        onclick: scan_action,
+                        i { class: "bi bi-upc-scan" }
+                        { t!("scan") }
+                    }
+                }
+                li {
+                    button {
+                        class: "hover:text-success",
+                        onclick: save_action,
+                        i { class: "bi bi-floppy" }
+                        { t!("save") }
+                    }
+                }
+            }
+        }
+        div { class: "h-0.25 bg-base-300 mx-4 my-1" }
+
+        ul {
+            class: "list flex-scrollable",
+            if let Some(variant) = variant {
+                for (idx, question) in variant.questions.into_iter().enumerate() {
+                    li {
+                        key: "{question.id}",
+                        class: "list-row rounded-none px-4 py-1 items-center",
+                        div { class: "font-semibold w-8", { format!("{}.", idx + 1) } }
+                        div { class: "list-col-grow truncate", "{question.name}" }
+                        if question.kind == QuizActivityQuestionKind::Open {
+                            input {
+                                class: "input input-sm w-80",
+                                value: sheet_guard.answers.get(&question.id).and_then(|a| a.iter().next().cloned()).unwrap_or_default(),
+                                onchange: {
+                                    let question_id = question.id.clone();
+                                    move |evt: FormEvent| {
+                                        let value = evt.value();
+                                        sheet.with_mut(|s| {
+                                            if value.trim().is_empty() {
+                                                s.answers.remove(&question_id);
+                                            } else {
+                                                s.answers.insert(question_id.clone(), HashSet::from([value]));
+                                            }
+                                        })
+                                    }
+                                }
+                            }
+                        } else {
+                            div {
+                                class: "flex gap-1",
+                                for (answer_idx, answer_id) in question.answers.keys().cloned().enumerate() {
+                                    label {
+                                        key: "{answer_id}",
+                                        class: "flex flex-col items-center text-xs",
+                                        { AnswerSheetLayout::answer_letter(answer_idx).to_string() }
+                                        input {
+                                            r#type: "checkbox",
+                                            class: "checkbox checkbox-sm checked:checkbox-success rounded-sm",
+                                            checked: sheet_guard.answers.get(&question.id).is_some_and(|a| a.contains(&answer_id)),
+                                            onchange: {
+                                                let question_id = question.id.clone();
+                                                move |evt: FormEvent| {
+                                                    to_owned![question_id, answer_id];
+                                                    sheet.with_mut(|s| {
+                                                        let answers = s.answers.entry(question_id.clone()).or_default();
+                                                        if evt.checked() {
+                                                            answers.insert(answer_id);
+                                                        } else {
+                                                            answers.remove(&answer_id);
+                                                        }
+                                                        if answers.is_empty() {
+                                                            s.answers.remove(&question_id);
+                                                        }
+                                                    })
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            } else {
+                li {
+                    class: "flex w-full justify-center p-10 text-base-content/60",
+                    { t!("quiz-variants-empty") }
+                }
+            }
+        }
+    }
+}
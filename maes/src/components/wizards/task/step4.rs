@@ -12,25 +12,65 @@ pub fn TaskWizardStep4() -> Element {
 
     let create_task_action = move |evt: FormEvent| {
         evt.stop();
-        let (Some(name), Some(path), Some(ids), Some(enabled), Some(count)) =
-            form_values!(evt, "name", "path", ["category_id"], ["enabled"], ["count"])
-        else {
+        let (Some(name), Some(path), Some(ids), Some(enabled), Some(count), Some(rules)) = form_values!(
+            evt,
+            "name",
+            "path",
+            ["category_id"],
+            ["enabled"],
+            ["count"],
+            ["rules"]
+        ) else {
             ToastService::error(t!("missing-fields"));
             return;
         };
         let enabled = extract_form_checkboxes(&enabled);
+        let sources = evt.get_values("category_quiz").unwrap_or_default();
+        let ids = ids
+            .into_iter()
+            .zip(sources.into_iter().chain(std::iter::repeat_with(String::new)));
+        let opens_at = evt.get_value("opens_at").and_then(parse_datetime_local);
+        let closes_at = evt.get_value("closes_at").and_then(parse_datetime_local);
+        let practice = evt.get_value("practice").is_some();
+        let review = QuizReviewSettings {
+            mode: QuizReviewMode::from(evt.get_value("review_mode").unwrap_or_default()),
+            after_close: evt.get_value("review_after_close").is_some(),
+        };
+        if let (Some(opens_at), Some(closes_at)) = (opens_at, closes_at)
+            && closes_at <= opens_at
+        {
+            ToastService::error(t!("task-window-invalid"));
+            return;
+        }
 
         let categories = ids
             .into_iter()
             .zip(enabled.into_iter())
             .zip(count.into_iter())
-            .filter(|((_id, enabled), _count)| *enabled)
-            .map(|((id, _enabled), count)| TaskCategory {
-                id,
-                name: "".to_string(),
-                count: count.parse::<usize>().unwrap_or(0),
-                total: 0,
-                checked: count.parse::<usize>().unwrap_or(0) > 0,
+            .zip(rules.into_iter())
+            .filter(|(((_id, enabled), _count), _rules)| *enabled)
+            .map(|((((id, quiz), _enabled), count), rules)| {
+                let rules = serde_json::from_str::<Vec<QuizAssemblyRule>>(&rules)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|r| r.count > 0)
+                    .collect::<Vec<_>>();
+                let count = if rules.is_empty() {
+                    count.parse::<usize>().unwrap_or(0)
+                } else {
+                    rules.iter().map(|r| r.count).sum()
+                };
+                TaskCategory {
+                    id,
+                    quiz,
+                    name: "".to_string(),
+                    count,
+                    total: 0,
+                    checked: count > 0,
+                    tags: vec![],
+                    rules,
+                    ..Default::default()
+                }
             })
             .collect::<Vec<_>>();
 
@@ -47,6 +87,10 @@ pub fn TaskWizardStep4() -> Element {
             name,
             path,
             categories,
+            opens_at,
+            closes_at,
+            practice,
+            review,
         };
         let on_success = move |_body: Task| {
             ToastService::success(t!("task-created"));
@@ -161,6 +205,82 @@ fn TaskWizardDetails() -> Element {
                 initial_value: "{task.read().1.path}",
             }
         }
+        div {
+            class: "flex w-full gap-2",
+            fieldset {
+                class: "fieldset p-2 w-full",
+                legend {
+                    class: "fieldset-legend text-sm text-primary capitalize",
+                    i { class: "bi bi-calendar-check" }
+                    { t!("opens-at") }
+                }
+                input {
+                    class: "input w-full",
+                    r#type: "datetime-local",
+                    name: "opens_at",
+                }
+            }
+            fieldset {
+                class: "fieldset p-2 w-full",
+                legend {
+                    class: "fieldset-legend text-sm text-primary capitalize",
+                    i { class: "bi bi-calendar-x" }
+                    { t!("closes-at") }
+                }
+                input {
+                    class: "input w-full",
+                    r#type: "datetime-local",
+                    name: "closes_at",
+                }
+            }
+        }
+        if kind() == EntityKind::Quiz {
+            fieldset {
+                class: "fieldset p-2 w-full",
+                label {
+                    class: "label text-sm",
+                    input {
+                        r#type: "checkbox",
+                        class: "toggle toggle-sm checked:toggle-accent",
+                        name: "practice",
+                    }
+                    { t!("practice-mode") }
+                }
+                div { class: "text-xs text-base-content/60", { t!("practice-mode-hint") } }
+            }
+            fieldset {
+                class: "fieldset p-2 w-full",
+                legend {
+                    class: "fieldset-legend text-sm text-primary capitalize",
+                    i { class: "bi bi-eye" }
+                    { t!("review-mode") }
+                }
+                div {
+                    class: "flex w-full gap-4 items-center",
+                    select {
+                        class: "select w-full",
+                        name: "review_mode",
+                        for mode in [QuizReviewMode::Hidden, QuizReviewMode::Score, QuizReviewMode::Categories, QuizReviewMode::Answers] {
+                            option {
+                                key: "{mode}",
+                                value: "{mode}",
+                                selected: mode == QuizReviewMode::default(),
+                                { t!(format!("review-mode-{mode}")) }
+                            }
+                        }
+                    }
+                    label {
+                        class: "label text-sm shrink-0",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-sm checked:toggle-accent",
+                            name: "review_after_close",
+                        }
+                        { t!("review-after-close") }
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -169,6 +289,7 @@ fn TaskWizardStep4Categories() -> Element {
     let kind = use_context::<Signal<EntityKind>>();
     let task = use_context::<Signal<(SelectedItem, SelectedItem)>>();
     let mut categories = use_signal(Vec::<TaskCategory>::new);
+    let mut quizzes = use_signal(Vec::<Entity>::new);
 
     use_effect(move || {
         api_fetch!(
@@ -182,6 +303,40 @@ fn TaskWizardStep4Categories() -> Element {
         )
     });
 
+    use_effect(move || {
+        if kind() != EntityKind::Quiz {
+            return;
+        }
+        api_fetch!(
+            GET,
+            format!("/api/v1/entities/{kind}", kind = EntityKind::Quiz),
+            on_success = move |body: Vec<Entity>| quizzes.set(body),
+        )
+    });
+
+    let add_quiz_action = move |evt: FormEvent| {
+        let id = evt.value();
+        if id.is_empty() {
+            return;
+        }
+        api_fetch!(
+            GET,
+            format!("/api/v1/tasks/categories/{kind}/{id}", kind = EntityKind::Quiz),
+            on_success = move |body: Vec<TaskCategory>| {
+                categories.with_mut(|vec| {
+                    for category in body {
+                        if !vec.iter().any(|c| c.id == category.id) {
+                            vec.push(category)
+                        }
+                    }
+                })
+            },
+        )
+    };
+
+    let primary = task.read().0.id.clone();
+    let is_composite = categories.read().iter().any(|c| c.quiz != primary);
+
     rsx! {
         div {
             class: "flex flex-nowrap shrink-0 w-full gap-2 px-3 pt-2 items-center h-10 space-between",
@@ -190,6 +345,24 @@ fn TaskWizardStep4Categories() -> Element {
                 class: "w-full",
                 { t!("categories") }
             }
+            if kind() == EntityKind::Quiz {
+                div {
+                    class: "tooltip tooltip-left",
+                    "data-tip": t!("add-quiz"),
+                    select {
+                        class: "select select-sm w-48",
+                        onchange: add_quiz_action,
+                        option { value: "", selected: true, { t!("add-quiz") } }
+                        for quiz in quizzes
+                            .read()
+                            .iter()
+                            .filter(|q| !categories.read().iter().any(|c| c.quiz == q.id))
+                        {
+                            option { key: "{quiz.id}", value: "{quiz.id}", "{quiz.name}" }
+                        }
+                    }
+                }
+            }
             ul {
                 class: "menu menu-horizontal p-0 m-0 text-base-content flex-nowrap",
                 li {
@@ -241,6 +414,11 @@ fn TaskWizardStep4Categories() -> Element {
                             name: "category_id",
                             value: "{category.id}"
                         }
+                        input {
+                            r#type: "hidden",
+                            name: "category_quiz",
+                            value: "{category.quiz}"
+                        }
                         input {
                             r#type: "checkbox",
                             class: "checkbox checked:checkbox-success rounded-sm",
@@ -266,15 +444,23 @@ fn TaskWizardStep4Categories() -> Element {
                             name: "enabled",
                             value: ""
                         }
+                        input {
+                            r#type: "hidden",
+                            name: "rules",
+                            value: serde_json::to_string(&category.rules).unwrap_or_default(),
+                        }
                     }
                     div {
-                        class: "list-col-grow flex-1 content-center",
+                        class: "list-col-grow flex flex-col flex-1 justify-center",
                         "{category.name}"
+                        if is_composite {
+                            div { class: "text-xs text-base-content/60", "{category.quiz_name}" }
+                        }
                     }
                     div {
                         class: format!("flex-nowrap gap-2 items-center {class}", class = if kind() == EntityKind::Quiz { "flex" } else { "hidden" } ),
                         div {
-                            class: "tooltip tooltip-left",
+                            class: if category.rules.is_empty() { "tooltip tooltip-left" } else { "hidden" },
                             "data-tip": t!("questions-count"),
                             // i { class: "bi bi-question-octagon text-xl text-info" }
                             TextInputComponent {
@@ -286,9 +472,161 @@ fn TaskWizardStep4Categories() -> Element {
                                 initial_value: "{category.count}",
                             }
                         }
+                        div {
+                            class: "tooltip tooltip-left",
+                            "data-tip": t!("add-assembly-rule"),
+                            button {
+                                class: "btn btn-ghost btn-square hover:text-success",
+                                onclick: {
+                                    let idx = category.id.clone();
+                                    move |evt: MouseEvent| {
+                                        evt.prevent_default();
+                                        to_owned![idx];
+                                        categories.with_mut(|vec| {
+                                            if let Some(c) = vec.iter_mut().find(|c| c.id == idx) {
+                                                c.rules.push(QuizAssemblyRule {
+                                                    count: 1,
+                                                    ..Default::default()
+                                                });
+                                            }
+                                        })
+                                    }
+                                },
+                                i { class: "bi bi-funnel text-lg" }
+                            }
+                        }
+                    }
+                    if kind() == EntityKind::Quiz && !category.rules.is_empty() {
+                        TaskWizardCategoryRules {
+                            categories,
+                            category: category.clone(),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TaskWizardCategoryRules(categories: Signal<Vec<TaskCategory>>, category: TaskCategory) -> Element {
+    let datalist_id = format!("tags-{id}", id = category.id);
+
+    rsx! {
+        div {
+            class: "list-col-wrap flex flex-col gap-1",
+            div {
+                class: "text-xs text-base-content/60",
+                { t!("assembly-rules") }
+            }
+            datalist {
+                id: "{datalist_id}",
+                for tag in category.tags.iter() {
+                    option { key: "{tag}", value: "{tag}" }
+                }
+            }
+            for (idx, rule) in category.rules.iter().enumerate() {
+                div {
+                    key: "{category.id}-{idx}",
+                    class: "flex flex-nowrap gap-2 items-center",
+                    input {
+                        r#type: "number",
+                        class: "input input-sm w-20",
+                        min: 1,
+                        max: category.total as i32,
+                        value: "{rule.count}",
+                        onchange: {
+                            let id = category.id.clone();
+                            move |evt: FormEvent| {
+                                let count = evt.value().parse::<usize>().unwrap_or(0);
+                                update_rule(categories, &id, idx, |r| r.count = count)
+                            }
+                        }
+                    }
+                    select {
+                        class: "select select-sm w-32",
+                        onchange: {
+                            let id = category.id.clone();
+                            move |evt: FormEvent| {
+                                let value = evt.value();
+                                let difficulty = (!value.is_empty()).then(|| QuizDifficulty::from(value));
+                                update_rule(categories, &id, idx, |r| r.difficulty = difficulty)
+                            }
+                        },
+                        option {
+                            value: "",
+                            selected: rule.difficulty.is_none(),
+                            { t!("difficulty-any") }
+                        }
+                        for difficulty in [QuizDifficulty::Easy, QuizDifficulty::Medium, QuizDifficulty::Hard] {
+                            option {
+                                key: "{difficulty}",
+                                value: "{difficulty}",
+                                selected: rule.difficulty == Some(difficulty),
+                                { t!(format!("difficulty-{difficulty}")) }
+                            }
+                        }
+                    }
+                    input {
+                        class: "input input-sm flex-1",
+                        list: "{datalist_id}",
+                        placeholder: t!("tags").to_lowercase(),
+                        value: rule.tags.join(", "),
+                        onchange: {
+                            let id = category.id.clone();
+                            move |evt: FormEvent| {
+                                let tags = split_tags(evt.value());
+                                update_rule(categories, &id, idx, |r| r.tags = tags)
+                            }
+                        }
+                    }
+                    input {
+                        class: "input input-sm flex-1",
+                        list: "{datalist_id}",
+                        placeholder: t!("exclude-tags").to_lowercase(),
+                        value: rule.exclude_tags.join(", "),
+                        onchange: {
+                            let id = category.id.clone();
+                            move |evt: FormEvent| {
+                                let tags = split_tags(evt.value());
+                                update_rule(categories, &id, idx, |r| r.exclude_tags = tags)
+                            }
+                        }
+                    }
+                    button {
+                        class: "btn btn-sm btn-ghost btn-square hover:text-error",
+                        onclick: {
+                            let id = category.id.clone();
+                            move |evt: MouseEvent| {
+                                evt.prevent_default();
+                                categories.with_mut(|vec| {
+                                    if let Some(c) = vec.iter_mut().find(|c| c.id == id) {
+                                        c.rules.remove(idx);
+                                    }
+                                })
+                            }
+                        },
+                        i { class: "bi bi-trash" }
                     }
                 }
             }
         }
     }
 }
+
+fn update_rule(
+    mut categories: Signal<Vec<TaskCategory>>,
+    category_id: &str,
+    idx: usize,
+    f: impl FnOnce(&mut QuizAssemblyRule),
+) {
+    categories.with_mut(|vec| {
+        if let Some(rule) = vec
+            .iter_mut()
+            .find(|c| c.id == category_id)
+            .and_then(|c| c.rules.get_mut(idx))
+        {
+            f(rule)
+        }
+    })
+}
@@ -48,6 +48,12 @@ fn RenderListItemRow(item: ReadSignal<Entity>) -> Element {
         )
     };
 
+    let share_action = move |evt: MouseEvent| {
+        evt.stop_propagation();
+        let item_guard = item.read();
+        Exchange::export_package(vec![item_guard.id.clone()], item_guard.name.clone())
+    };
+
     let click_action = move |evt: MouseEvent| {
         evt.stop_propagation();
         let navigator = use_navigator();
@@ -114,6 +120,12 @@ fn RenderListItemRow(item: ReadSignal<Entity>) -> Element {
                 }
             }
             if claims.is_admin() {
+                div {
+                    class: "hidden group-hover:flex h-full w-14 items-center justify-center",
+                    class: "text-base-content/60 hover:text-info-content hover:bg-info cursor-pointer",
+                    onclick: share_action,
+                    i { class: "bi bi-box-arrow-up text-lg" }
+                }
                 div {
                     class: "hidden group-hover:flex h-full w-14 items-center justify-center",
                     class: "text-base-content/60 hover:text-error-content hover:bg-error cursor-pointer",
@@ -31,6 +31,16 @@ pub fn SideMenu() -> Element {
                 label: t!("students")
             }
             if claims.is_supervisor() {
+                MenuItem {
+                    to: Route::Grades {},
+                    icon: rsx! { i { class: "bi bi-journal-text text-2xl" } },
+                    label: t!("gradebook")
+                }
+                MenuItem {
+                    to: Route::Dashboard {},
+                    icon: rsx! { i { class: "bi bi-graph-up text-2xl" } },
+                    label: t!("analytics")
+                }
                 li { class: "mx-0" }
                 MenuItem {
                     to: Route::WorkspaceManager {},
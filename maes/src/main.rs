@@ -83,6 +83,7 @@ fn main() {
             _ = bind_msg_dispatcher();
             let mut dialog = use_init_dialog();
             use_init_context_menu();
+            use_init_import_preview_dialog();
 
             let download_url_sig = use_signal(|| "".to_string());
             let update_action = Callback::new(move |_| UpdateService::update(download_url_sig()));
@@ -149,6 +150,7 @@ fn main() {
                     }
 
                     DialogContainer { key: "dialog-container" }
+                    ImportPreviewDialogContainer { key: "import-preview-dialog-container" }
                     ToastContainer { key: "toast-container" }
                     ContextMenuContainer { key: "ctx-menu-container" }
                     Resizer { key: "resizer" }
@@ -0,0 +1,210 @@
+use crate::{components::widgets::*, prelude::*};
+use ::chrono::{TimeDelta, TimeZone, Utc};
+use ::std::ops::Add;
+
+#[component]
+pub fn Dashboard() -> Element {
+    let mut analytics = use_signal(Analytics::default);
+    let mut quizzes = use_signal(Vec::<Entity>::new);
+    let mut trail = use_signal(Vec::<(String, String)>::new);
+    let mut quiz = use_signal(String::new);
+    let mut tag = use_signal(String::new);
+    let mut from = use_signal(|| Option::<i64>::None);
+    let mut to = use_signal(|| Option::<i64>::None);
+    let mut period = use_signal(AnalyticsPeriod::default);
+
+    use_effect(move || {
+        api_fetch!(
+            GET,
+            format!("/api/v1/entities/{kind}", kind = EntityKind::Quiz),
+            on_success = move |body: Vec<Entity>| quizzes.set(body)
+        );
+    });
+
+    use_effect(move || {
+        let mut query = vec![format!("period={}", period())];
+        if let Some((node, _)) = trail.read().last() {
+            query.push(format!("node={node}"));
+        }
+        if !quiz.read().is_empty() {
+            query.push(format!("quiz={}", quiz.read()));
+        }
+        if !tag.read().trim().is_empty() {
+            query.push(format!("tag={}", tag.read().trim().replace(' ', "+")));
+        }
+        if let Some(from) = from() {
+            query.push(format!("from={from}"));
+        }
+        if let Some(to) = to() {
+            query.push(format!("to={to}"));
+        }
+        api_fetch!(
+            GET,
+            format!("/api/v1/analytics?{}", query.join("&")),
+            on_success = move |body: Analytics| analytics.set(body)
+        );
+    });
+
+    let analytics_guard = analytics.read();
+    let categories = analytics_guard.periods.clone();
+    let series = analytics_guard
+        .units
+        .iter()
+        .filter(|u| u.total.count > 0)
+        .map(|u| ChartSeries {
+            name: u.name.clone(),
+            data: categories
+                .iter()
+                .map(|p| {
+                    u.periods
+                        .iter()
+                        .find(|s| &s.period == p)
+                        .map_or(0, |s| s.stats.average_score.round() as usize)
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+
+    rsx! {
+        Panel {
+            div {
+                class: "flex flex-wrap shrink-0 w-full gap-2 p-3 items-end",
+                select {
+                    class: "select select-sm w-56",
+                    onchange: move |evt| quiz.set(evt.value()),
+                    option { value: "", selected: quiz.read().is_empty(), { t!("all-quizzes") } }
+                    for q in quizzes.read().iter() {
+                        option { key: "{q.id}", value: "{q.id}", selected: *quiz.read() == q.id, "{q.name}" }
+                    }
+                }
+                input {
+                    class: "input input-sm w-40",
+                    placeholder: t!("tags").to_lowercase(),
+                    value: "{tag}",
+                    onchange: move |evt| tag.set(evt.value())
+                }
+                Calendar {
+                    class: "input-sm w-40",
+                    placeholder: t!("from-date"),
+                    onchange: move |evt: FormEvent| {
+                        let date = parse_date_with_unknown_format(evt.value())
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                        .map(|datetime| Utc.from_utc_datetime(&datetime).timestamp());
+                        from.set(date)
+                    }
+                }
+                Calendar {
+                    class: "input-sm w-40",
+                    placeholder: t!("to-date"),
+                    onchange: move |evt: FormEvent| {
+                        let date = parse_date_with_unknown_format(evt.value())
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                        .map(|datetime| Utc.from_utc_datetime(&datetime).add(TimeDelta::days(1)).timestamp());
+                        to.set(date)
+                    }
+                }
+                select {
+                    class: "select select-sm w-32",
+                    onchange: move |evt| period.set(AnalyticsPeriod::from(evt.value())),
+                    for p in [AnalyticsPeriod::Week, AnalyticsPeriod::Month, AnalyticsPeriod::Quarter] {
+                        option { key: "{p}", value: "{p}", selected: period() == p, { t!(format!("period-{p}")) } }
+                    }
+                }
+            }
+            div {
+                class: "breadcrumbs text-sm px-3",
+                ul {
+                    li {
+                        a {
+                            onclick: move |_| trail.set(vec![]),
+                            i { class: "bi bi-diagram-3 mr-1" }
+                            { t!("all-units") }
+                        }
+                    }
+                    for (idx, (id, name)) in trail.read().iter().cloned().enumerate() {
+                        li {
+                            key: "{id}",
+                            a {
+                                onclick: move |_| trail.with_mut(|t| t.truncate(idx + 1)),
+                                "{name}"
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                class: "flex-scrollable px-3",
+                table {
+                    class: "table table-zebra table-sm",
+                    thead {
+                        tr {
+                            th { { t!("unit") } }
+                            th { class: "text-center", { t!("results-count") } }
+                            th { class: "text-center", { t!("average-score") } }
+                            th { class: "text-center", { t!("grade-average") } }
+                            th { class: "text-center", { t!("grade-a") } }
+                            th { class: "text-center", { t!("grade-b") } }
+                            th { class: "text-center", { t!("grade-c") } }
+                            th { class: "text-center", { t!("grade-d") } }
+                            th { class: "text-center", { t!("pass-rate") } }
+                        }
+                    }
+                    tbody {
+                        for unit in analytics_guard.units.iter().cloned() {
+                            tr {
+                                key: "{unit.id}",
+                                class: if unit.has_children { "cursor-pointer hover:bg-base-300" } else { "" },
+                                onclick: {
+                                    let (id, name) = (unit.id.clone(), unit.name.clone());
+                                    move |_| {
+                                        if unit.has_children {
+                                            trail.with_mut(|t| t.push((id.clone(), name.clone())))
+                                        }
+                                    }
+                                },
+                                AnalyticsRow { name: unit.name.clone(), stats: unit.total.clone() }
+                            }
+                        }
+                        tr {
+                            class: "font-semibold",
+                            AnalyticsRow { name: t!("total"), stats: analytics_guard.node.total.clone() }
+                        }
+                    }
+                }
+                if !series.is_empty() && !categories.is_empty() {
+                    div {
+                        class: "pt-5",
+                        div { class: "font-semibold text-center", { t!("average-score-trend") } }
+                        BarChart { series, categories, distributed: false }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AnalyticsRow(name: String, stats: AnalyticsStats) -> Element {
+    if stats.count == 0 {
+        return rsx! {
+            td { "{name}" }
+            td { class: "text-center", "0" }
+            for _ in 0..7 {
+                td { class: "text-center", "—" }
+            }
+        };
+    }
+
+    rsx! {
+        td { "{name}" }
+        td { class: "text-center", "{stats.count}" }
+        td { class: "text-center", { format!("{:.1}", stats.average_score) } }
+        td { class: "text-center", { format!("{:.2}", stats.average_grade) } }
+        td { class: "text-center", "{stats.grade_a}" }
+        td { class: "text-center", "{stats.grade_b}" }
+        td { class: "text-center", "{stats.grade_c}" }
+        td { class: "text-center", "{stats.grade_d}" }
+        td { class: "text-center", { format!("{:.0}%", stats.pass_rate) } }
+    }
+}
+
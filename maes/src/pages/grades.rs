@@ -0,0 +1,251 @@
+use crate::{
+    components::{widgets::*, workspace::*},
+    prelude::*,
+    services::*,
+    window::*,
+};
+
+#[component]
+pub fn Grades() -> Element {
+    use_context_provider(|| Signal::new(EntityKind::Workspace));
+    use_context_provider(|| Signal::new(SelectedItem::default()));
+    use_context_provider(|| Signal::new(Vec::<TreeNode>::new()));
+    use_context_provider(|| Signal::new(Vec::<Entity>::new()));
+
+    rsx! {
+        SplitPanel {
+            left_title: t!("unit-navigator"),
+            left: rsx! {
+                div {
+                    class: "flex-scrollable",
+                    WorkspaceTree {}
+                }
+            },
+            right: rsx! {
+                div {
+                    class: "flex-fixed",
+                    GradebookList {}
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn GradebookList() -> Element {
+    let claims = AuthService::claims();
+    let selected = use_context::<Signal<SelectedItem>>();
+    let mut gradebook = use_signal(Gradebook::default);
+    let mut weights_open = use_signal(|| false);
+
+    use_effect(move || {
+        let node = selected.read().id.clone();
+        if node.is_empty() {
+            return;
+        }
+        api_fetch!(
+            GET,
+            format!("/api/v1/gradebook?node={node}"),
+            on_success = move |body: Gradebook| gradebook.set(body)
+        );
+    });
+
+    let gradebook_guard = gradebook.read();
+
+    rsx! {
+        div {
+            class: "flex shrink-0 w-full min-h-0 p-1",
+            ul {
+                class: "menu menu-horizontal p-0 m-0 text-base-content flex-nowrap",
+                if claims.is_admin() {
+                    li {
+                        button {
+                            class: if weights_open() { "bg-secondary/30 text-secondary" } else { "" },
+                            onclick: move |_| weights_open.set(!weights_open()),
+                            i { class: "bi bi-sliders" }
+                            { t!("gradebook-weights") }
+                        }
+                    }
+                }
+            }
+        }
+        div { class: "h-0.25 bg-base-300 mx-4 my-1" }
+
+        if weights_open() {
+            GradebookWeightsEditor { gradebook }
+        } else {
+            ul {
+                class: "list flex-scrollable",
+                for student in gradebook_guard.students.iter() {
+                    li {
+                        key: "{student.id}",
+                        class: "list-row hover:bg-base-200 rounded-none cursor-pointer",
+                        onclick: {
+                            let id = student.id.clone();
+                            move |_| WindowManager::open_window(
+                                t!("transcript"),
+                                WindowKind::StudentTranscript { student: id.clone() },
+                            )
+                        },
+                        div {
+                            class: "list-col-grow flex flex-col justify-center gap-1",
+                            div { class: "font-semibold", "{student.name}" }
+                            if let Some(rank) = &student.rank {
+                                div { class: "text-xs text-base-content/60", "{rank}" }
+                            }
+                        }
+                        div {
+                            class: "flex flex-wrap gap-1 items-center justify-end",
+                            for entry in student.entries.iter().rev().take(5) {
+                                div {
+                                    key: "{entry.record}",
+                                    class: format!("badge badge-sm {class}", class = match entry.grade {
+                                        5 | 4 => "badge-success",
+                                        3 => "badge-warning",
+                                        2 => "badge-error",
+                                        _ => "badge-ghost",
+                                    }),
+                                    title: "{entry.name}",
+                                    if entry.grade > 0 { "{entry.grade}" } else { "—" }
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex w-16 items-center justify-end font-semibold text-lg",
+                            { student.average().map(|a| format!("{a:.2}")).unwrap_or_default() }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn GradebookWeightsEditor(gradebook: Signal<Gradebook>) -> Element {
+    let mut weights = use_signal(|| gradebook.peek().weights.clone());
+    let mut quizzes = use_signal(Vec::<Entity>::new);
+    let mut new_tag = use_signal(String::new);
+
+    use_effect(move || {
+        api_fetch!(
+            GET,
+            format!("/api/v1/entities/{kind}", kind = EntityKind::Quiz),
+            on_success = move |body: Vec<Entity>| quizzes.set(body)
+        );
+    });
+
+    let save_action = move |_| {
+        api_fetch!(
+            PATCH,
+            "/api/v1/gradebook/weights",
+            weights(),
+            on_success = move |body: GradebookWeights| {
+                gradebook.with_mut(|g| g.weights = body);
+                ToastService::success(t!("saved"))
+            }
+        );
+    };
+
+    let mut tags = weights.read().tags.keys().cloned().collect::<Vec<_>>();
+    tags.sort_unstable();
+
+    rsx! {
+        ul {
+            class: "list flex-scrollable",
+            li {
+                class: "list-row rounded-none px-4 py-2 text-sm text-base-content/60",
+                div { class: "list-col-grow", { t!("quizzes") } }
+            }
+            for quiz in quizzes.read().iter() {
+                li {
+                    key: "{quiz.id}",
+                    class: "list-row rounded-none px-4 py-1 items-center",
+                    div { class: "list-col-grow", "{quiz.name}" }
+                    input {
+                        class: "input input-sm w-24",
+                        r#type: "number",
+                        min: 0,
+                        step: 0.1,
+                        placeholder: "1.0",
+                        value: weights.read().quizzes.get(&quiz.id).map(|w| w.to_string()).unwrap_or_default(),
+                        onchange: {
+                            let id = quiz.id.clone();
+                            move |evt: FormEvent| {
+                                let weight = evt.value().parse::<f64>().ok();
+                                weights.with_mut(|w| match weight {
+                                    Some(weight) => w.quizzes.insert(id.clone(), weight),
+                                    None => w.quizzes.remove(&id),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            li {
+                class: "list-row rounded-none px-4 py-2 text-sm text-base-content/60",
+                div { class: "list-col-grow", { t!("tags") } }
+            }
+            for tag in tags {
+                li {
+                    key: "{tag}",
+                    class: "list-row rounded-none px-4 py-1 items-center",
+                    div { class: "list-col-grow", "{tag}" }
+                    input {
+                        class: "input input-sm w-24",
+                        r#type: "number",
+                        min: 0,
+                        step: 0.1,
+                        value: weights.read().tags.get(&tag).map(|w| w.to_string()).unwrap_or_default(),
+                        onchange: {
+                            let tag = tag.clone();
+                            move |evt: FormEvent| {
+                                let weight = evt.value().parse::<f64>().unwrap_or(1.0);
+                                weights.with_mut(|w| w.tags.insert(tag.clone(), weight));
+                            }
+                        }
+                    }
+                    button {
+                        class: "btn btn-sm btn-ghost btn-square hover:text-error",
+                        onclick: {
+                            let tag = tag.clone();
+                            move |_| {
+                                weights.with_mut(|w| w.tags.remove(&tag));
+                            }
+                        },
+                        i { class: "bi bi-trash" }
+                    }
+                }
+            }
+            li {
+                class: "list-row rounded-none px-4 py-1 items-center",
+                input {
+                    class: "list-col-grow input input-sm",
+                    placeholder: t!("tags").to_lowercase(),
+                    value: "{new_tag}",
+                    oninput: move |evt| new_tag.set(evt.value())
+                }
+                button {
+                    class: "btn btn-sm btn-ghost btn-square hover:text-success",
+                    onclick: move |_| {
+                        let tag = new_tag.read().trim().to_string();
+                        if !tag.is_empty() {
+                            weights.with_mut(|w| w.tags.entry(tag).or_insert(1.0));
+                            new_tag.set(String::new());
+                        }
+                    },
+                    i { class: "bi bi-plus-lg" }
+                }
+            }
+        }
+        div {
+            class: "flex shrink-0 justify-end p-2",
+            button {
+                class: "btn btn-sm btn-primary",
+                onclick: save_action,
+                i { class: "bi bi-floppy" }
+                { t!("save") }
+            }
+        }
+    }
+}
@@ -9,6 +9,7 @@ pub fn Login() -> Element {
     let config = ConfigService::read();
     let mut workspaces = use_signal(Vec::<WorkspaceMetadata>::new);
     let mut create_workspace_dialog = use_init_create_workspace_dialog();
+    let import_preview_dialog = use_import_preview_dialog();
     let mut refresh_counter = use_signal(|| 0);
 
     use_effect(move || {
@@ -96,7 +97,7 @@ pub fn Login() -> Element {
                                     span { { t!("or") } }
                                     a {
                                         class: "link link-hover text-accent",
-                                        onclick: move |_| Exchange::import(),
+                                        onclick: move |_| Exchange::import(import_preview_dialog),
                                         { t!("import-existed-workspace") }
                                     }
                                 }
@@ -7,6 +7,8 @@ mod settings;
 mod about;
 mod reports;
 mod students;
+mod grades;
+mod dashboard;
 mod quiz_manager;
 mod survey_manager;
 mod task_wizard;
@@ -21,6 +23,8 @@ pub use self::{
     about::*,
     reports::*,
     students::*,
+    grades::*,
+    dashboard::*,
     quiz_manager::*,
     survey_manager::*,
     task_wizard::*,   
@@ -37,6 +37,7 @@ pub fn Reports() -> Element {
     let mut state = use_context_provider(ReportsState::default);
     let mut reports = use_context_provider(|| Signal::new(Vec::<Entity>::new()));
     let mut dialog = use_dialog();
+    let import_preview_dialog = use_import_preview_dialog();
 
     use_effect(move || {
         _ = state.changed.read();
@@ -92,6 +93,56 @@ pub fn Reports() -> Element {
         )
     };
 
+    let split_action = move |_| {
+        let Some(id) = state.selected.read().iter().next().cloned() else {
+            return;
+        };
+        api_fetch!(
+            POST,
+            format!("/api/v1/entities/split/{id}"),
+            on_success = move |body: Vec<Entity>| {
+                state.selected.with_mut(|s| {
+                    s.clear();
+                    s.extend(body.iter().map(|e| e.id.clone()));
+                });
+                reports.with_mut(|vec| {
+                    for entity in body.into_iter().rev() {
+                        vec.insert(0, entity)
+                    }
+                });
+                ToastService::success(t!("reports-split"))
+            }
+        )
+    };
+
+    let reopen_action = {
+        let callback = Callback::new(move |_| {
+            let Some(id) = state.selected.peek().iter().next().cloned() else {
+                return;
+            };
+            api_fetch!(
+                POST,
+                format!("/api/v1/tasks/reopen/{id}"),
+                on_success = move |body: Task| {
+                    reports.with_mut(|r| r.retain(|e| e.id != body.id));
+                    state.selected.with_mut(|s| s.clear());
+                    ToastService::success(t!("report-reopened"))
+                }
+            )
+        });
+        Callback::new(move |_| {
+            if state.selected.read().len() != 1 {
+                return;
+            }
+            dialog.warning(t!("reopen-report-message"), Some(callback));
+        })
+    };
+
+    let is_single_quiz_record = state.selected.read().len() == 1
+        && reports.read().iter().any(|e| {
+            e.kind == EntityKind::QuizRecord && state.selected.read().contains(&e.id)
+        });
+
     rsx! {
         Panel {
             div {
@@ -103,7 +154,7 @@ pub fn Reports() -> Element {
                     li {
                         button {
                             class: "hover:text-accent",
-                            onclick: move |_| Exchange::import(),
+                            onclick: move |_| Exchange::import(import_preview_dialog),
                             i { class: "bi bi-upload" }
                             { t!("upload") }
                         }
@@ -142,6 +193,26 @@ pub fn Reports() -> Element {
                             { t!("merge") }
                         }
                     }
+                    li {
+                        button {
+                            class: format!("hover:text-accent {class}",
+                                class = if !is_single_quiz_record { "btn-disabled bg-transparent text-base-content/50" } else { ""}
+                            ),
+                            onclick: split_action,
+                            i { class: "bi bi-diagram-3" }
+                            { t!("split") }
+                        }
+                    }
+                    li {
+                        button {
+                            class: format!("hover:text-warning {class}",
+                                class = if state.selected.read().len() != 1 { "btn-disabled bg-transparent text-base-content/50" } else { ""}
+                            ),
+                            onclick: reopen_action,
+                            i { class: "bi bi-arrow-counterclockwise" }
+                            { t!("reopen") }
+                        }
+                    }
                     div { class: "divider divider-horizontal m-1 w-1" }
                     li {
                         button {
@@ -7,6 +7,30 @@ use crate::{
 #[component]
 pub fn Settings() -> Element {
     let config = ConfigService::read();
+    let mut storage = use_signal(|| config.server.storage);
+
+    let migrate_storage_action = move |evt: MouseEvent| {
+        evt.prevent_default();
+        let backend = storage();
+        if backend == ConfigService::read().server.storage {
+            return;
+        }
+        api_fetch!(
+            POST,
+            "/api/v1/storage/migrate",
+            MigrateStoragePayload { backend },
+            on_success = move |body: MigrateStorageSummary| {
+                match ConfigService::with_mut(|config| config.server.storage = backend) {
+                    Ok(_) => ToastService::success(t!(
+                        "storage-migrated",
+                        workspaces = body.workspaces,
+                        records = body.records
+                    )),
+                    Err(e) => ToastService::error(t!(e.to_string())),
+                }
+            }
+        )
+    };
 
     let save_settings_action = move |evt: FormEvent| {
         evt.stop();
@@ -155,6 +179,38 @@ pub fn Settings() -> Element {
                         }
                     }
                 }
+                if !config.server.remote {
+                    div {
+                        class: "card pt-5",
+                        div {
+                            class: "card-title text-2xl text-primary",
+                            i { class: "bi bi-database mr-2" }
+                            { t!("storage-settings") }
+                        }
+                        div {
+                            class: "card-body flex-row items-center gap-2",
+                            select {
+                                class: "select w-80",
+                                onchange: move |evt| storage.set(StorageKind::from(evt.value())),
+                                for kind in [StorageKind::File, StorageKind::Sqlite] {
+                                    option {
+                                        key: "{kind.as_str()}",
+                                        value: "{kind.as_str()}",
+                                        selected: storage() == kind,
+                                        { t!(format!("storage-{}", kind.as_str())) }
+                                    }
+                                }
+                            }
+                            button {
+                                class: "btn btn-accent",
+                                disabled: storage() == config.server.storage,
+                                onclick: migrate_storage_action,
+                                i { class: "bi bi-arrow-left-right mr-2" }
+                                { t!("storage-migrate") }
+                            }
+                        }
+                    }
+                }
                 div {
                     class: "card pt-5 pr-5",
                     div {
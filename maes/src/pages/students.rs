@@ -5,6 +5,7 @@ pub fn Students() -> Element {
     use_init_input_dialog();
     use_init_create_user_dialog();
     use_init_add_student_dialog();
+    use_init_import_students_dialog();
 
     use_context_provider(|| Signal::new(EntityKind::Workspace));
     use_context_provider(|| Signal::new(SelectedItem::default()));
@@ -31,5 +32,6 @@ pub fn Students() -> Element {
         InputDialogContainer { key: "students-input-dialog" }
         CreateUserDialogContainer { key: "students-create-user-dialog" }
         AddStudentDialogContainer { key: "students-add-student-dialog" }
+        ImportStudentsDialogContainer { key: "students-import-students-dialog" }
     }
 }
\ No newline at end of file
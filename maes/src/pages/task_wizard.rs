@@ -8,6 +8,7 @@ pub fn TaskWizard() -> Element {
     use_init_input_dialog();
     use_init_create_user_dialog();
     use_init_add_student_dialog();
+    use_init_import_students_dialog();
     let steps = use_init_steps(vec![t!("start"), t!("task"), t!("unit"), t!("finish")]);
 
     use_context_provider(|| Signal::new(EntityKind::Quiz));
@@ -33,5 +34,6 @@ pub fn TaskWizard() -> Element {
         InputDialogContainer { key: "wizard-quiz-input-dialog" }
         CreateUserDialogContainer { key: "wizard-create-user-dialog" }
         AddStudentDialogContainer { key: "wizard-add-student-dialog" }
+        ImportStudentsDialogContainer { key: "wizard-import-students-dialog" }
     }
 }
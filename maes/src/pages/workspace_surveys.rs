@@ -13,10 +13,24 @@ pub fn WorkspaceSurveys() -> Element {
     use_init_input_dialog();
 
     use_context_provider(|| Signal::new(EntityKind::Survey));
-    use_context_provider(|| Signal::new(SelectedItem::default()));
+    let selected = use_context_provider(|| Signal::new(SelectedItem::default()));
     use_context_provider(|| Signal::new(None::<SelectedItem>));
     use_context_provider(|| Signal::new(Vec::<TreeNode>::new()));
-    use_context_provider(|| Signal::new(Vec::<Entity>::new()));
+    let mut list = use_context_provider(|| Signal::new(Vec::<Entity>::new()));
+
+    let import_package_action = move |_| {
+        if selected.read().id.is_empty() {
+            ToastService::error(t!("select-folder-first"));
+            return;
+        }
+        Exchange::import_package(
+            PackageTarget {
+                survey_node: Some(selected.read().id.clone()),
+                ..Default::default()
+            },
+            Callback::new(move |entities: Vec<Entity>| list.with_mut(|l| l.extend(entities))),
+        )
+    };
 
     rsx! {
         SplitPanel {
@@ -32,6 +46,14 @@ pub fn WorkspaceSurveys() -> Element {
                     if claims.is_admin() {
                         ul {
                             class: "menu menu-horizontal p-0 m-0 text-base-content",
+                            li {
+                                button {
+                                    class: "hover:text-accent",
+                                    onclick: import_package_action,
+                                    i { class: "bi bi-box-arrow-in-down" }
+                                    { t!("import") }
+                                }
+                            }
                             li {
                                 button {
                                     class: "hover:text-success",
@@ -1,13 +1,17 @@
 mod wifi_instruction;
 mod quiz_tickets;
+mod quiz_variants;
 mod survey_tickets;
 mod quiz;
 mod survey;
+mod transcript;
 
 pub use self::{
     wifi_instruction::*,
     quiz_tickets::*,
+    quiz_variants::*,
     survey_tickets::*,
     quiz::*,
     survey::*,
+    transcript::*,
 };
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{prelude::*, services::*};
 use ::std::collections::{HashMap, HashSet};
 
 #[derive(Default, Clone, PartialEq)]
@@ -34,11 +34,14 @@ pub fn QuizReport(entity: ReadSignal<String>) -> Element {
             ),
             on_success = move |body: QuizRecord| {
                 if is_supervisor {
-                    api_fetch!(
-                        GET,
-                        format!("/api/v1/manager/quizzes/{id}", id = body.quiz),
-                        on_success = move |body: Quiz| quiz.set(body)
-                    )
+                    match body.snapshot.clone() {
+                        Some(snapshot) => quiz.set(snapshot),
+                        None => api_fetch!(
+                            GET,
+                            format!("/api/v1/manager/quizzes/{id}", id = body.quiz),
+                            on_success = move |body: Quiz| quiz.set(body)
+                        ),
+                    }
                 }
                 quiz_rec.set(body);
             }
@@ -138,6 +141,19 @@ fn RenderQuizReport() -> Element {
 
     let has_ranks = quiz_rec_guard.students.values().any(|s| s.rank.is_some());
     let result_cols = quiz_rec_guard.categories.len();
+    // Composite exams group category columns under the quiz they were drawn from.
+    let mut source_groups = Vec::<(String, usize)>::new();
+    if quiz_rec_guard.is_composite() {
+        let mut last_source = "";
+        for category in quiz_rec_guard.categories.values() {
+            let source = quiz_rec_guard.category_source(category);
+            match source_groups.last_mut() {
+                Some((_, span)) if source == last_source => *span += 1,
+                _ => source_groups.push((category.quiz_name.clone(), 1)),
+            }
+            last_source = source;
+        }
+    }
 
     rsx! {
         div {
@@ -155,6 +171,15 @@ fn RenderQuizReport() -> Element {
             table {
                 class: "report-table table-zebra w-[calc(100%_-_1px)]",
                 thead {
+                    if result_cols > 1 && !source_groups.is_empty() {
+                        tr {
+                            th { colspan: if has_ranks { "2" } else { "1" } }
+                            for (name, span) in source_groups.iter() {
+                                th { colspan: "{span}", class: "text-center", "{name}" }
+                            }
+                            th {}
+                        }
+                    }
                     tr {
                         if has_ranks {
                             th { class: "w-min text-center", { t!("rank") } }
@@ -170,56 +195,69 @@ fn RenderQuizReport() -> Element {
                 }
                 tbody {
                     for (student_idx, student) in quiz_rec_guard.students.values().enumerate() {
-                        tr {
-                            class: if state.peek().is_supervisor && student.grade > 0 {
-                                "cursor-pointer hover:bg-base-300"
-                            } else { "" },
-                            onclick: {
-                                let student_id = student.id.clone();
-                                let student_grade = student.grade;
-                                move |_| {
-                                    if state.peek().is_supervisor && student_grade > 0 {
-                                        state.with_mut(|s| s.active_student = Some(student_id.clone()))
-                                    }
-                                }
-                            },
-                            if has_ranks && let Some(rank) = &student.rank {
-                                td { class: "text-left", "{rank}" }
-                            }
-                            td { class: "text-left", "{student.name}" }
-                            if student.grade == 0 {
-                                for _ in 0..result_cols {
-                                    td { { t!("uncertified-placeholder") } }
-                                }
-                                if result_cols > 1 {
-                                    td { { t!("uncertified-placeholder") } }
-                                }
-                            } else if result_cols > 1 {
-                                for i in 0..result_cols {
-                                    td {
-                                        {if state.read().as_percentage {
-                                            quiz_rec_guard.results.get(student_idx, i).to_string()
-                                        } else {
-                                            quiz_rec_guard.grade.calc(*quiz_rec_guard.results.get(student_idx, i)).to_string()
-                                        }}
-                                    }
-                                }
-                                td {
-                                    class: "font-semibold",
-                                    {if state.read().as_percentage {
-                                        format!("{:.0}", quiz_rec_guard.results.calc_row_average(student_idx))
-                                    } else {
-                                        student.grade.to_string()
-                                    }}
-                                }
+                        {
+                            let grade = student.final_grade();
+                            let results = quiz_rec_guard.final_results(student_idx);
+                            let average = if results.is_empty() {
+                                0.0
                             } else {
-                                td {
-                                    class: "font-semibold",
-                                    {if state.read().as_percentage{
-                                        format!("{:.0}", quiz_rec_guard.results.calc_row_average(student_idx))
+                                results.iter().sum::<usize>() as f64 / results.len() as f64
+                            };
+                            let grade_overridden = student.grade_override.as_ref().is_some_and(|o| o.grade.is_some());
+                            let reason = student.grade_override.as_ref().map(|o| o.reason.clone()).unwrap_or_default();
+                            rsx! {
+                                tr {
+                                    class: if state.peek().is_supervisor && grade > 0 {
+                                        "cursor-pointer hover:bg-base-300"
+                                    } else { "" },
+                                    onclick: {
+                                        let student_id = student.id.clone();
+                                        move |_| {
+                                            if state.peek().is_supervisor && grade > 0 {
+                                                state.with_mut(|s| s.active_student = Some(student_id.clone()))
+                                            }
+                                        }
+                                    },
+                                    if has_ranks && let Some(rank) = &student.rank {
+                                        td { class: "text-left", "{rank}" }
+                                    }
+                                    td { class: "text-left", "{student.name}" }
+                                    if grade == 0 {
+                                        for _ in 0..result_cols {
+                                            td { { t!("uncertified-placeholder") } }
+                                        }
+                                        if result_cols > 1 {
+                                            td { { t!("uncertified-placeholder") } }
+                                        }
                                     } else {
-                                        student.grade.to_string()
-                                    }}
+                                        if result_cols > 1 {
+                                            for (i, category) in quiz_rec_guard.categories.keys().enumerate() {
+                                                td {
+                                                    title: if student.result_override(category).is_some() { reason.clone() } else { String::new() },
+                                                    {if state.read().as_percentage {
+                                                        results[i].to_string()
+                                                    } else {
+                                                        quiz_rec_guard.grade.calc(results[i]).to_string()
+                                                    }}
+                                                    if student.result_override(category).is_some() {
+                                                        sup { "*" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        td {
+                                            class: "font-semibold",
+                                            title: if grade_overridden { reason.clone() } else { String::new() },
+                                            {if state.read().as_percentage {
+                                                format!("{average:.0}")
+                                            } else {
+                                                grade.to_string()
+                                            }}
+                                            if grade_overridden {
+                                                sup { "*" }
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -268,13 +306,12 @@ fn StatsReport() -> Element {
         .collect::<Vec<_>>();
 
     for (student_idx, student) in quiz_rec_guard.students.values().enumerate() {
-        if student.grade == 0 {
+        if student.final_grade() == 0 {
             continue;
         }
-        for (i, &&score) in quiz_rec_guard
-            .results
-            .get_row(student_idx)
-            .iter()
+        for (i, score) in quiz_rec_guard
+            .final_results(student_idx)
+            .into_iter()
             .enumerate()
         {
             match quiz_rec_guard.grade.calc(score) {
@@ -299,14 +336,14 @@ fn StatsReport() -> Element {
         quiz_rec_guard.students.values().fold(
             (0, 0, 0, 0, 0),
             |(mut a, mut b, mut c, mut d, mut total), s| {
-                match s.grade {
+                match s.final_grade() {
                     5 => a += 1,
                     4 => b += 1,
                     3 => c += 1,
                     2 => d += 1,
                     _ => (),
                 }
-                total += s.grade;
+                total += s.final_grade();
 
                 (a, b, c, d, total)
             },
@@ -452,7 +489,7 @@ fn StudentsReport() -> Element {
         .read()
         .students
         .values()
-        .filter(|s| s.grade == 0)
+        .filter(|s| s.final_grade() == 0)
         .cloned()
         .collect::<Vec<_>>();
     let absent_total = absent_students.len();
@@ -460,7 +497,7 @@ fn StudentsReport() -> Element {
         .read()
         .students
         .values()
-        .filter(|s| s.grade == 2)
+        .filter(|s| s.final_grade() == 2)
         .cloned()
         .collect::<Vec<_>>();
     let uncertified_total = uncertified_students.len();
@@ -522,7 +559,7 @@ fn RenderStudentReport() -> Element {
     // let quiz_guard = quiz.read();
     let quiz_rec = use_context::<Signal<QuizRecord>>();
     let quiz_rec_guard = quiz_rec.read();
-    let mut student = use_signal(QuizRecordStudent::default);
+    let mut student = use_context_provider(|| Signal::new(QuizRecordStudent::default()));
     let mut answered = use_context_provider(|| Signal::new(Vec::new()));
 
     use_hook(move || {
@@ -569,17 +606,141 @@ fn RenderStudentReport() -> Element {
             div { class: "flex w-full justify-end", { t!("date-stamp", date = quiz_rec_guard.metadata.updated_at()) } }
         }
 
+        if let Some(grade_override) = student.read().grade_override.clone() {
+            div {
+                class: "flex w-full pt-2 italic",
+                { t!("grade-overridden", reason = grade_override.reason, user = grade_override.overridden_by) }
+            }
+        }
+        if state.read().is_supervisor {
+            RenderGradeOverride {}
+        }
+
         for (idx, category) in quiz_rec_guard.categories.values().enumerate() {
             RenderStudentCategoryReport{ category_idx: idx, category_id: category.id.clone()}
         }
     }
 }
 
+#[component]
+fn RenderGradeOverride() -> Element {
+    let mut quiz_rec = use_context::<Signal<QuizRecord>>();
+    let mut student = use_context::<Signal<QuizRecordStudent>>();
+    let current = student.peek().grade_override.clone().unwrap_or_default();
+    let mut grade = use_signal(|| current.grade);
+    let mut results = use_signal(|| current.results.clone());
+    let mut reason = use_signal(|| current.reason.clone());
+
+    let mut override_action = move |payload: OverrideQuizGradePayload| {
+        api_fetch!(
+            PATCH,
+            format!(
+                "/api/v1/tasks/grade/{id}/{student_id}",
+                id = quiz_rec.peek().id,
+                student_id = student.peek().id
+            ),
+            payload,
+            on_success = move |body: QuizRecordStudent| {
+                let current = body.grade_override.clone().unwrap_or_default();
+                grade.set(current.grade);
+                results.set(current.results);
+                reason.set(current.reason);
+                quiz_rec.with_mut(|quiz_rec| {
+                    if let Some(student) = quiz_rec.students.get_mut(&body.id) {
+                        *student = body.clone();
+                    }
+                });
+                student.set(body);
+                ToastService::success(t!("saved"))
+            }
+        );
+    };
+
+    let quiz_rec_guard = quiz_rec.read();
+    let student_guard = student.read();
+
+    rsx! {
+        div {
+            class: "flex flex-wrap w-full items-end gap-2 pt-3 print:hidden",
+            label {
+                class: "flex flex-col gap-1 text-sm",
+                { t!("total-grade") }
+                select {
+                    class: "select select-sm w-24",
+                    onchange: move |evt| grade.set(evt.value().parse::<usize>().ok()),
+                    option { value: "", selected: grade().is_none(), "{student_guard.grade}" }
+                    for value in 2..=5_usize {
+                        option { key: "{value}", value: "{value}", selected: grade() == Some(value), "{value}" }
+                    }
+                }
+            }
+            for (idx, category) in quiz_rec_guard.categories.values().enumerate() {
+                label {
+                    key: "{category.id}",
+                    class: "flex flex-col gap-1 text-sm",
+                    "{category.name}"
+                    input {
+                        class: "input input-sm w-24",
+                        r#type: "number",
+                        min: 0,
+                        max: 100,
+                        placeholder: quiz_rec_guard
+                            .students
+                            .get_index_of(&student_guard.id)
+                            .map(|row| quiz_rec_guard.results.get(row, idx).to_string())
+                            .unwrap_or_default(),
+                        value: results.read().get(&category.id).map(|r| r.to_string()).unwrap_or_default(),
+                        oninput: {
+                            let category_id = category.id.clone();
+                            move |evt: FormEvent| {
+                                let value = evt.value().parse::<usize>().ok().map(|v| v.min(100));
+                                results.with_mut(|r| match value {
+                                    Some(value) => r.insert(category_id.clone(), value),
+                                    None => r.remove(&category_id),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            label {
+                class: "flex flex-col flex-1 gap-1 text-sm min-w-60",
+                { t!("override-reason") }
+                input {
+                    class: "input input-sm w-full",
+                    value: "{reason}",
+                    oninput: move |evt| reason.set(evt.value())
+                }
+            }
+            button {
+                class: "btn btn-sm btn-primary",
+                disabled: reason.read().trim().is_empty() || (grade().is_none() && results.read().is_empty()),
+                onclick: move |_| override_action(OverrideQuizGradePayload {
+                    grade: grade(),
+                    results: results(),
+                    reason: reason(),
+                }),
+                i { class: "bi bi-pencil-square" }
+                { t!("override-grade") }
+            }
+            if student_guard.grade_override.is_some() {
+                button {
+                    class: "btn btn-sm btn-ghost hover:text-error",
+                    onclick: move |_| override_action(OverrideQuizGradePayload::default()),
+                    i { class: "bi bi-x-lg" }
+                    { t!("clear-override") }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn RenderStudentCategoryReport(category_idx: usize, category_id: String) -> Element {
     let quiz = use_context::<Signal<Quiz>>();
     let quiz_guard = quiz.read();
     let answered = use_context::<Signal<Vec<HashMap<String, HashSet<String>>>>>();
+    let student = use_context::<Signal<QuizRecordStudent>>();
 
     let Some(category) = quiz_guard.categories.get(&category_id) else {
         return rsx! {};
@@ -600,7 +761,11 @@ fn RenderStudentCategoryReport(category_idx: usize, category_id: String) -> Elem
             class: "flex flex-col",
             for (question_id, answers_ids) in answered_questions.iter() {
                 if let Some(question) = category.questions.get(question_id) {
-                    RenderStudentQuestionReport { question: question.clone(), answers_ids: answers_ids.clone() }
+                    RenderStudentQuestionReport {
+                        question: question.clone(),
+                        answers_ids: answers_ids.clone(),
+                        open: student.read().open.get(question_id).cloned(),
+                    }
                 }
             }
         }
@@ -608,7 +773,11 @@ fn RenderStudentCategoryReport(category_idx: usize, category_id: String) -> Elem
 }
 
 #[component]
-fn RenderStudentQuestionReport(question: QuizQuestion, answers_ids: HashSet<String>) -> Element {
+fn RenderStudentQuestionReport(
+    question: QuizQuestion,
+    answers_ids: HashSet<String>,
+    open: Option<QuizOpenResult>,
+) -> Element {
     let quiz = use_context::<Signal<Quiz>>();
     let quiz_guard = quiz.read();
     let img_base_url = format!(
@@ -633,7 +802,7 @@ fn RenderStudentQuestionReport(question: QuizQuestion, answers_ids: HashSet<Stri
                 class: "flex gap-2",
                 div {
                     class: "flex items-center justify-center",
-                    if question.answers.len() == 1 {
+                    if question.is_open() {
                         i { class: "bi bi-openai text-base-content/70" }
                     } else if is_correct {
                         i { class: "bi bi-check-square text-green-700" }
@@ -652,18 +821,19 @@ fn RenderStudentQuestionReport(question: QuizQuestion, answers_ids: HashSet<Stri
                     "{question.name}"
                 }
             }
-            if question.answers.len() == 1 {
+            if question.is_open() {
                 ol {
                     class: "list-inside space-y-0.5 pl-4 pt-1 pb-3",
-                    li {
-                        class: "flex gap-2",
-                        div {
-                            class: "flex items-center justify-center w-6",
-                            i { class: "bi bi-chat-right-text text-base-content/70" }
-                        }
-                        div {
-                            class: "flex flex-col",
-                            if let Some(answer) = question.answers.values().next() {
+                    for answer in question.answers.values() {
+                        li {
+                            key: "{answer.id}",
+                            class: "flex gap-2",
+                            div {
+                                class: "flex items-center justify-center w-6",
+                                i { class: "bi bi-chat-right-text text-base-content/70" }
+                            }
+                            div {
+                                class: "flex flex-col",
                                 if answer.img {
                                     div {
                                         class: "max-w-30 w-full p-2",
@@ -674,37 +844,26 @@ fn RenderStudentQuestionReport(question: QuizQuestion, answers_ids: HashSet<Stri
                             }
                         }
                     }
-                    li {
-                        class: "flex gap-2",
-                        if let Some(answer) = question.answers.values().next() {{
-                            let mut correct = false;
-                            let mut similarity = 0;
-                            let mut text = String::new();
-                            for s in answers_ids.iter() {
-                                if s.starts_with(answer.id.as_str()) {
-                                    let mut parts = s.splitn(3, '|');
-                                    let _a = parts.next().unwrap_or_default();
-                                    let b = parts.next().unwrap_or_default();
-                                    let c = parts.next().unwrap_or_default();
-
-                                    correct = c == "1";
-                                    similarity = b.parse::<usize>().unwrap_or_default();
+                    if let Some(open) = open {
+                        li {
+                            class: "flex gap-2",
+                            div {
+                                class: "flex items-center justify-center w-6",
+                                if open.is_passed() {
+                                    i { class: "bi bi-check-square text-green-700" }
                                 } else {
-                                    text.push_str(s);
+                                    i { class: "bi bi-x-square text-red-700" }
                                 }
                             }
-                            rsx! {
+                            div { "[{open.similarity.to_string()}%] {open.answer}" }
+                            if let Some(review) = &open.review {
                                 div {
-                                    class: "flex items-center justify-center w-6",
-                                    if correct {
-                                        i { class: "bi bi-check-square text-green-700" }
-                                    } else {
-                                        i { class: "bi bi-x-square text-red-700" }
-                                    }
+                                    class: "text-base-content/60",
+                                    i { class: "bi bi-person-check mr-1" }
+                                    "{review.reviewed_by}"
                                 }
-                                div { "[{similarity.to_string()}%] {text}" }
                             }
-                        }}
+                        }
                     }
                 }
             } else {
@@ -0,0 +1,270 @@
+use crate::{prelude::*, services::*};
+
+#[component]
+pub fn QuizVariants(task: ReadSignal<String>) -> Element {
+    let mut quiz_rec = use_signal(QuizRecord::default);
+    let mut count = use_signal(|| 2_usize);
+    let mut answer_sheets = use_signal(|| false);
+    let quiz_rec_guard = quiz_rec.read();
+
+    use_effect(move || {
+        api_fetch!(
+            GET,
+            format!(
+                "/api/v1/entities/payload/{kind}/{id}",
+                kind = EntityKind::QuizRecord,
+                id = task.read()
+            ),
+            on_success = move |body: QuizRecord| {
+                if !body.variants.is_empty() {
+                    count.set(body.variants.len())
+                }
+                quiz_rec.set(body)
+            }
+        );
+    });
+
+    let generate_action = move |evt: MouseEvent| {
+        evt.prevent_default();
+        api_fetch!(
+            POST,
+            format!("/api/v1/tasks/variants/{id}", id = task.read()),
+            CreateQuizVariantsPayload {
+                count: count(),
+                seed: None,
+            },
+            on_success = move |body: Vec<QuizVariant>| {
+                quiz_rec.with_mut(|q| q.variants = body);
+                ToastService::success(t!("quiz-variants-generated"))
+            }
+        );
+    };
+
+    rsx! {
+        div {
+            class: "flex shrink-0 w-full min-h-0 print:hidden p-1 items-center",
+            ul {
+                class: "menu menu-horizontal p-0 m-0 text-base-content flex-nowrap items-center",
+                li {
+                    button {
+                        class: "hover:text-info",
+                        disabled: quiz_rec_guard.variants.is_empty(),
+                        onclick: move |event: MouseEvent| {
+                            event.prevent_default();
+                            event.stop_propagation();
+                            document::eval("window.print()");
+                        },
+                        i { class: "bi bi-printer" }
+                        { t!("print") }
+                    }
+                }
+                div { class: "divider divider-horizontal m-1 w-1" }
+                div {
+                    class: "tooltip tooltip-bottom",
+                    "data-tip": t!("quiz-variants-count"),
+                    input {
+                        class: "input input-sm w-20",
+                        r#type: "number",
+                        min: 1,
+                        max: 30,
+                        value: "{count}",
+                        onchange: move |evt| count.set(evt.value().parse::<usize>().unwrap_or(1).clamp(1, 30)),
+                    }
+                }
+                li {
+                    button {
+                        class: "hover:text-success",
+                        onclick: generate_action,
+                        i { class: "bi bi-shuffle" }
+                        { t!("generate") }
+                    }
+                }
+                div { class: "divider divider-horizontal m-1 w-1" }
+                div {
+                    class: "tooltip tooltip-bottom",
+                    "data-tip": t!("quiz-booklets-or-answer-sheets"),
+                    li {
+                        label {
+                            class: "swap swap-rotate text-sm",
+                            input {
+                                r#type: "checkbox",
+                                checked: answer_sheets(),
+                                onchange: move |evt| answer_sheets.set(evt.checked())
+                            }
+                            i { class: "bi bi-ui-radios-grid swap-on" }
+                            i { class: "bi bi-journal-text swap-off" }
+                        }
+                    }
+                }
+            }
+        }
+        div {
+            class: "flex flex-1 flex-col print-area overflow-y-auto",
+            "data-theme": "lofi",
+            if quiz_rec_guard.variants.is_empty() {
+                div {
+                    class: "flex w-full justify-center p-10 text-base-content/60",
+                    { t!("quiz-variants-empty") }
+                }
+            } else if answer_sheets() {
+                for (idx, student) in quiz_rec_guard.students.values().enumerate() {
+                    AnswerSheet {
+                        key: "{student.id}",
+                        quiz_rec: quiz_rec_guard.clone(),
+                        student: student.clone(),
+                        variant: quiz_rec_guard.variants[idx % quiz_rec_guard.variants.len()].clone(),
+                    }
+                }
+            } else {
+                for variant in quiz_rec_guard.variants.iter() {
+                    QuestionBooklet {
+                        key: "{variant.id}",
+                        quiz_rec: quiz_rec_guard.clone(),
+                        variant: variant.clone(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn QuestionBooklet(quiz_rec: QuizRecord, variant: QuizVariant) -> Element {
+    rsx! {
+        div {
+            class: "quiz-booklet",
+            div {
+                class: "flex flex-col w-full items-center gap-1 pb-3",
+                div { class: "text-lg font-semibold", "{quiz_rec.name}" }
+                div { class: "font-semibold", { t!("quiz-variant", variant = variant.id) } }
+            }
+            ol {
+                class: "flex flex-col gap-3",
+                for question in variant.questions.iter() {
+                    li {
+                        key: "{question.id}",
+                        class: "break-inside-avoid",
+                        div { class: "font-semibold", "{question.name}" }
+                        if question.img {
+                            img {
+                                class: "max-w-50 h-auto object-contain",
+                                src: format!("{}/images/{}/{}/{}.webp", localhost(), quiz_rec.workspace, quiz_rec.assets(), question.id)
+                            }
+                        }
+                        if question.kind == QuizActivityQuestionKind::Open {
+                            div { class: "pl-4 italic", { t!("quiz-open-answer-hint") } }
+                        } else {
+                            for (idx, answer) in question.answers.values().enumerate() {
+                                div {
+                                    key: "{answer.id}",
+                                    class: "flex gap-2 pl-4",
+                                    span { class: "font-semibold", { format!("{}.", AnswerSheetLayout::answer_letter(idx)) } }
+                                    span { "{answer.name}" }
+                                    if answer.img {
+                                        img {
+                                            class: "max-w-30 h-auto object-contain",
+                                            src: format!("{}/images/{}/{}/{}.webp", localhost(), quiz_rec.workspace, quiz_rec.assets(), answer.id)
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AnswerSheet(quiz_rec: QuizRecord, student: QuizRecordStudent, variant: QuizVariant) -> Element {
+    let code = QuizVariantSheet {
+        task: quiz_rec.id.clone(),
+        variant: variant.id,
+        student: student.id.clone(),
+        ..Default::default()
+    }
+    .code();
+    let qr_size = AnswerSheetLayout::QR_SIZE;
+    let bubble_size = AnswerSheetLayout::BUBBLE_SIZE;
+    let half = bubble_size / 2.0;
+
+    let mut marks = Vec::<(String, &'static str, String, String)>::new();
+    for block in 0..variant.questions.len().div_ceil(AnswerSheetLayout::ROWS) {
+        for answer in 0..AnswerSheetLayout::MAX_ANSWERS {
+            let (x, y) = AnswerSheetLayout::bubble(block * AnswerSheetLayout::ROWS, answer);
+            marks.push((
+                format!("header-{block}-{answer}"),
+                "answer-sheet-letter",
+                format!(
+                    "left: {left}mm; top: {top}mm; width: {bubble_size}mm;",
+                    left = x - half,
+                    top = y - half - AnswerSheetLayout::ROW_HEIGHT
+                ),
+                AnswerSheetLayout::answer_letter(answer).to_string(),
+            ));
+        }
+    }
+    for (idx, question) in variant.questions.iter().enumerate() {
+        let (x, y) = AnswerSheetLayout::label(idx);
+        marks.push((
+            format!("label-{idx}"),
+            "answer-sheet-label",
+            format!("left: {x}mm; top: {y}mm; height: {bubble_size}mm;"),
+            format!("{number}.", number = idx + 1),
+        ));
+        if question.kind == QuizActivityQuestionKind::Open {
+            let (x, y) = AnswerSheetLayout::bubble(idx, 0);
+            marks.push((
+                format!("line-{idx}"),
+                "answer-sheet-line",
+                format!(
+                    "left: {left}mm; top: {top}mm; width: {width}mm;",
+                    left = x - half,
+                    top = y + half,
+                    width = AnswerSheetLayout::COLUMN_WIDTH * (AnswerSheetLayout::MAX_ANSWERS - 1) as f32
+                        + bubble_size
+                ),
+                String::new(),
+            ));
+            continue;
+        }
+        for answer in 0..question.answers.len() {
+            let (x, y) = AnswerSheetLayout::bubble(idx, answer);
+            marks.push((
+                format!("bubble-{idx}-{answer}"),
+                "answer-sheet-bubble",
+                format!(
+                    "left: {left}mm; top: {top}mm; width: {bubble_size}mm; height: {bubble_size}mm;",
+                    left = x - half,
+                    top = y - half
+                ),
+                String::new(),
+            ));
+        }
+    }
+
+    rsx! {
+        div {
+            class: "answer-sheet",
+            img {
+                class: "absolute",
+                style: "left: 0; top: 0; width: {qr_size}mm; height: {qr_size}mm;",
+                src: QrGenerator::symbol(code),
+            }
+            div {
+                class: "absolute flex flex-col gap-1",
+                style: format!("left: {left}mm; top: 0;", left = qr_size + 10.0),
+                div { class: "font-semibold", "{quiz_rec.name}" }
+                if let Some(rank) = &student.rank {
+                    div { "{rank}" }
+                }
+                div { class: "text-lg font-semibold", "{student.name}" }
+                div { { t!("quiz-variant", variant = variant.id) } }
+            }
+            for (key, class, style, text) in marks {
+                div { key: "{key}", class, style, "{text}" }
+            }
+        }
+    }
+}
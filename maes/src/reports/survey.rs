@@ -3,13 +3,20 @@ use crate::{components::widgets::*, prelude::*};
 #[derive(Default, Clone, PartialEq)]
 struct SurveyReportState {
     pub extended: bool,
+    pub segment: Option<String>,
 }
 
 #[component]
 pub fn SurveyReport(entity: ReadSignal<String>) -> Element {
+    let mut source = use_signal(SurveyRecord::default);
     let mut survey_rec = use_context_provider(|| Signal::new(SurveyRecord::default()));
     let survey_rec_guard = survey_rec.read();
-    let mut state = use_context_provider(|| Signal::new(SurveyReportState { extended: true }));
+    let mut state = use_context_provider(|| {
+        Signal::new(SurveyReportState {
+            extended: true,
+            ..Default::default()
+        })
+    });
 
     use_effect(move || {
         api_fetch!(
@@ -19,10 +26,53 @@ pub fn SurveyReport(entity: ReadSignal<String>) -> Element {
                 kind = EntityKind::SurveyRecord,
                 id = entity.read()
             ),
-            on_success = move |body: SurveyRecord| survey_rec.set(body)
+            on_success = move |body: SurveyRecord| {
+                source.set(body.clone());
+                survey_rec.set(body);
+                state.with_mut(|s| s.segment = None);
+            }
         );
     });
 
+    let segment_action = move |value: String| {
+        let record = value
+            .split_once('/')
+            .and_then(|(category, option)| source.read().segmented(category, option));
+        state.with_mut(|s| s.segment = record.as_ref().map(|_| value.clone()));
+        survey_rec.set(record.unwrap_or_else(|| source()));
+    };
+
+    // Segment values below the threshold stay listed but can't be picked.
+    let source_guard = source.read();
+    let threshold = source_guard.min_segment_size.max(1);
+    let segment_options = source_guard
+        .categories
+        .values()
+        .filter(|c| c.segment)
+        .flat_map(|c| {
+            c.answers.values().map(|answer| {
+                let total = source_guard
+                    .segments
+                    .iter()
+                    .find(|s| s.category == c.id && s.option == answer.id)
+                    .map(|s| s.total)
+                    .unwrap_or(0);
+                (
+                    format!("{}/{}", c.id, answer.id),
+                    format!("{}: {}", c.name, answer.name),
+                    total >= threshold,
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    let segment_name = state.read().segment.as_ref().and_then(|value| {
+        segment_options
+            .iter()
+            .find(|(v, _, _)| v == value)
+            .map(|(_, name, _)| name.clone())
+    });
+    let suppressed = source_guard.categories.len() > survey_rec_guard.categories.len();
+
     if survey_rec_guard.id.is_empty() || survey_rec_guard.total == 0 {
         return rsx! {};
     }
@@ -56,6 +106,27 @@ pub fn SurveyReport(entity: ReadSignal<String>) -> Element {
                         }
                     }
                 }
+                if !segment_options.is_empty() {
+                    div { class: "divider divider-horizontal m-1 w-1" }
+                    select {
+                        class: "select select-sm w-64",
+                        onchange: move |evt| segment_action(evt.value()),
+                        option { value: "", selected: state.read().segment.is_none(), { t!("all-respondents") } }
+                        for (value, name, allowed) in segment_options.iter().cloned() {
+                            option {
+                                key: "{value}",
+                                value: "{value}",
+                                disabled: !allowed,
+                                selected: state.read().segment.as_ref() == Some(&value),
+                                if allowed {
+                                    "{name}"
+                                } else {
+                                    { t!("segment-suppressed", name = name, size = threshold) }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
 
@@ -69,6 +140,12 @@ pub fn SurveyReport(entity: ReadSignal<String>) -> Element {
                     "{survey_rec_guard.name}"
                 }
                 div { "{survey_rec_guard.path}" }
+                if let Some(name) = segment_name {
+                    div { class: "font-semibold", { t!("segment-stamp", name = name) } }
+                    if suppressed {
+                        div { class: "text-sm", { t!("segment-categories-suppressed", size = threshold) } }
+                    }
+                }
                 div {
                     class: "flex w-full justify-end",
                     if survey_rec_guard.metadata.created_at() != survey_rec_guard.metadata.updated_at() {
@@ -85,7 +162,15 @@ pub fn SurveyReport(entity: ReadSignal<String>) -> Element {
                 div {
                     class: "flex flex-col p-5 items-center break-inside-avoid",
                     div { class: "text-lg font-semibold px-10", "{category.name}" }
-                    if category.answers.is_empty() {
+                    div {
+                        class: "text-sm",
+                        { t!("survey-category-total", total = survey_rec_guard.category_total(category)) }
+                    }
+                    if category.kind == SurveyCategoryKind::Text {
+                        RenderTextCategory { category: category.clone() }
+                    } else if category.kind != SurveyCategoryKind::Choice {
+                        RenderStatsCategory { category: category.clone() }
+                    } else if category.answers.is_empty() {
                         RenderQuestionsCategory { category: category.clone() }
                     } else if category.questions.is_empty() {
                         RenderAnswersCategory { category: category.clone() }
@@ -114,7 +199,7 @@ fn RenderMultiplyCategory(category: SurveyRecordCategory) -> Element {
     let state = use_context::<Signal<SurveyReportState>>();
     let survey_rec = use_context::<Signal<SurveyRecord>>();
     let survey_rec_guard = survey_rec.read();
-    let total = survey_rec_guard.total;
+    let total = survey_rec_guard.category_total(&category).max(1);
     let questions_total = category.questions.len();
 
     let answers = category
@@ -164,7 +249,7 @@ fn RenderMultiplyCategory(category: SurveyRecordCategory) -> Element {
 fn RenderQuestionsCategory(category: SurveyRecordCategory) -> Element {
     let survey_rec = use_context::<Signal<SurveyRecord>>();
     let survey_rec_guard = survey_rec.read();
-    let total = survey_rec_guard.total;
+    let total = survey_rec_guard.category_total(&category).max(1);
     let data = category
         .results
         .get_col(0)
@@ -190,7 +275,7 @@ fn RenderQuestionsCategory(category: SurveyRecordCategory) -> Element {
 fn RenderAnswersCategory(category: SurveyRecordCategory) -> Element {
     let survey_rec = use_context::<Signal<SurveyRecord>>();
     let survey_rec_guard = survey_rec.read();
-    let total = survey_rec_guard.total;
+    let total = survey_rec_guard.category_total(&category).max(1);
     let data = category
         .results
         .get_row(0)
@@ -211,3 +296,110 @@ fn RenderAnswersCategory(category: SurveyRecordCategory) -> Element {
         BarChart { series, categories, distributed: true }
     }
 }
+
+fn category_prompts(category: &SurveyRecordCategory) -> Vec<String> {
+    if category.questions.is_empty() {
+        vec![String::new()]
+    } else {
+        category.questions.values().map(|q| q.name.clone()).collect()
+    }
+}
+
+#[component]
+fn RenderTextCategory(category: SurveyRecordCategory) -> Element {
+    let prompts = category_prompts(&category);
+
+    rsx! {
+        for (idx, prompt) in prompts.into_iter().enumerate() {
+            div {
+                class: "flex flex-col w-full gap-1 pt-3",
+                if !prompt.is_empty() {
+                    div { class: "font-semibold", "{prompt}" }
+                }
+                if let Some(responses) = category.responses.get(idx).filter(|r| !r.is_empty()) {
+                    ul {
+                        class: "list-disc pl-6",
+                        for response in responses.iter() {
+                            li { class: "text-pretty whitespace-pre-line", "{response}" }
+                        }
+                    }
+                } else {
+                    div { class: "text-base-content/60", { t!("no-responses") } }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RenderStatsCategory(category: SurveyRecordCategory) -> Element {
+    let state = use_context::<Signal<SurveyReportState>>();
+    let prompts = category_prompts(&category);
+    let is_ranking = category.kind == SurveyCategoryKind::Ranking;
+
+    let mut rows = prompts
+        .into_iter()
+        .enumerate()
+        .map(|(idx, prompt)| (prompt, category.stats(idx)))
+        .collect::<Vec<_>>();
+    if is_ranking {
+        rows.sort_by(|a, b| a.1.mean.total_cmp(&b.1.mean));
+    }
+
+    let (min, max) = SurveyCategoryKind::scale_range(category.min, category.max);
+    let scale_series = (min..=max)
+        .enumerate()
+        .map(|(col, point)| ChartSeries {
+            name: point.to_string(),
+            data: (0..category.results.rows())
+                .map(|row| {
+                    let count: usize = category.results.get_row(row).iter().map(|&v| *v).sum();
+                    (category.results.get(row, col) * 100).checked_div(count).unwrap_or(0)
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    let scale_categories = category
+        .questions
+        .values()
+        .map(|q| q.name.clone())
+        .collect::<Vec<_>>();
+
+    rsx! {
+        table {
+            class: "report-table table-zebra w-full mt-3",
+            thead {
+                tr {
+                    if is_ranking {
+                        th { class: "text-center", "#" }
+                    }
+                    th { "" }
+                    th { class: "text-center", { t!("responses") } }
+                    th { class: "text-center", if is_ranking { { t!("average-rank") } } else { { t!("mean") } } }
+                    th { class: "text-center", { t!("median") } }
+                    th { class: "text-center", { t!("std-dev") } }
+                }
+            }
+            tbody {
+                for (pos, (prompt, stats)) in rows.into_iter().enumerate() {
+                    tr {
+                        if is_ranking {
+                            td { class: "text-center", "{pos + 1}" }
+                        }
+                        td { "{prompt}" }
+                        td { class: "text-center", "{stats.count}" }
+                        td { class: "text-center", { format!("{:.2}", stats.mean) } }
+                        td { class: "text-center", { format!("{:.2}", stats.median) } }
+                        td { class: "text-center", { format!("{:.2}", stats.std_dev) } }
+                    }
+                }
+            }
+        }
+        if category.kind == SurveyCategoryKind::Scale {
+            div {
+                class: format!("flex w-full {class}", class = if state.read().extended { "" } else { "hidden" }),
+                StackedBarChart { series: scale_series, categories: scale_categories, distributed: false }
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::prelude::*;
+use ::chrono::{TimeZone, Utc};
+
+#[component]
+pub fn StudentTranscript(student: ReadSignal<String>) -> Element {
+    let mut gradebook = use_signal(Gradebook::default);
+
+    use_effect(move || {
+        api_fetch!(
+            GET,
+            format!("/api/v1/gradebook?student={id}", id = student.read()),
+            on_success = move |body: Gradebook| gradebook.set(body)
+        );
+    });
+
+    let gradebook_guard = gradebook.read();
+    let Some(student) = gradebook_guard.students.first() else {
+        return rsx! {};
+    };
+    let average = student.average();
+
+    rsx! {
+        div {
+            class: "flex shrink-0 w-full min-h-0 print:hidden p-1",
+            ul {
+                class: "menu menu-horizontal p-0 m-0 text-base-content flex-nowrap",
+                li {
+                    button {
+                        class: "hover:text-info",
+                        onclick: move |event: MouseEvent| {
+                            event.prevent_default();
+                            event.stop_propagation();
+                            document::eval("window.print()");
+                        },
+                        i { class: "bi bi-printer" }
+                        { t!("print") }
+                    }
+                }
+            }
+        }
+        div {
+            class: "flex flex-1 flex-col print-area overflow-auto px-5 print:px-1 print:contents",
+            "data-theme": "lofi",
+            div {
+                class: "flex flex-col w-full items-center gap-0.25 pt-5",
+                div { class: "text-lg font-semibold", { t!("transcript") } }
+                div {
+                    if let Some(rank) = &student.rank {
+                        "{rank} {student.name}"
+                    } else {
+                        "{student.name}"
+                    }
+                }
+                div {
+                    class: "flex w-full justify-end",
+                    { t!("date-stamp", date = Utc::now().format("%d.%m.%Y").to_string()) }
+                }
+            }
+
+            div {
+                class: "flex w-full h-min-0 w-min-0 print:contents",
+                table {
+                    class: "report-table table-zebra w-[calc(100%_-_1px)]",
+                    thead {
+                        tr {
+                            th { class: "w-min text-center", { t!("date") } }
+                            th { class: "max-w-none text-center", { t!("quiz") } }
+                            th { class: "text-center", { t!("categories") } }
+                            th { class: "rotated", { t!("attempts") } }
+                            th { class: "rotated", { t!("weight") } }
+                            th { class: "rotated font-bold", { t!("total-grade") } }
+                        }
+                    }
+                    tbody {
+                        for entry in student.entries.iter() {
+                            tr {
+                                key: "{entry.record}",
+                                td {
+                                    {
+                                        Utc.timestamp_opt(entry.date, 0)
+                                            .single()
+                                            .map(|d| d.format("%d.%m.%Y").to_string())
+                                            .unwrap_or_default()
+                                    }
+                                }
+                                td {
+                                    class: "text-left",
+                                    div { "{entry.name}" }
+                                    div { class: "text-xs", "{entry.path}" }
+                                }
+                                td {
+                                    class: "text-left text-xs",
+                                    for score in entry.categories.iter() {
+                                        div { "{score.name}: {score.score}%" }
+                                    }
+                                }
+                                td { "{entry.attempts}" }
+                                td { { format!("{:.1}", entry.weight) } }
+                                td {
+                                    class: "font-semibold",
+                                    if entry.excused {
+                                        { t!("excused") }
+                                    } else if entry.grade == 0 {
+                                        { t!("uncertified-placeholder") }
+                                    } else {
+                                        "{entry.grade}"
+                                        if entry.overridden {
+                                            sup { "*" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "flex flex-nowrap w-auto gap-2 py-5",
+                span { { t!("weighted-average") } }
+                span {
+                    class: "font-semibold underline",
+                    { average.map(|a| format!("{a:.2}")).unwrap_or_else(|| t!("uncertified-placeholder")) }
+                }
+            }
+            div {
+                class: "flex flex-nowrap w-auto py-5",
+                span { { t!("supervisor-sign") } }
+            }
+        }
+    }
+}
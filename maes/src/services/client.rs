@@ -1,10 +1,12 @@
 use crate::{prelude::*, services::*};
 use ::reqwest::{
-    Client, Response, Url,
+    Body, Client, Response, Url,
     header::*,
+    multipart::{Form, Part},
 };
 use ::serde::{Serialize, de::DeserializeOwned};
-use ::std::{string::ToString, sync::LazyLock, time::Duration};
+use ::std::{path::Path, string::ToString, sync::LazyLock, time::Duration};
+use ::tokio::{fs, io::AsyncWriteExt};
 use ::shared::common::{Result as SharedResult, Error};
 pub use ::reqwest::Method;
 
@@ -29,9 +31,13 @@ impl Http {
         headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache, no-store, must-revalidate"));
         headers.insert(PRAGMA, HeaderValue::from_static("no-cache"));
 
-        let (_scheme, _host, port) = parse_scheme_host_port(&config.server.host)
+        let (scheme, host, port) = parse_scheme_host_port(&config.server.host)
             .unwrap_or_else(|_| ("".to_string(), "".to_string(), 4583));
-        let base = Url::parse(&format!("http://127.0.0.1:{port}")).unwrap();
+        let base = if config.server.remote && !host.is_empty() {
+            Url::parse(&format!("{scheme}://{host}:{port}")).unwrap()
+        } else {
+            Url::parse(&format!("http://127.0.0.1:{port}")).unwrap()
+        };
 
         let client = Client::builder()
             .pool_idle_timeout(Duration::from_secs(30))
@@ -146,6 +152,60 @@ impl ClientService {
         Self::handle_json_response(request.send().await).await
     }
 
+    pub async fn upload_file(endpoint: impl AsRef<str>, path: impl AsRef<Path>) -> SharedResult<()> {
+        let form = Self::file_form(path).await?;
+        let (url, method) = Self::build_request(Method::POST, endpoint);
+        let request = Self::request_with_headers(method, url).multipart(form);
+        Self::handle_response(request.send().await).await?;
+        Ok(())
+    }
+
+    pub async fn upload_file_with_json<T: DeserializeOwned + 'static>(
+        endpoint: impl AsRef<str>,
+        path: impl AsRef<Path>,
+    ) -> SharedResult<T> {
+        let form = Self::file_form(path).await?;
+        let (url, method) = Self::build_request(Method::POST, endpoint);
+        let request = Self::request_with_headers(method, url).multipart(form);
+        Self::handle_json_response(request.send().await).await
+    }
+
+    /// Streams the file from disk, archives can be too large to buffer in memory.
+    async fn file_form(path: impl AsRef<Path>) -> SharedResult<Form> {
+        let path = path.as_ref();
+        let file = fs::File::open(path).await.map_err(|_| "file-read-error")?;
+        let length = file
+            .metadata()
+            .await
+            .map_err(|_| "file-read-error")?
+            .len();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let part = Part::stream_with_length(Body::from(file), length).file_name(file_name);
+        Ok(Form::new().part("file", part))
+    }
+
+    pub async fn download_file(
+        endpoint: impl AsRef<str>,
+        payload: impl Serialize,
+        path: impl AsRef<Path>,
+    ) -> SharedResult<()> {
+        let (url, method) = Self::build_request(Method::POST, endpoint);
+        let request = Self::request_with_headers(method, url).json(&payload);
+        let mut response = Self::handle_response(request.send().await).await?;
+
+        let mut file = fs::File::create(path.as_ref())
+            .await
+            .map_err(|_| "file-write-error")?;
+        while let Some(chunk) = response.chunk().await.map_err(|_| "network-error")? {
+            file.write_all(&chunk).await.map_err(|_| "file-write-error")?;
+        }
+        file.flush().await.map_err(|_| "file-write-error")?;
+        Ok(())
+    }
+
     pub fn execute_request_with_callbacks(
         method: Method,
         endpoint: impl AsRef<str>,
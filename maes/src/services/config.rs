@@ -64,6 +64,7 @@ impl ConfigService {
                 ident: safe_nanoid!(10),
                 host: "http://192.168.137.1:4583".to_string(),
                 remote: false,
+                storage: StorageKind::File,
             },
             wifi: WiFiConfig {
                 ssid: format!("maes-{}", safe_nanoid!(4)),
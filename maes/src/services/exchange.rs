@@ -1,4 +1,4 @@
-use crate::{prelude::*, services::*};
+use crate::{components::dialogs::*, prelude::*, services::*};
 use ::chrono::Local;
 
 #[derive(Copy, Clone)]
@@ -37,6 +37,19 @@ impl Exchange {
                 }
             })
             .ok();
+            if config.server.remote {
+                match ClientService::download_file(
+                    "/api/v1/exchange/export/download",
+                    ExchangeDownloadPayload { entities },
+                    path.path(),
+                )
+                .await
+                {
+                    Ok(_) => ToastService::success(t!("export-success")),
+                    Err(e) => ToastService::error(t!(e.to_string())),
+                }
+                return;
+            }
             api_call!(
                 POST,
                 "/api/v1/exchange/export",
@@ -48,7 +61,7 @@ impl Exchange {
         });
     }
 
-    pub fn import() {
+    pub fn import(mut preview_dialog: ImportPreviewDialog) {
         spawn(async move {
             let config = ConfigService::read();
             let Some(path) = rfd::AsyncFileDialog::new()
@@ -63,12 +76,133 @@ impl Exchange {
                 }
             })
                 .ok();
-            api_call!(
+            if config.server.remote {
+                match ClientService::upload_file_with_json::<ImportPreview>(
+                    "/api/v1/exchange/import/upload/preview",
+                    path.path(),
+                )
+                .await
+                {
+                    Ok(preview) => preview_dialog.open(preview),
+                    Err(e) => ToastService::error(t!(e.to_string())),
+                }
+                return;
+            }
+            api_fetch!(
                 POST,
-                "/api/v1/exchange/import",
+                "/api/v1/exchange/import/preview",
                 ExchangeImportPayload {
                     path: path.path().to_string_lossy().to_string(),
                 },
+                on_success = move |body: ImportPreview| preview_dialog.open(body)
+            )
+        });
+    }
+
+    pub fn export_package(entities: Vec<String>, name: String) {
+        spawn(async move {
+            let filename = format!(
+                "{}.maesp",
+                name.replace("/", "")
+                    .replace("\\", "")
+                    .replace("\"", "")
+                    .replace("'", "")
+            );
+            let config = ConfigService::read();
+            let Some(path) = rfd::AsyncFileDialog::new()
+                .set_title(t!("export-package-dialog-title"))
+                .set_directory(&config.recent.export)
+                .set_can_create_directories(true)
+                .set_file_name(filename)
+                .add_filter(t!("package-dialog-filter"), &["maesp"])
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            ConfigService::with_mut(|config| {
+                if let Some(path) = path.path().parent() {
+                    config.recent.export = path.to_path_buf()
+                }
+            })
+            .ok();
+            if config.server.remote {
+                match ClientService::download_file(
+                    "/api/v1/exchange/package/download",
+                    ExchangeDownloadPayload { entities },
+                    path.path(),
+                )
+                .await
+                {
+                    Ok(_) => ToastService::success(t!("export-success")),
+                    Err(e) => ToastService::error(t!(e.to_string())),
+                }
+                return;
+            }
+            api_call!(
+                POST,
+                "/api/v1/exchange/package/export",
+                ExportPackagePayload {
+                    path: path.path().to_string_lossy().to_string(),
+                    entities,
+                },
+                on_success = || ToastService::success(t!("export-success"))
+            )
+        });
+    }
+
+    pub fn import_package(target: PackageTarget, on_imported: Callback<Vec<Entity>>) {
+        spawn(async move {
+            let config = ConfigService::read();
+            let Some(path) = rfd::AsyncFileDialog::new()
+                .set_title(t!("import-package-dialog-title"))
+                .set_directory(&config.recent.import)
+                .add_filter(t!("package-dialog-filter"), &["maesp"])
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+            ConfigService::with_mut(|config| {
+                if let Some(path) = path.path().parent() {
+                    config.recent.import = path.to_path_buf()
+                }
+            })
+            .ok();
+            if config.server.remote {
+                let query = [
+                    target.quiz_node.as_ref().map(|node| format!("quiz_node={node}")),
+                    target.survey_node.as_ref().map(|node| format!("survey_node={node}")),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("&");
+                match ClientService::upload_file_with_json::<Vec<Entity>>(
+                    format!("/api/v1/exchange/package/upload?{query}"),
+                    path.path(),
+                )
+                .await
+                {
+                    Ok(entities) => {
+                        ToastService::success(t!("package-imported", count = entities.len()));
+                        on_imported.call(entities)
+                    }
+                    Err(e) => ToastService::error(t!(e.to_string())),
+                }
+                return;
+            }
+            api_fetch!(
+                POST,
+                "/api/v1/exchange/package/import",
+                ImportPackagePayload {
+                    path: path.path().to_string_lossy().to_string(),
+                    target,
+                },
+                on_success = move |body: Vec<Entity>| {
+                    ToastService::success(t!("package-imported", count = body.len()));
+                    on_imported.call(body)
+                }
             )
         });
     }
@@ -1,5 +1,5 @@
 use super::manager::WindowKind;
-use crate::{prelude::*, services::*, elements::*, components::widgets::*, reports::*, pages::*};
+use crate::{prelude::*, services::*, elements::*, components::{tasks::*, widgets::*}, reports::*, pages::*};
 use ::dioxus::desktop::{
     Config as LaunchBuilderConfig, LogicalPosition, LogicalSize, WindowBuilder, use_window
 };
@@ -58,9 +58,14 @@ fn ChildWindow(title: String, kind: WindowKind, claims: Arc<Claims>) -> Element{
                 WindowKind::About => rsx! { About {} },
                 WindowKind::WiFiInstruction => rsx! { WiFiInstruction {} },
                 WindowKind::QuizTickets { task } => rsx! { QuizTickets { task } },
+                WindowKind::QuizVariants { task } => rsx! { QuizVariants { task } },
+                WindowKind::QuizVariantEntry { task } => rsx! { QuizVariantEntry { task } },
+                WindowKind::QuizReview { task } => rsx! { QuizReviewQueue { task } },
+                WindowKind::QuizRegrade { task } => rsx! { QuizRegradePanel { task } },
                 WindowKind::SurveyTickets { task } => rsx! { SurveyTickets { task } },
                 WindowKind::QuizReport { entity } => rsx! { QuizReport { entity } },
                 WindowKind::SurveyReport { entity } => rsx! { SurveyReport { entity } },
+                WindowKind::StudentTranscript { student } => rsx! { StudentTranscript { student } },
                 _ => rsx! {},
             }
         }
@@ -7,9 +7,14 @@ pub enum WindowKind {
     Mock { url: String },
     WiFiInstruction,
     QuizTickets { task: String },
+    QuizVariants { task: String },
+    QuizVariantEntry { task: String },
+    QuizReview { task: String },
+    QuizRegrade { task: String },
     QuizReport { entity: String },
     SurveyTickets { task: String },
     SurveyReport { entity: String },
+    StudentTranscript { student: String },
 }
 
 #[derive(Copy, Clone)]
@@ -23,9 +28,14 @@ impl WindowManager {
             WindowKind::Mock { url } => open_mock_window(title, url.clone()),
             WindowKind::WiFiInstruction |
             WindowKind::QuizTickets { .. } |
+            WindowKind::QuizVariants { .. } |
+            WindowKind::QuizVariantEntry { .. } |
+            WindowKind::QuizReview { .. } |
+            WindowKind::QuizRegrade { .. } |
             WindowKind::SurveyTickets { .. } |
             WindowKind::QuizReport { .. } |
-            WindowKind::SurveyReport { .. } => open_child_window(title, kind, claims),
+            WindowKind::SurveyReport { .. } |
+            WindowKind::StudentTranscript { .. } => open_child_window(title, kind, claims),
         }
     }
 }
\ No newline at end of file
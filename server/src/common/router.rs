@@ -1,6 +1,7 @@
-use crate::{middleware::*, handlers::*};
+use crate::{middleware::*, handlers::*, services::{ARCHIVE_UPLOAD_LIMIT, IMAGE_UPLOAD_LIMIT}};
 use ::axum::{
     Router,
+    extract::DefaultBodyLimit,
     http::{HeaderValue, Method, header},
     routing::{delete, get, patch, post},
 };
@@ -70,12 +71,16 @@ fn api_v1_router() -> Router {
         .nest("/manager/surveys", survey_manager_router())
         .nest("/manager/images", image_manager_router())
         .nest("/reports", reports_router())
+        .nest("/gradebook", gradebook_router())
+        .route("/analytics", get(get_analytics))
         .nest("/exchange", exchange_router())
+        .route("/storage/migrate", post(migrate_storage))
 }
 
 fn entity_router() -> Router {
     Router::new()
         .route("/merge", post(merge_entities))
+        .route("/split/{id}", post(split_entity))
         .route("/payload/{kind}/{id}", get(get_entity_payload))
         .route(
             "/{kind}/{id}",
@@ -149,13 +154,37 @@ fn students_manager_router() -> Router {
                 .post(add_students)
                 .delete(remove_students_by_node),
         )
+        .route("/import", post(import_students))
         .route("/", get(list_students).delete(remove_students))
 }
 
 fn task_manager_router() -> Router {
     Router::new()
         .route("/finish/{id}", post(finish_task))
+        .route("/reopen/{id}", post(reopen_task))
         .route("/categories/{kind}/{id}", get(get_task_categories))
+        .route("/regrade/{id}", post(regrade_quiz_record))
+        .route("/schedule/{id}", patch(update_task_schedule))
+        .route("/deadlines/{id}", patch(update_quiz_deadlines))
+        .route("/review-settings/{id}", patch(update_quiz_review))
+        .route("/students/{id}", post(add_quiz_students))
+        .route("/grade/{id}/{student_id}", patch(override_quiz_grade))
+        .route(
+            "/students/{id}/{student_id}",
+            patch(update_quiz_student).delete(remove_quiz_student),
+        )
+        .route(
+            "/review/{id}",
+            get(list_quiz_reviews).patch(review_quiz_answer),
+        )
+        .route(
+            "/variants/{id}/scan",
+            post(scan_quiz_variant).layer(DefaultBodyLimit::max(IMAGE_UPLOAD_LIMIT)),
+        )
+        .route(
+            "/variants/{id}",
+            post(create_quiz_variants).patch(submit_quiz_variant),
+        )
         .route("/{kind}/{task_id}", get(get_task).delete(delete_task))
         .route("/{kind}", post(create_task))
         .route("/", get(list_tasks))
@@ -165,6 +194,10 @@ fn image_manager_router() -> Router {
     Router::new()
         .route("/copy", post(copy_images))
         .route("/validate/{kind}/{entity_id}", get(validate_images))
+        .route(
+            "/upload/{entity_id}/{item_id}",
+            post(upload_image).layer(DefaultBodyLimit::max(IMAGE_UPLOAD_LIMIT)),
+        )
         .route("/{entity_id}/{item_id}", post(add_image).delete(remove_image))
 }
 
@@ -174,6 +207,8 @@ fn activities_router() -> Router {
         .route("/details/{workspace_id}/{task_id}/{student_id}", get(get_activity_details_with_student))
         .route("/{workspace_id}/{task_id}", get(get_activity))
         .route("/{workspace_id}/{task_id}/{student_id}", get(get_activity_with_student))
+        .route("/review/{workspace_id}/{task_id}/{student_id}", get(get_activity_review))
+        .route("/check", post(check_activity_answer))
         .route("/", post(update_activity))
 }
 
@@ -182,8 +217,35 @@ fn reports_router() -> Router {
         .route("/", get(list_reports).delete(delete_entities))
 }
 
+fn gradebook_router() -> Router {
+    Router::new()
+        .route("/weights", patch(update_gradebook_weights))
+        .route("/", get(get_gradebook))
+}
+
 fn exchange_router() -> Router {
     Router::new()
+        .route("/export/download", post(download_export))
         .route("/export", post(export))
+        .route(
+            "/import/upload",
+            post(upload_import).layer(DefaultBodyLimit::max(ARCHIVE_UPLOAD_LIMIT)),
+        )
+        .route(
+            "/import/upload/preview",
+            post(upload_import_preview).layer(DefaultBodyLimit::max(ARCHIVE_UPLOAD_LIMIT)),
+        )
+        .route("/import/preview", post(import_preview))
+        .route(
+            "/import/staged/{id}",
+            post(apply_staged_import).delete(discard_staged_import),
+        )
         .route("/import", post(import))
+        .route("/package/export", post(export_package))
+        .route("/package/download", post(download_package))
+        .route(
+            "/package/upload",
+            post(upload_package).layer(DefaultBodyLimit::max(ARCHIVE_UPLOAD_LIMIT)),
+        )
+        .route("/package/import", post(import_package))
 }
\ No newline at end of file
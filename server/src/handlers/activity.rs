@@ -1,8 +1,13 @@
-use crate::{handlers::*, services::*};
-use ::axum::{Json, extract::Path, response::Response};
+use crate::{handlers::*, repositories::*};
+use ::axum::{
+    Json,
+    extract::{Path, Query},
+    response::Response,
+};
+use ::chrono::Utc;
 use ::serde::Deserialize;
 use ::serde_json::Value;
-use ::shared::{common::*, models::*};
+use ::shared::{common::*, models::*, payloads::*};
 
 pub async fn get_activity_details(
     Path((workspace, task_id)): Path<(String, String)>,
@@ -10,50 +15,79 @@ pub async fn get_activity_details(
     get_activity_details_with_student(Path((workspace, task_id, "".to_string()))).await
 }
 
+/// Quiz details stay readable after the task closes, so students can still see their result.
 pub async fn get_activity_details_with_student(
     Path((workspace, task_id, student_id)): Path<(String, String, String)>,
 ) -> Result<Response> {
-    let tasks_arc = Store::find::<Tasks>(&workspace, TASKS).await?;
-
-    let kind = {
-        let tasks_guard = tasks_arc.read().await;
-        let task_guard = tasks_guard
-            .get(&task_id)
-            .ok_or((StatusCode::NOT_FOUND, "task-not-found"))?;
-        task_guard.kind
-    };
-
-    match kind {
-        EntityKind::QuizRecord => get_quiz_activity_details(workspace, task_id, student_id).await,
-        EntityKind::SurveyRecord => get_survey_activity_details(workspace, task_id).await,
+    match find_task_status(&workspace, &task_id).await? {
+        (_, TaskStatus::Scheduled) => Err((StatusCode::FORBIDDEN, "task-not-open"))?,
+        (EntityKind::QuizRecord, status) => {
+            let closed = status == TaskStatus::Closed;
+            get_quiz_activity_details(workspace, task_id, student_id, closed).await
+        }
+        (EntityKind::SurveyRecord, TaskStatus::Open) => {
+            get_survey_activity_details(workspace, task_id).await
+        }
+        (EntityKind::SurveyRecord, _) => Err((StatusCode::FORBIDDEN, "task-closed"))?,
         _ => Err((StatusCode::NOT_FOUND, "task-not-found"))?,
     }
 }
 
-pub async fn get_activity(Path((workspace, task_id)): Path<(String, String)>) -> Result<Response> {
-    get_activity_with_student(Path((workspace, task_id, "".to_string()))).await
+/// Finished tasks live on as entities and count as closed, so their results
+/// and reviews stay available to students.
+async fn find_task_status(workspace: &str, task_id: &str) -> Result<(EntityKind, TaskStatus)> {
+    if let Ok(task) = TaskRepository::get(workspace, task_id).await {
+        return Ok((task.kind, task.status(Utc::now().timestamp())));
+    }
+    let entity = EntityRepository::find(workspace, task_id)
+        .await
+        .map_err(|_| (StatusCode::NOT_FOUND, "task-not-found"))?;
+    Ok((entity.kind, TaskStatus::Closed))
+}
+
+#[derive(Default, Deserialize)]
+pub struct ActivityQuery {
+    #[serde(default)]
+    pub language: String,
+}
+
+pub async fn get_activity(
+    Path((workspace, task_id)): Path<(String, String)>,
+    query: Query<ActivityQuery>,
+) -> Result<Response> {
+    get_activity_with_student(Path((workspace, task_id, "".to_string())), query).await
 }
 
 pub async fn get_activity_with_student(
     Path((workspace, task_id, student_id)): Path<(String, String, String)>,
+    Query(query): Query<ActivityQuery>,
 ) -> Result<Response> {
-    let tasks_arc = Store::find::<Tasks>(&workspace, TASKS).await?;
-
-    let kind = {
-        let tasks_guard = tasks_arc.read().await;
-        let task_guard = tasks_guard
-            .get(&task_id)
-            .ok_or((StatusCode::NOT_FOUND, "task-not-found"))?;
-        task_guard.kind
-    };
+    let kind = TaskRepository::get_open(&workspace, &task_id, 0).await?.kind;
 
     match kind {
-        EntityKind::QuizRecord => get_quiz_activity(workspace, task_id, student_id).await,
+        EntityKind::QuizRecord => {
+            get_quiz_activity(workspace, task_id, student_id, query.language).await
+        }
         EntityKind::SurveyRecord => get_survey_activity(workspace, task_id).await,
         _ => Err((StatusCode::NOT_FOUND, "task-not-found"))?,
     }
 }
 
+pub async fn get_activity_review(
+    Path((workspace, task_id, student_id)): Path<(String, String, String)>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Response> {
+    let (kind, status) = find_task_status(&workspace, &task_id).await?;
+    let closed = status == TaskStatus::Closed;
+
+    match kind {
+        EntityKind::QuizRecord => {
+            get_quiz_activity_review(workspace, task_id, student_id, query.language, closed).await
+        }
+        _ => Err((StatusCode::NOT_FOUND, "task-not-found"))?,
+    }
+}
+
 pub async fn update_activity(Json(payload): Json<Value>) -> Result<()> {
     #[derive(Deserialize)]
     #[serde(untagged)]
@@ -65,8 +99,27 @@ pub async fn update_activity(Json(payload): Json<Value>) -> Result<()> {
     let payload: Variants =
         serde_json::from_value(payload).map_err(|_| (StatusCode::BAD_REQUEST, "invalid-payload"))?;
 
+    let (workspace, task_id) = match &payload {
+        Variants::QuizActivity(quiz) => (&quiz.workspace, &quiz.task),
+        Variants::SurveyActivity(survey) => (&survey.workspace, &survey.id),
+    };
+    TaskRepository::get_open(workspace, task_id, SUBMIT_GRACE).await?;
+
     match payload {
         Variants::QuizActivity(quiz) => update_quiz_activity(quiz).await,
         Variants::SurveyActivity(survey) => update_survey_activity(survey).await,
     }
 }
+
+pub async fn check_activity_answer(
+    Json(payload): Json<CheckQuizAnswerPayload>,
+) -> Result<Response> {
+    let kind = TaskRepository::get_open(&payload.workspace, &payload.task, SUBMIT_GRACE)
+        .await?
+        .kind;
+
+    match kind {
+        EntityKind::QuizRecord => check_quiz_answer(payload).await,
+        _ => Err((StatusCode::NOT_FOUND, "task-not-found"))?,
+    }
+}
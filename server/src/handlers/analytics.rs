@@ -0,0 +1,156 @@
+use crate::{middleware::*, repositories::*, services::*};
+use ::axum::{Json, extract::Query};
+use ::shared::{common::*, models::*, payloads::*, utils::*};
+use ::std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Aggregates finished quiz records under a unit node, one row per child unit. Students count
+/// towards the unit they belong to, not the one the record was created for.
+pub async fn get_analytics(
+    session: Session,
+    Query(query): Query<AnalyticsQuery>,
+) -> Result<Json<Analytics>> {
+    session.checked_supervisor()?;
+    let AnalyticsQuery {
+        quiz,
+        tag,
+        node,
+        from,
+        to,
+        period,
+    } = query;
+    let unit_tree = Store::find::<Workspace>(&session.workspace, &session.workspace)
+        .await?
+        .read()
+        .await
+        .unit_tree
+        .clone();
+
+    let visible = session
+        .nodes()
+        .await?
+        .map(|vec| vec.into_iter().collect::<HashSet<_>>());
+    let node = node.unwrap_or_else(|| session.node.clone());
+    if visible
+        .as_ref()
+        .is_some_and(|set| !node.is_empty() && !set.contains(&node))
+    {
+        Err((StatusCode::FORBIDDEN, "forbidden"))?
+    }
+    let scope = (!node.is_empty()).then(|| {
+        unit_tree
+            .node_descendants(&node)
+            .into_iter()
+            .collect::<HashSet<_>>()
+    });
+
+    let unit_ids = if node.is_empty() {
+        unit_tree.root_nodes()
+    } else {
+        unit_tree
+            .values()
+            .filter(|n| n.parent == node)
+            .map(|n| n.id.clone())
+            .collect()
+    };
+    let mut owners = HashMap::<String, usize>::new();
+    for (idx, id) in unit_ids.iter().enumerate() {
+        for descendant in unit_tree.node_descendants(id) {
+            owners.insert(descendant, idx);
+        }
+    }
+
+    let mut analytics = Analytics {
+        node: UnitAnalytics {
+            id: node.clone(),
+            name: unit_tree.get(&node).map(|n| n.name.clone()).unwrap_or_default(),
+            has_children: !unit_ids.is_empty(),
+            ..Default::default()
+        },
+        units: unit_ids
+            .iter()
+            .map(|id| UnitAnalytics {
+                id: id.clone(),
+                name: unit_tree.get(id).map(|n| n.name.clone()).unwrap_or_default(),
+                has_children: unit_tree.values().any(|n| &n.parent == id),
+                ..Default::default()
+            })
+            .collect(),
+        periods: vec![],
+    };
+    let mut node_periods = BTreeMap::<String, AnalyticsStats>::new();
+    let mut unit_periods = vec![BTreeMap::<String, AnalyticsStats>::new(); unit_ids.len()];
+
+    let entities = EntityRepository::list_by_filter(
+        &session.workspace,
+        Some(vec![EntityKind::QuizRecord]),
+        None,
+        None,
+    )
+    .await?;
+    for entity in entities {
+        let created_at = entity.metadata.created_at;
+        if from.is_some_and(|from| created_at < from) || to.is_some_and(|to| created_at > to) {
+            continue;
+        }
+        let quiz_rec_arc = QuizResultRepository::find(&session.workspace, &entity.id).await?;
+        let quiz_rec_guard = quiz_rec_arc.read().await;
+        if quiz_rec_guard.practice
+            || quiz
+                .as_ref()
+                .is_some_and(|quiz| !quiz_rec_guard.sources().contains(&quiz.as_str()))
+        {
+            continue;
+        }
+        if let Some(tag) = &tag
+            && !QuizResultRepository::tags(&quiz_rec_guard).await?.contains(tag)
+        {
+            continue;
+        }
+
+        let key = period.key(created_at);
+        for (idx, student) in quiz_rec_guard.students.values().enumerate() {
+            let grade = student.final_grade();
+            if grade == 0 || student.excused {
+                continue;
+            }
+            let student_node = if student.node.is_empty() {
+                &quiz_rec_guard.node
+            } else {
+                &student.node
+            };
+            if scope.as_ref().is_some_and(|set| !set.contains(student_node))
+                || visible.as_ref().is_some_and(|set| !set.contains(student_node))
+            {
+                continue;
+            }
+            let results = quiz_rec_guard.final_results(idx);
+            let score = if results.is_empty() {
+                0.0
+            } else {
+                results.iter().sum::<usize>() as f64 / results.len() as f64
+            };
+
+            analytics.node.total.add(grade, score);
+            node_periods.entry(key.clone()).or_default().add(grade, score);
+            if let Some(&unit) = owners.get(student_node) {
+                analytics.units[unit].total.add(grade, score);
+                unit_periods[unit].entry(key.clone()).or_default().add(grade, score);
+            }
+        }
+    }
+
+    analytics.periods = node_periods.keys().cloned().collect();
+    analytics.node.periods = into_period_stats(node_periods);
+    for (unit, periods) in analytics.units.iter_mut().zip(unit_periods) {
+        unit.periods = into_period_stats(periods);
+    }
+
+    Ok(Json(analytics))
+}
+
+fn into_period_stats(periods: BTreeMap<String, AnalyticsStats>) -> Vec<AnalyticsPeriodStats> {
+    periods
+        .into_iter()
+        .map(|(period, stats)| AnalyticsPeriodStats { period, stats })
+        .collect()
+}
@@ -4,8 +4,12 @@ use ::axum::{
     extract::Path,
     response::{IntoResponse, Response},
 };
+use ::indexmap::IndexMap;
 use ::shared::{common::*, models::*, utils::*};
-use ::std::str::FromStr;
+use ::std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 use shared::payloads::UpdateEntityPayload;
 
 pub async fn list_reports(session: Session) -> Result<Json<Vec<Entity>>> {
@@ -87,7 +91,7 @@ pub async fn get_entity_payload(
 
     match kind {
         EntityKind::QuizRecord => {
-            let quiz_record = Store::find::<QuizRecord>(&session.workspace, id)
+            let quiz_record = QuizResultRepository::find(&session.workspace, id)
                 .await?
                 .read()
                 .await
@@ -95,11 +99,12 @@ pub async fn get_entity_payload(
             Ok(Json(quiz_record).into_response())
         }
         EntityKind::SurveyRecord => {
-            let survey_record = Store::find::<SurveyRecord>(&session.workspace, id)
+            let mut survey_record = Store::find::<SurveyRecord>(&session.workspace, id)
                 .await?
                 .read()
                 .await
                 .clone();
+            survey_record.suppress_segments();
             Ok(Json(survey_record).into_response())
         }
         _ => Err((StatusCode::NOT_FOUND, "entity-not-found"))?,
@@ -115,7 +120,7 @@ pub async fn update_entity(
 
     match kind {
         EntityKind::QuizRecord => {
-            let quiz_rec_arc = Store::find::<QuizRecord>(&session.workspace, id).await?;
+            let quiz_rec_arc = QuizResultRepository::find(&session.workspace, id).await?;
             let snapshot = {
                 let mut quiz_rec_guard = quiz_rec_arc.write().await;
                 quiz_rec_guard.name = payload.name;
@@ -183,6 +188,83 @@ pub async fn merge_entities(
     Ok(Json(entity))
 }
 
+pub async fn split_entity(
+    session: Session,
+    Path(entity_id): Path<String>,
+) -> Result<Json<Vec<Entity>>> {
+    session.checked_supervisor()?;
+    let entity = EntityRepository::find(&session.workspace, &entity_id).await?;
+    if entity.kind != EntityKind::QuizRecord {
+        Err((StatusCode::CONFLICT, "entities-split-failed"))?
+    }
+
+    let unit_tree = Store::find::<Workspace>(&session.workspace, &session.workspace)
+        .await?
+        .read()
+        .await
+        .unit_tree
+        .clone();
+    let quiz_rec = QuizResultRepository::find(&session.workspace, &entity_id)
+        .await?
+        .read()
+        .await
+        .clone();
+    let roster = if quiz_rec.students.values().any(|s| s.node.is_empty()) {
+        StudentRepository::list_by_filter(&session.workspace, None)
+            .await?
+            .into_iter()
+            .map(|s| (s.id, s.node))
+            .collect::<HashMap<_, _>>()
+    } else {
+        HashMap::new()
+    };
+
+    let mut groups = IndexMap::<String, HashSet<String>>::new();
+    for student in quiz_rec.students.values() {
+        let node = if student.node.is_empty() {
+            roster.get(&student.id).cloned().unwrap_or_default()
+        } else {
+            student.node.clone()
+        };
+        let path = unit_tree.node_path_ids(&node);
+        let unit = path
+            .iter()
+            .position(|id| *id == quiz_rec.node)
+            .and_then(|idx| path.get(idx + 1))
+            .cloned()
+            .unwrap_or_else(|| quiz_rec.node.clone());
+        groups.entry(unit).or_default().insert(student.id.clone());
+    }
+    if groups.len() < 2 {
+        Err((StatusCode::CONFLICT, "entities-split-failed"))?
+    }
+
+    let mut entities = Vec::with_capacity(groups.len());
+    for (node, students) in groups {
+        let mut record = quiz_rec.retain_students(&students);
+        record.id = safe_nanoid!();
+        record.path = unit_tree.node_path(&node);
+        record.node = node;
+        record.metadata.update(&session.username);
+        if let Some(snapshot) = record.snapshot.as_mut() {
+            ImageService::copy_images(
+                &session.workspace,
+                &snapshot.id,
+                &session.workspace,
+                &record.id,
+            )?;
+            snapshot.id = record.id.clone();
+        }
+
+        let entity = record.to_entity();
+        Store::upsert(record).await?;
+        EntityRepository::upsert(&session.workspace, entity.clone()).await?;
+        entities.push(entity);
+    }
+
+    Ok(Json(entities))
+}
+
 async fn merge_quiz_records(session: &Session, entities: Vec<String>) -> Result<Entity> {
     let mut merge_count = 1;
     let unit_tree = Store::find::<Workspace>(&session.workspace, &session.workspace)
@@ -191,21 +273,22 @@ async fn merge_quiz_records(session: &Session, entities: Vec<String>) -> Result<
         .await
         .unit_tree
         .clone();
-    let mut merge = Store::find::<QuizRecord>(&session.workspace, &entities[0])
+    let mut merge = QuizResultRepository::find(&session.workspace, &entities[0])
         .await?
         .read()
         .await
         .clone();
+    let mut assets = vec![merge.assets().to_string()];
     for entity in entities.iter().skip(1) {
-        let quiz_rec_arc = Store::find::<QuizRecord>(&session.workspace, entity).await?;
+        let quiz_rec_arc = QuizResultRepository::find(&session.workspace, entity).await?;
         let quiz_rec_guard = quiz_rec_arc.read().await;
         if merge.id == quiz_rec_guard.id
-            || merge.quiz != quiz_rec_guard.quiz
-            || merge.categories.keys().collect::<Vec<_>>()
-                != quiz_rec_guard.categories.keys().collect::<Vec<_>>()
+            || !merge.same_layout(&quiz_rec_guard)
+            || !merge.merge_snapshot(&quiz_rec_guard)
         {
             continue;
         }
+        assets.push(quiz_rec_guard.assets().to_string());
         merge.students.extend(quiz_rec_guard.students.clone());
         merge.answers.extend_rows(&quiz_rec_guard.answers);
         merge.results.extend_rows(&quiz_rec_guard.results);
@@ -224,6 +307,17 @@ async fn merge_quiz_records(session: &Session, entities: Vec<String>) -> Result<
     }
     merge.id = safe_nanoid!();
     merge.metadata.update(&session.username);
+    if let Some(snapshot) = merge.snapshot.as_mut() {
+        for source in assets {
+            ImageService::copy_images(
+                &session.workspace,
+                &source,
+                &session.workspace,
+                &merge.id,
+            )?;
+        }
+        snapshot.id = merge.id.clone();
+    }
 
     let entity = merge.to_entity();
     Store::upsert(merge).await?;
@@ -245,20 +339,40 @@ async fn merge_survey_records(session: &Session, entities: Vec<String>) -> Resul
         .read()
         .await
         .clone();
+    // Per-category counts are summed below, so legacy records get theirs first.
+    let totals = merge
+        .categories
+        .values()
+        .map(|c| merge.category_total(c))
+        .collect::<Vec<_>>();
+    for (category, total) in merge.categories.values_mut().zip(totals) {
+        category.total = total;
+    }
     for entity in entities.iter().skip(1) {
         let survey_rec_arc = Store::find::<SurveyRecord>(&session.workspace, entity).await?;
         let survey_rec_guard = survey_rec_arc.read().await;
-        if merge.id == survey_rec_guard.id
-            || merge.survey != survey_rec_guard.survey
-            || merge.categories.keys().collect::<Vec<_>>()
-                != survey_rec_guard.categories.keys().collect::<Vec<_>>()
-        {
+        if merge.id == survey_rec_guard.id || !merge.same_layout(&survey_rec_guard) {
             continue;
         }
 
         for category in merge.categories.values_mut() {
-            if let Some(other) = survey_rec_guard.categories.get(&category.id) {
-                category.results.merge(&other.results)
+            let Some(other) = survey_rec_guard.categories.get(&category.id) else {
+                continue;
+            };
+            let rows = category.results.rows();
+            category.results.merge(&other.results);
+            category.total += survey_rec_guard.category_total(other);
+            if !other.responses.is_empty() {
+                category.responses.resize(rows, vec![]);
+                for (responses, others) in category.responses.iter_mut().zip(&other.responses) {
+                    responses.extend(others.iter().cloned());
+                }
+            }
+            if !other.values.is_empty() {
+                category.values.resize(rows, vec![]);
+                for (values, others) in category.values.iter_mut().zip(&other.values) {
+                    values.extend(others.iter().copied());
+                }
             }
         }
 
@@ -268,6 +382,7 @@ async fn merge_survey_records(session: &Session, entities: Vec<String>) -> Resul
             merge.path = unit_tree.node_path(&common_node);
             merge.node = common_node;
         }
+        merge.merge_segments(&survey_rec_guard);
         merge.total += survey_rec_guard.total;
         merge_count += 1;
     }
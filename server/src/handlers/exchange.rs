@@ -1,6 +1,16 @@
 use crate::{common::*, middleware::*, services::*};
-use ::axum::Json;
-use ::shared::{common::*, payloads::*};
+use ::axum::{
+    Json,
+    body::Body,
+    extract::{Multipart, Path, Query},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use ::shared::{common::*, models::*, payloads::*};
+use ::std::path::PathBuf;
+use ::tokio::{fs, io::AsyncReadExt};
+
+const DOWNLOAD_CHUNK_SIZE: usize = 256 * 1024;
 
 pub async fn export(session: Session, Json(payload): Json<ExchangeExportPayload>) -> Result<()> {
     tokio::spawn(async move {
@@ -29,10 +39,186 @@ pub async fn export(session: Session, Json(payload): Json<ExchangeExportPayload>
     Ok(())
 }
 
+pub async fn download_export(
+    session: Session,
+    Json(payload): Json<ExchangeDownloadPayload>,
+) -> Result<Response> {
+    session.checked_admin()?;
+    let path = ExchangeService::mk_temp_file("maes")?;
+
+    let result = if payload.entities.is_empty() {
+        ExchangeService::export_workspace(&session.workspace, &path).await
+    } else {
+        ExchangeService::export(&session.workspace, payload.entities, &path).await
+    };
+    if let Err(e) = result {
+        fs::remove_file(&path).await.ok();
+        return Err(e);
+    }
+
+    stream_temp_file(path, "export.maes").await
+}
+
 pub async fn import(connection: Connection, Json(payload): Json<ExchangeImportPayload>) -> Result<()> {
     connection.checked()?;
+    spawn_import(PathBuf::from(payload.path), false);
+    Ok(())
+}
+
+pub async fn upload_import(session: Session, mut multipart: Multipart) -> Result<()> {
+    session.checked_admin()?;
+    let path = ExchangeService::mk_temp_file("maes")?;
+    UploadService::save_to_file(&mut multipart, &path).await?;
+    spawn_import(path, true);
+    Ok(())
+}
+
+pub async fn import_preview(
+    connection: Connection,
+    Json(payload): Json<ExchangeImportPayload>,
+) -> Result<Json<ImportPreview>> {
+    connection.checked()?;
+    let stage_id = ExchangeService::stage(payload.path).await?;
+    stage_preview(stage_id).await
+}
+
+pub async fn upload_import_preview(
+    session: Session,
+    mut multipart: Multipart,
+) -> Result<Json<ImportPreview>> {
+    session.checked_admin()?;
+    let path = ExchangeService::mk_temp_file("maes")?;
+    let result = UploadService::save_to_file(&mut multipart, &path).await;
+    let result = match result {
+        Ok(_) => ExchangeService::stage(&path).await,
+        Err(e) => Err(e),
+    };
+    fs::remove_file(&path).await.ok();
+    stage_preview(result?).await
+}
+
+pub async fn apply_staged_import(
+    Path(stage_id): Path<String>,
+    Json(payload): Json<ImportResolution>,
+) -> Result<()> {
+    ExchangeService::apply(stage_id, &payload).await
+}
+
+pub async fn discard_staged_import(Path(stage_id): Path<String>) -> Result<()> {
+    ExchangeService::discard(stage_id)
+}
+
+async fn stage_preview(stage_id: String) -> Result<Json<ImportPreview>> {
+    match ExchangeService::preview(&stage_id).await {
+        Ok(preview) => Ok(Json(preview)),
+        Err(e) => {
+            ExchangeService::discard(&stage_id).ok();
+            Err(e)
+        }
+    }
+}
+
+pub async fn export_package(
+    session: Session,
+    Json(payload): Json<ExportPackagePayload>,
+) -> Result<()> {
+    session.checked_admin()?;
+    ExchangeService::export_package(&session.workspace, payload.entities, payload.path).await
+}
+
+pub async fn download_package(
+    session: Session,
+    Json(payload): Json<ExchangeDownloadPayload>,
+) -> Result<Response> {
+    session.checked_admin()?;
+    let path = ExchangeService::mk_temp_file("maesp")?;
+    if let Err(e) = ExchangeService::export_package(&session.workspace, payload.entities, &path).await {
+        fs::remove_file(&path).await.ok();
+        return Err(e);
+    }
+    stream_temp_file(path, "package.maesp").await
+}
+
+pub async fn import_package(
+    session: Session,
+    Json(payload): Json<ImportPackagePayload>,
+) -> Result<Json<Vec<Entity>>> {
+    session.checked_admin()?;
+    let entities = ExchangeService::import_package(
+        &session.workspace,
+        &session.username,
+        payload.path,
+        &payload.target,
+    )
+    .await?;
+    Ok(Json(entities))
+}
+
+pub async fn upload_package(
+    session: Session,
+    Query(target): Query<PackageTarget>,
+    mut multipart: Multipart,
+) -> Result<Json<Vec<Entity>>> {
+    session.checked_admin()?;
+    let path = ExchangeService::mk_temp_file("maesp")?;
+    let result = match UploadService::save_to_file(&mut multipart, &path).await {
+        Ok(_) => {
+            ExchangeService::import_package(&session.workspace, &session.username, &path, &target)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    fs::remove_file(&path).await.ok();
+    Ok(Json(result?))
+}
+
+async fn stream_temp_file(path: PathBuf, filename: &str) -> Result<Response> {
+    let file = fs::File::open(&path).await.map_err(map_log_err)?;
+    let length = file.metadata().await.map_err(map_log_err)?.len();
+
+    let stream = futures::stream::unfold(Some((file, path)), |state| async move {
+        let (mut file, path) = state?;
+        let mut buf = vec![0u8; DOWNLOAD_CHUNK_SIZE];
+        match file.read(&mut buf).await {
+            Ok(0) => {
+                drop(file);
+                fs::remove_file(&path).await.ok();
+                None
+            }
+            Ok(n) => {
+                buf.truncate(n);
+                Some((Ok(buf), Some((file, path))))
+            }
+            Err(e) => {
+                drop(file);
+                fs::remove_file(&path).await.ok();
+                Some((Err::<Vec<u8>, _>(e), None))
+            }
+        }
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::CONTENT_LENGTH, length.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+fn spawn_import(path: PathBuf, remove_after: bool) {
     tokio::spawn(async move {
-        match ExchangeService::import(&payload.path).await {
+        let result = ExchangeService::import(&path).await;
+        if remove_after {
+            fs::remove_file(&path).await.ok();
+        }
+
+        match result {
             Ok(_) => {
                 State::dispatcher().task_send(DispatcherTask::Finished);
                 State::dispatcher().msg_send(DispatcherMessage::Info("import-success".into()))
@@ -47,5 +233,4 @@ pub async fn import(connection: Connection, Json(payload): Json<ExchangeImportPa
             }
         }
     });
-    Ok(())
 }
@@ -0,0 +1,143 @@
+use crate::{middleware::*, repositories::*, services::*};
+use ::axum::{Json, extract::Query};
+use ::shared::{common::*, models::*, payloads::*};
+use ::std::collections::{HashMap, HashSet};
+
+pub async fn get_gradebook(
+    session: Session,
+    Query(query): Query<GradebookQuery>,
+) -> Result<Json<Gradebook>> {
+    session.checked_supervisor()?;
+    let GradebookQuery { student, node } = query;
+    let (unit_tree, weights) = {
+        let ws_arc = Store::find::<Workspace>(&session.workspace, &session.workspace).await?;
+        let ws_guard = ws_arc.read().await;
+        (ws_guard.unit_tree.clone(), ws_guard.gradebook_weights.clone())
+    };
+
+    let visible = session
+        .nodes()
+        .await?
+        .map(|vec| vec.into_iter().collect::<HashSet<_>>());
+    let nodes = node.map(|node| unit_tree.node_descendants(node));
+    let mut students = StudentRepository::list_by_filter(&session.workspace, nodes)
+        .await?
+        .into_iter()
+        .filter(|s| visible.as_ref().is_none_or(|set| set.contains(&s.node)))
+        .filter(|s| student.as_ref().is_none_or(|id| &s.id == id))
+        .map(|s| GradebookStudent {
+            id: s.id,
+            rank: s.rank,
+            name: s.name,
+            node: s.node,
+            entries: vec![],
+        })
+        .collect::<Vec<_>>();
+    students.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    if students.is_empty() {
+        return Ok(Json(Gradebook { weights, students }));
+    }
+
+    // Records are scanned whatever their node, a student keeps their history after a transfer.
+    let mut records = EntityRepository::list_by_filter(
+        &session.workspace,
+        Some(vec![EntityKind::QuizRecord]),
+        None,
+        None,
+    )
+    .await?
+    .into_iter()
+    .map(|e| (e.id, true))
+    .collect::<Vec<_>>();
+    records.extend(
+        TaskRepository::list_by_filter(&session.workspace, Some(vec![EntityKind::QuizRecord]), None)
+            .await?
+            .into_iter()
+            .map(|t| (t.id, false)),
+    );
+
+    let ids = students.iter().map(|s| s.id.clone()).collect::<HashSet<_>>();
+    let mut entries = HashMap::<String, Vec<GradebookEntry>>::with_capacity(ids.len());
+    for (record_id, finished) in records {
+        let quiz_rec_arc = QuizResultRepository::find(&session.workspace, &record_id).await?;
+        let quiz_rec_guard = quiz_rec_arc.read().await;
+        if quiz_rec_guard.practice || !quiz_rec_guard.students.keys().any(|id| ids.contains(id)) {
+            continue;
+        }
+
+        let tags = if weights.tags.is_empty() {
+            vec![]
+        } else {
+            QuizResultRepository::tags(&quiz_rec_guard).await?
+        };
+        let weight = weights.weight(&quiz_rec_guard.quiz, &tags);
+
+        for (idx, student) in quiz_rec_guard.students.values().enumerate() {
+            if !ids.contains(&student.id) {
+                continue;
+            }
+            let categories = quiz_rec_guard
+                .categories
+                .values()
+                .zip(quiz_rec_guard.final_results(idx))
+                .map(|(c, score)| GradebookScore {
+                    name: c.name.clone(),
+                    score,
+                })
+                .collect();
+            entries
+                .entry(student.id.clone())
+                .or_default()
+                .push(GradebookEntry {
+                    record: quiz_rec_guard.id.clone(),
+                    quiz: quiz_rec_guard.quiz.clone(),
+                    name: quiz_rec_guard.name.clone(),
+                    path: quiz_rec_guard.path.clone(),
+                    date: quiz_rec_guard.metadata.created_at,
+                    finished,
+                    grade: student.final_grade(),
+                    overridden: student.grade_override.is_some(),
+                    attempts: student.attempts,
+                    excused: student.excused,
+                    weight,
+                    categories,
+                    tags: tags.clone(),
+                });
+        }
+    }
+
+    for student in students.iter_mut() {
+        if let Some(mut vec) = entries.remove(&student.id) {
+            vec.sort_unstable_by(|a, b| a.date.cmp(&b.date).then(a.name.cmp(&b.name)));
+            student.entries = vec;
+        }
+    }
+
+    Ok(Json(Gradebook { weights, students }))
+}
+
+pub async fn update_gradebook_weights(
+    session: Session,
+    Json(payload): Json<GradebookWeights>,
+) -> Result<Json<GradebookWeights>> {
+    session.checked_admin()?;
+    if payload
+        .quizzes
+        .values()
+        .chain(payload.tags.values())
+        .any(|w| !w.is_finite() || *w < 0.0)
+    {
+        Err((StatusCode::BAD_REQUEST, "bad-request"))?
+    }
+
+    let ws_arc = Store::find::<Workspace>(&session.workspace, &session.workspace).await?;
+    let snapshot = {
+        let mut ws_guard = ws_arc.write().await;
+        ws_guard.gradebook_weights = payload.clone();
+        ws_guard.metadata.update(&session.username);
+        ws_guard.clone()
+    };
+    Store::upsert(snapshot).await?;
+
+    Ok(Json(payload))
+}
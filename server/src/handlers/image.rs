@@ -1,5 +1,9 @@
 use crate::{handlers::*, middleware::*, services::*};
-use ::axum::{Json, extract::Path, response::Response};
+use ::axum::{
+    Json,
+    extract::{Multipart, Path},
+    response::Response,
+};
 use ::shared::{common::*, models::*, payloads::*};
 use ::std::str::FromStr;
 
@@ -18,6 +22,21 @@ pub async fn add_image(
     Ok(())
 }
 
+pub async fn upload_image(
+    session: Session,
+    Path((entity_id, item_id)): Path<(String, String)>,
+    mut multipart: Multipart,
+) -> Result<()> {
+    session.checked_admin()?;
+    let data = UploadService::read_bytes(&mut multipart).await?;
+
+    ImageService::convert_bytes_and_save(data, &session.workspace, entity_id, item_id)
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "image-save-error"))?;
+
+    Ok(())
+}
+
 pub async fn remove_image(
     session: Session,
     Path((entity_id, item_id)): Path<(String, String)>,
@@ -1,8 +1,14 @@
+mod analytics;
 mod auth;
 mod entity;
+mod gradebook;
 mod health;
 mod quiz_manager;
 mod quiz_activity;
+mod quiz_regrade;
+mod quiz_review;
+mod quiz_students;
+mod quiz_variant;
 mod students;
 mod survey_manager;
 mod task;
@@ -12,8 +18,10 @@ mod survey_activity;
 mod image;
 mod activity;
 mod exchange;
+mod storage;
 
 pub use self::{
-    auth::*, entity::*, health::*, quiz_manager::*, quiz_activity::*, students::*, survey_manager::*,
+    analytics::*, auth::*, entity::*, gradebook::*, health::*, quiz_manager::*, quiz_activity::*, quiz_regrade::*, quiz_review::*, quiz_students::*, quiz_variant::*, students::*, survey_manager::*,
     task::*, workspace::*, workspace_users::*, survey_activity::*, image::*, activity::*, exchange::*,
+    storage::*,
 };
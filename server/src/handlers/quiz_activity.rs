@@ -1,10 +1,11 @@
 use crate::{middleware::*, repositories::*, services::*};
 use ::axum::{
     Json,
+    extract::Path,
     response::{IntoResponse, Response},
 };
 use ::indexmap::IndexMap;
-use ::rand::prelude::SliceRandom;
+use ::rand::{Rng, prelude::SliceRandom};
 use ::shared::{common::*, models::*, payloads::*, utils::*};
 use ::std::{
     collections::{HashMap, HashSet},
@@ -12,13 +13,13 @@ use ::std::{
 };
 
 pub async fn get_quiz_record(session: &Session, id: impl Into<String>) -> Result<Response> {
-    let quiz_rec_arc = Store::find::<QuizRecord>(&session.workspace, id).await?;
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, id).await?;
     let quiz_rec = { quiz_rec_arc.read().await.clone() };
     Ok(Json(quiz_rec).into_response())
 }
 
 pub async fn get_quiz_record_base(session: &Session, id: impl Into<String>) -> Result<Response> {
-    let quiz_rec_arc = Store::find::<QuizRecord>(&session.workspace, id).await?;
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, id).await?;
     let quiz_rec_base = {
         let quiz_rec_guard = quiz_rec_arc.read().await;
         quiz_rec_guard.to_base()
@@ -34,6 +35,9 @@ pub async fn create_quiz_record(session: &Session, payload: CreateTaskPayload) -
         name,
         path,
         categories,
+        practice,
+        review,
+        ..
     } = payload;
 
     let nodes = {
@@ -42,7 +46,7 @@ pub async fn create_quiz_record(session: &Session, payload: CreateTaskPayload) -
     };
 
     let students_fut = StudentRepository::list_by_filter(&session.workspace, Some(nodes));
-    let quiz_fut = Store::find::<Quiz>(&session.workspace, id);
+    let quiz_fut = Store::find::<Quiz>(&session.workspace, &id);
     let (mut students_vec, quiz_arc) = tokio::try_join!(students_fut, quiz_fut)?;
 
     if students_vec.is_empty() {
@@ -61,39 +65,74 @@ pub async fn create_quiz_record(session: &Session, payload: CreateTaskPayload) -
                 id: s.id,
                 rank: s.rank,
                 name: s.name,
-                attempts: 0,
-                grade: 0,
+                node: s.node,
+                ..Default::default()
             },
         );
     }
 
+    // Composite exams draw some categories from other quizzes, the payload quiz stays the
+    // primary one for attempts, grading thresholds and languages.
+    let mut sources = IndexMap::<String, Quiz>::new();
+    sources.insert(id.clone(), quiz_arc.read().await.clone());
+    for category_req in categories.iter() {
+        if category_req.quiz.is_empty() || sources.contains_key(&category_req.quiz) {
+            continue;
+        }
+        let source = Store::find::<Quiz>(&session.workspace, &category_req.quiz)
+            .await?
+            .read()
+            .await
+            .clone();
+        sources.insert(source.id.clone(), source);
+    }
+
     let record = {
-        let quiz_guard = quiz_arc.read().await;
+        let quiz = &sources[&id];
 
         let mut task_categories =
             IndexMap::<String, QuizRecordCategory>::with_capacity(categories.len());
-        let mut total_count = 0_i64;
+        let mut snapshot_categories = IndexMap::with_capacity(categories.len());
+        let mut duration = 0_i64;
 
         for category_req in categories {
-            let Some(category) = quiz_guard.categories.get(&category_req.id) else {
+            let source_id = if category_req.quiz.is_empty() {
+                &id
+            } else {
+                &category_req.quiz
+            };
+            let source = &sources[source_id];
+            let Some(category) = source.categories.get(&category_req.id) else {
                 continue;
             };
+            if task_categories.contains_key(&category.id) {
+                continue;
+            }
 
-            let count = category_req.count.min(category.questions.len());
+            let count = if category_req.rules.is_empty() {
+                category_req.count
+            } else {
+                category.assemble(&category_req.rules, |_| {}).len()
+            };
+            let count = count.min(category.questions.len());
             if count == 0 {
                 continue;
             }
-            total_count += count as i64;
+            duration += source.duration * count as i64;
 
             let category_id = category.id.clone();
             task_categories.insert(
                 category_id.clone(),
                 QuizRecordCategory {
-                    id: category_id,
+                    id: category_id.clone(),
+                    quiz: source.id.clone(),
+                    quiz_name: source.name.clone(),
                     name: category.name.clone(),
                     count,
+                    rules: category_req.rules,
                 },
             );
+            snapshot_categories.insert(category_id, category.clone());
         }
 
         let answers = Grid::<HashMap<String, HashSet<String>>>::new(
@@ -104,23 +143,58 @@ pub async fn create_quiz_record(session: &Session, payload: CreateTaskPayload) -
 
         let results = Grid::<usize>::new(students.len(), task_categories.len(), 0_usize);
 
+        let record_id = safe_nanoid!();
+        let mut snapshot = quiz.clone();
+        snapshot.id = record_id.clone();
+        snapshot.categories = snapshot_categories;
+
+        let languages = quiz
+            .languages
+            .iter()
+            .filter(|l| sources.values().all(|q| q.languages.contains(l)))
+            .cloned()
+            .collect();
+
         QuizRecord {
-            id: safe_nanoid!(),
+            version: QUIZ_RECORD_VERSION,
+            id: record_id,
             workspace: session.workspace.clone(),
-            quiz: quiz_guard.id.clone(),
+            quiz: quiz.id.clone(),
             name,
             node,
             path,
-            attempts: quiz_guard.attempts,
-            duration: quiz_guard.duration * total_count,
-            grade: quiz_guard.grade.clone(),
+            attempts: quiz.attempts,
+            duration,
+            grade: quiz.grade.clone(),
+            languages,
             categories: task_categories,
             answers,
             students,
             results,
+            variants: vec![],
+            snapshot: Some(snapshot),
+            regrades: vec![],
+            practice,
+            review,
             metadata: Metadata::new(&session.username),
         }
     };
+    let image_ids = record
+        .snapshot
+        .as_ref()
+        .map(|s| s.image_ids())
+        .unwrap_or_default();
+    if !image_ids.is_empty() {
+        for source in record.sources() {
+            ImageService::copy_selected_images(
+                &record.workspace,
+                source,
+                &record.workspace,
+                &record.id,
+                image_ids.clone(),
+            )?;
+        }
+    }
 
     let task = Task {
         id: record.id.clone(),
@@ -130,6 +204,9 @@ pub async fn create_quiz_record(session: &Session, payload: CreateTaskPayload) -
         node: record.node.clone(),
         path: record.path.clone(),
         progress: 0,
+        opens_at: None,
+        closes_at: None,
+        practice: record.practice,
         metadata: record.metadata.clone(),
     };
 
@@ -149,12 +226,26 @@ pub async fn get_quiz_categories(
         quiz_guard
             .categories
             .values()
-            .map(|c| TaskCategory {
-                id: c.id.clone(),
-                name: c.name.clone(),
-                count: c.count,
-                total: c.questions.len(),
-                checked: c.count > 0,
+            .map(|c| {
+                let mut tags = c
+                    .questions
+                    .values()
+                    .flat_map(|q| q.tags.iter().cloned())
+                    .collect::<Vec<_>>();
+                tags.sort_unstable();
+                tags.dedup();
+
+                TaskCategory {
+                    id: c.id.clone(),
+                    quiz: quiz_guard.id.clone(),
+                    quiz_name: quiz_guard.name.clone(),
+                    name: c.name.clone(),
+                    count: c.count,
+                    total: c.questions.len(),
+                    checked: c.count > 0,
+                    tags,
+                    rules: vec![],
+                }
             })
             .collect::<Vec<_>>()
     };
@@ -162,99 +253,341 @@ pub async fn get_quiz_categories(
     Ok(categories)
 }
 
+pub async fn update_quiz_deadlines(
+    session: Session,
+    Path(task_id): Path<String>,
+    Json(payload): Json<UpdateQuizDeadlinesPayload>,
+) -> Result<()> {
+    session.checked_supervisor()?;
+    let UpdateQuizDeadlinesPayload { deadlines } = payload;
+
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, task_id).await?;
+    let snapshot = {
+        let mut quiz_rec_guard = quiz_rec_arc.write().await;
+        for (student_id, deadline) in deadlines {
+            let student = quiz_rec_guard
+                .students
+                .get_mut(&student_id)
+                .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
+            student.deadline = deadline;
+        }
+        quiz_rec_guard.metadata.update(&session.username);
+        quiz_rec_guard.clone()
+    };
+    Store::upsert(snapshot).await
+}
+
+pub async fn update_quiz_review(
+    session: Session,
+    Path(task_id): Path<String>,
+    Json(payload): Json<UpdateQuizReviewPayload>,
+) -> Result<()> {
+    session.checked_supervisor()?;
+
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, task_id).await?;
+    let snapshot = {
+        let mut quiz_rec_guard = quiz_rec_arc.write().await;
+        quiz_rec_guard.review = payload.review;
+        quiz_rec_guard.metadata.update(&session.username);
+        quiz_rec_guard.clone()
+    };
+    Store::upsert(snapshot).await
+}
+
 pub async fn get_quiz_activity_details(
     workspace: impl Into<String>,
     task_id: impl Into<String>,
     student: impl Into<String>,
+    closed: bool,
 ) -> Result<Response> {
     let ws_id = workspace.into();
     let task_id = task_id.into();
     let student_id = student.into();
 
-    let quiz_rec_arc = Store::find::<QuizRecord>(&ws_id, task_id).await?;
+    let quiz_rec_arc = Store::find::<QuizRecord>(&ws_id, &task_id).await?;
+    let entry = QuizResultRepository::entry(&ws_id, &task_id, &student_id).await?;
     let activity = {
         let quiz_rec_guard = quiz_rec_arc.read().await;
         let student_idx = quiz_rec_guard
             .students
             .get_index_of(&student_id)
             .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
-        let scores = quiz_rec_guard.results.get_row(student_idx);
-        let score = if scores.is_empty() {
+        let mut student = quiz_rec_guard.students.index(student_idx).clone();
+        let scores = match entry {
+            Some(entry) => {
+                student.attempts = entry.attempts;
+                student.grade = entry.grade;
+                entry.results
+            }
+            None => quiz_rec_guard
+                .results
+                .get_row(student_idx)
+                .into_iter()
+                .copied()
+                .collect(),
+        };
+        let score = if quiz_rec_guard.practice {
+            student.practice.last().map_or(0, |p| p.score)
+        } else if scores.is_empty() {
             0
         } else {
-            let sum: i32 = scores.iter().map(|&&v| v as i32).sum();
+            let sum: i32 = scores.iter().map(|&v| v as i32).sum();
             ((sum as f64) / (scores.len() as f64)).round() as usize
         };
-        let student = quiz_rec_guard.students.index(student_idx);
-        let can_take = quiz_rec_guard.attempts == 0 || quiz_rec_guard.attempts > student.attempts;
+        let review = quiz_rec_guard.review_mode(&student, closed);
+        let (grade, score) = if review == QuizReviewMode::Hidden {
+            (0, 0)
+        } else {
+            (student.final_grade(), score)
+        };
+        let can_take = !closed
+            && !student.excused
+            && (quiz_rec_guard.practice
+                || quiz_rec_guard
+                    .attempts_limit(&student)
+                    .is_none_or(|limit| limit > student.attempts));
 
         QuizActivityDetails {
             workspace: quiz_rec_guard.workspace.clone(),
             quiz: quiz_rec_guard.quiz.clone(),
             quiz_name: quiz_rec_guard.name.clone(),
-            duration: quiz_rec_guard.duration,
-            student: student.id.clone(),
-            student_rank: student.rank.clone(),
-            student_name: student.name.clone(),
-            grade: student.grade,
+            duration: quiz_rec_guard.duration_for(&student),
+            student: student.id,
+            student_rank: student.rank,
+            student_name: student.name,
+            grade,
             score,
             can_take,
+            languages: quiz_rec_guard.languages.clone(),
+            practice: quiz_rec_guard.practice,
+            practice_attempts: student.practice.len(),
+            review: if student.attempts > 0 {
+                review
+            } else {
+                QuizReviewMode::Hidden
+            },
         }
     };
 
     Ok(Json(activity).into_response())
 }
 
+pub async fn get_quiz_activity_review(
+    workspace: impl Into<String>,
+    task_id: impl Into<String>,
+    student: impl Into<String>,
+    language: impl Into<String>,
+    closed: bool,
+) -> Result<Response> {
+    let ws_id = workspace.into();
+    let task_id = task_id.into();
+    let student_id = student.into();
+    let language = language.into();
+
+    let quiz_rec_arc = Store::find::<QuizRecord>(&ws_id, &task_id).await?;
+    let entry = QuizResultRepository::entry(&ws_id, &task_id, &student_id).await?;
+    let (mut review, categories, answers, open, quiz) = {
+        let quiz_rec_guard = quiz_rec_arc.read().await;
+        let student_idx = quiz_rec_guard
+            .students
+            .get_index_of(&student_id)
+            .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
+        let mut student = quiz_rec_guard.students.index(student_idx).clone();
+        let (answers, results) = match entry {
+            Some(entry) => {
+                student.attempts = entry.attempts;
+                student.grade = entry.grade;
+                student.open = entry.open;
+                (entry.answers, entry.results)
+            }
+            None => (
+                quiz_rec_guard
+                    .answers
+                    .get_row(student_idx)
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                quiz_rec_guard
+                    .results
+                    .get_row(student_idx)
+                    .into_iter()
+                    .copied()
+                    .collect::<Vec<_>>(),
+            ),
+        };
+        let mode = quiz_rec_guard.review_mode(&student, closed);
+        if mode == QuizReviewMode::Hidden {
+            Err((StatusCode::FORBIDDEN, "review-not-available"))?
+        }
+        if student.attempts == 0 {
+            Err((StatusCode::NOT_FOUND, "no-attempts"))?
+        }
+
+        let categories = quiz_rec_guard
+            .categories
+            .values()
+            .cloned()
+            .zip(results)
+            .map(|(category, result)| {
+                let result = student.result_override(&category.id).unwrap_or(result);
+                (category, result)
+            })
+            .collect::<Vec<_>>();
+        let score = if categories.is_empty() {
+            0
+        } else {
+            categories.iter().map(|(_, r)| r).sum::<usize>() / categories.len()
+        };
+        let review = QuizAttemptReview {
+            quiz: quiz_rec_guard.assets().to_string(),
+            quiz_name: quiz_rec_guard.name.clone(),
+            student_name: student.name.clone(),
+            mode,
+            grade: student.final_grade(),
+            score,
+            categories: vec![],
+            questions: vec![],
+        };
+
+        (
+            review,
+            categories,
+            answers,
+            student.open,
+            QuizResultRepository::quiz(&quiz_rec_guard).await?,
+        )
+    };
+
+    if review.mode >= QuizReviewMode::Categories {
+        review.categories = categories
+            .iter()
+            .map(|(category, score)| QuizAttemptCategory {
+                name: category.name.clone(),
+                score: *score,
+            })
+            .collect();
+    }
+
+    if review.mode >= QuizReviewMode::Answers {
+        for ((category, _), cell) in categories.iter().zip(answers.iter()) {
+            let Some(quiz_category) = quiz.categories.get(&category.id) else {
+                continue;
+            };
+            for question in quiz_category.questions.values() {
+                let Some(answered) = cell.get(&question.id) else {
+                    continue;
+                };
+                let passed = match open.get(&question.id) {
+                    Some(result) => result.is_passed(),
+                    None => !question.is_open() && question.correct_answers() == *answered,
+                };
+                if passed {
+                    continue;
+                }
+                let answered = if question.is_open() {
+                    answered.iter().cloned().collect()
+                } else {
+                    question
+                        .answers
+                        .values()
+                        .filter(|a| answered.contains(&a.id))
+                        .map(|a| a.localized_name(&language).to_string())
+                        .collect()
+                };
+                review.questions.push(QuizAttemptQuestion {
+                    id: question.id.clone(),
+                    category: category.name.clone(),
+                    name: question.localized_name(&language).to_string(),
+                    img: question.img,
+                    answered,
+                    correct: question
+                        .answers
+                        .values()
+                        .filter(|a| a.correct || question.is_open())
+                        .map(|a| a.localized_name(&language).to_string())
+                        .collect(),
+                    explanation: question.explanation.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(Json(review).into_response())
+}
+
 pub async fn get_quiz_activity(
     workspace: impl Into<String>,
     task_id: impl Into<String>,
     student: impl Into<String>,
+    language: impl Into<String>,
 ) -> Result<Response> {
     let ws_id = workspace.into();
     let task_id = task_id.into();
     let student_id = student.into();
+    let language = language.into();
 
     let quiz_rec_arc = Store::find::<QuizRecord>(&ws_id, &task_id).await?;
-    let (categories_map, quiz_id, duration) = {
+    let entry = QuizResultRepository::entry(&ws_id, &task_id, &student_id).await?;
+    let (categories_map, quiz, duration, language, practice) = {
         let quiz_rec_guard = quiz_rec_arc.read().await;
-        if quiz_rec_guard
+        let student = quiz_rec_guard
             .students
             .get(&student_id)
-            .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?
-            .attempts
-            > quiz_rec_guard.attempts
-            && quiz_rec_guard.attempts > 0
+            .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
+        if student.excused {
+            Err((StatusCode::FORBIDDEN, "student-excused"))?
+        }
+        if !quiz_rec_guard.practice
+            && quiz_rec_guard
+                .attempts_limit(student)
+                .is_some_and(|limit| entry.map_or(student.attempts, |e| e.attempts) > limit)
         {
             Err("attempts-exceeded")?
         }
+        if student.is_past_deadline(0) {
+            Err((StatusCode::FORBIDDEN, "deadline-passed"))?
+        }
+        if !language.is_empty() && !quiz_rec_guard.languages.contains(&language) {
+            Err((StatusCode::BAD_REQUEST, "language-not-found"))?
+        }
         let map = quiz_rec_guard
             .categories
             .values()
-            .map(|c| (c.id.clone(), c.count))
-            .collect::<HashMap<String, usize>>();
+            .map(|c| (c.id.clone(), (c.count, c.rules.clone())))
+            .collect::<HashMap<String, (usize, Vec<QuizAssemblyRule>)>>();
 
-        (map, quiz_rec_guard.quiz.clone(), quiz_rec_guard.duration)
+        (
+            map,
+            QuizResultRepository::quiz(&quiz_rec_guard).await?,
+            quiz_rec_guard.duration_for(student),
+            language,
+            quiz_rec_guard.practice,
+        )
     };
 
-    let quiz = Store::find::<Quiz>(&ws_id, &quiz_id)
-        .await?
-        .read()
-        .await
-        .clone();
-
+    let mut rng = rand::rng();
     let mut questions = Vec::new();
-    for (category_id, category_count) in categories_map {
-        let question = generate_category_questions(&quiz, &category_id, category_count).await;
+    for (category_id, (category_count, rules)) in categories_map {
+        let question = generate_category_questions(
+            &quiz,
+            &category_id,
+            category_count,
+            &rules,
+            &language,
+            &mut rng,
+        );
         questions.extend(question);
     }
-    questions.shuffle(&mut rand::rng());
+    questions.shuffle(&mut rng);
 
     let activity = QuizActivity {
         workspace: ws_id,
         task: task_id,
-        quiz: quiz_id,
+        quiz: quiz.id.clone(),
         duration,
         student: student_id,
+        language,
+        practice,
         questions: questions
             .into_iter()
             .map(|q| (q.id.clone(), q))
@@ -265,178 +598,403 @@ pub async fn get_quiz_activity(
 }
 
 pub async fn update_quiz_activity(activity: QuizActivity) -> Result<()> {
+    save_quiz_activity(activity, false).await
+}
+
+/// Grades an attempt entered by a supervisor, such as a paper answer sheet. Sheets are usually
+/// entered after the deadline, so it is not checked.
+pub async fn enter_quiz_activity(activity: QuizActivity) -> Result<()> {
+    save_quiz_activity(activity, true).await
+}
+
+async fn save_quiz_activity(activity: QuizActivity, supervised: bool) -> Result<()> {
+    let lock = QuizResultRepository::lock(&activity.workspace, &activity.task, &activity.student);
+    let _guard = lock.lock().await;
+
     let quiz_rec_arc = Store::find::<QuizRecord>(&activity.workspace, &activity.task).await?;
-    let (quiz_id, categories, student, student_idx) = {
+    let entry =
+        QuizResultRepository::entry(&activity.workspace, &activity.task, &activity.student).await?;
+    let (quiz, categories, student, practice) = {
         let quiz_rec_guard = quiz_rec_arc.read().await;
-        let student_idx = quiz_rec_guard
+        let mut student = quiz_rec_guard
             .students
-            .get_index_of(&activity.student)
-            .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
-        let student = quiz_rec_guard.students.index(student_idx).clone();
-        if quiz_rec_guard.attempts > 0 && student.attempts >= quiz_rec_guard.attempts {
+            .get(&activity.student)
+            .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?
+            .clone();
+        if let Some(entry) = entry {
+            student.attempts = entry.attempts;
+            student.grade = entry.grade;
+        }
+        if student.excused {
+            Err((StatusCode::FORBIDDEN, "student-excused"))?
+        }
+        if !quiz_rec_guard.practice
+            && quiz_rec_guard
+                .attempts_limit(&student)
+                .is_some_and(|limit| student.attempts >= limit)
+        {
             Err("attempts-exceeded")?
         }
-        let quiz_id = quiz_rec_guard.quiz.clone();
+        if !supervised && student.is_past_deadline(SUBMIT_GRACE) {
+            Err((StatusCode::FORBIDDEN, "deadline-passed"))?
+        }
+        if !activity.language.is_empty() && !quiz_rec_guard.languages.contains(&activity.language) {
+            Err((StatusCode::BAD_REQUEST, "language-not-found"))?
+        }
+        let quiz = QuizResultRepository::quiz(&quiz_rec_guard).await?;
         let categories = quiz_rec_guard.categories.clone();
-        (quiz_id, categories, student, student_idx)
+        (quiz, categories, student, quiz_rec_guard.practice)
     };
-    let quiz = Store::find::<Quiz>(&activity.workspace, &quiz_id)
-        .await?
-        .read()
-        .await
-        .clone();
 
-    let (total_grade, result, fail_important, answers) = categories.iter().fold(
-        (
-            0,
-            Vec::with_capacity(categories.len()),
-            false,
-            Vec::<HashMap<String, HashSet<String>>>::new(),
-        ),
-        |(mut total_grade, mut result, mut fail, mut answers), (category_id, category)| {
-            let activity_questions = activity
-                .questions
-                .iter()
-                .filter(|(_, q)| &q.category == category_id)
-                .collect::<Vec<_>>();
-            let (correct_questions, student_answers) = activity_questions.iter().fold(
-                (0, HashMap::<String, HashSet<String>>::new()),
-                |(mut correct, mut answers), (_question_id, question)| {
-                    let Some(quiz_question) = quiz
-                        .categories
-                        .get(&category.id)
-                        .and_then(|c| c.questions.get(&question.id))
-                    else {
-                        return (correct, answers);
-                    };
-                    if question.answered.is_empty() {
-                        return (correct, answers);
-                    }
-                    if quiz_question.answers.len() == 1 {
-                        if let (Some(student_answer), Some(answer)) = (
-                            question.answered.iter().next(),
-                            quiz_question.answers.values().next(),
-                        ) {
-                            let similarity = TextSimilarityService::compare_sync(
-                                answer.name.clone(),
-                                student_answer.clone(),
-                            );
-                            let passed = if quiz.grade.calc_similarity(similarity) { 1 } else { 0 }; 
-                            correct += passed;
-                            let mut set = HashSet::with_capacity(2);
-                            set.insert(student_answer.clone());
-                            set.insert(format!("{id}|{similarity}|{passed}", id = answer.id));
-                            answers.insert(quiz_question.id.clone(), set);
-                        };
-                    } else {
-                        let correct_answers = quiz_question
-                            .answers
-                            .values()
-                            .filter(|a| a.correct)
-                            .map(|a| a.id.clone())
-                            .collect::<HashSet<String>>();
-                        if question.answered == correct_answers {
-                            correct += 1;
-                        }
-                        answers.insert(quiz_question.id.clone(), question.answered.clone());
-                    }
-                    (correct, answers)
-                },
-            );
+    let open_questions = activity
+        .questions
+        .values()
+        .filter_map(|question| {
+            let quiz_question = quiz
+                .categories
+                .get(&question.category)
+                .and_then(|c| c.questions.get(&question.id))
+                .filter(|q| q.is_open())?;
+            let student_answer = question.answered.iter().next()?;
+            Some((quiz_question, student_answer))
+        })
+        .filter(|(q, _)| q.open_answer().strategy.is_semantic())
+        .collect::<Vec<_>>();
+    let keys = open_questions
+        .iter()
+        .flat_map(|(q, _)| q.answers.keys().map(|a| (q.id.clone(), a.clone())))
+        .collect::<Vec<_>>();
+    let pairs = open_questions
+        .iter()
+        .flat_map(|(q, student_answer)| {
+            q.answers.values().map(|a| {
+                (
+                    a.localized_name(&activity.language).to_string(),
+                    student_answer.to_string(),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    let similarities = TextSimilarityService::compare_batch_or_lexical(
+        format!("{}/{}", activity.workspace, quiz.id),
+        pairs,
+    )
+    .await
+    .map(|scores| {
+        keys.into_iter()
+            .zip(scores)
+            .collect::<HashMap<(String, String), usize>>()
+    });
 
-            let score = if !activity_questions.is_empty() {
-                ((correct_questions as f64) / (activity_questions.len() as f64) * 100.0) as usize
-            } else {
-                0
-            };
-            let grade = quiz.grade.calc(score);
-            let important = quiz
+    let mut answers = Vec::with_capacity(categories.len());
+    let mut served = Vec::with_capacity(categories.len());
+    let mut open = HashMap::<String, QuizOpenResult>::new();
+    for category_id in categories.keys() {
+        let activity_questions = activity
+            .questions
+            .values()
+            .filter(|q| &q.category == category_id)
+            .collect::<Vec<_>>();
+        let mut student_answers = HashMap::<String, HashSet<String>>::new();
+        for question in activity_questions.iter() {
+            let Some(quiz_question) = quiz
                 .categories
                 .get(category_id)
-                .and_then(|c| Some(c.important))
-                .unwrap_or(false);
-            if important && grade < 3 {
-                fail = true;
+                .and_then(|c| c.questions.get(&question.id))
+            else {
+                continue;
+            };
+            if question.answered.is_empty() {
+                continue;
             }
-            result.push(score);
-            total_grade += grade;
-            answers.push(student_answers);
+            if quiz_question.is_open() {
+                let Some(student_answer) = question.answered.iter().next() else {
+                    continue;
+                };
+                let (matched, similarity) = score_open_answer(
+                    quiz_question,
+                    student_answer,
+                    &activity.language,
+                    similarities.as_ref(),
+                );
+                let keywords = quiz_question.open_answer().keywords;
+                open.insert(
+                    quiz_question.id.clone(),
+                    QuizOpenResult {
+                        category: category_id.clone(),
+                        answer: student_answer.clone(),
+                        matched,
+                        similarity,
+                        passed: quiz.grade.calc_similarity(similarity)
+                            && contains_keywords(student_answer, &keywords),
+                        review: None,
+                    },
+                );
+                student_answers.insert(
+                    quiz_question.id.clone(),
+                    HashSet::from([student_answer.clone()]),
+                );
+            } else {
+                student_answers.insert(quiz_question.id.clone(), question.answered.clone());
+            }
+        }
+        answers.push(student_answers);
+        served.push(activity_questions.len());
+    }
 
-            (total_grade, result, fail, answers)
-        },
-    );
+    let (grade, result) = grade_quiz_student(&quiz, &categories, &answers, &open, &served);
 
-    let categories_count = if !categories.is_empty() {
-        categories.len()
-    } else {
-        1
-    };
-    let grade = if !fail_important {
-        ((total_grade as f64) / (categories_count as f64) + 0.5).floor() as usize
-    } else {
-        2
-    };
+    if practice {
+        // Practice runs land on the record itself, the student lock is taken again there.
+        drop(_guard);
+        return QuizResultRepository::update_student(
+            &activity.workspace,
+            &activity.task,
+            &student.id,
+            |quiz_rec| {
+                if let Some(student) = quiz_rec.students.get_mut(&student.id) {
+                    student.practice.push(QuizPracticeAttempt::new(result));
+                }
+                Ok(())
+            },
+        )
+        .await;
+    }
 
     if student.grade > grade {
         return Ok(());
     }
 
-    let (snapshot, progress) = {
-        let mut quiz_rec_guard = quiz_rec_arc.write().await;
-        if let Some(student) = quiz_rec_guard.students.get_mut(&student.id) {
-            student.attempts += 1;
-            student.grade = grade;
-        }
-        quiz_rec_guard.answers.set_row(student_idx, answers);
-        quiz_rec_guard.results.set_row(student_idx, result);
+    QuizResultRepository::append(
+        &activity.workspace,
+        &activity.task,
+        QuizRecordEntry {
+            student: student.id,
+            attempts: student.attempts + 1,
+            grade,
+            answers,
+            results: result,
+            served,
+            open,
+            language: Some(activity.language),
+        },
+    )
+    .await
+}
 
-        let count = quiz_rec_guard
+pub async fn check_quiz_answer(payload: CheckQuizAnswerPayload) -> Result<Response> {
+    let CheckQuizAnswerPayload {
+        workspace,
+        task,
+        student,
+        language,
+        question,
+    } = payload;
+
+    let quiz_rec_arc = Store::find::<QuizRecord>(&workspace, &task).await?;
+    let quiz = {
+        let quiz_rec_guard = quiz_rec_arc.read().await;
+        if !quiz_rec_guard.practice {
+            Err((StatusCode::FORBIDDEN, "practice-only"))?
+        }
+        let student = quiz_rec_guard
             .students
-            .values()
-            .filter(|s| s.grade > 0)
-            .count();
-        let progress = if quiz_rec_guard.students.is_empty() {
-            0
+            .get(&student)
+            .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
+        if student.excused {
+            Err((StatusCode::FORBIDDEN, "student-excused"))?
+        }
+        if student.is_past_deadline(SUBMIT_GRACE) {
+            Err((StatusCode::FORBIDDEN, "deadline-passed"))?
+        }
+        QuizResultRepository::quiz(&quiz_rec_guard).await?
+    };
+    let quiz_question = quiz
+        .categories
+        .get(&question.category)
+        .and_then(|c| c.questions.get(&question.id))
+        .ok_or((StatusCode::NOT_FOUND, "question-not-found"))?;
+
+    let feedback = if quiz_question.is_open() {
+        let student_answer = question.answered.iter().next().cloned().unwrap_or_default();
+        let open_answer = quiz_question.open_answer();
+        let similarities = if open_answer.strategy.is_semantic() && !student_answer.is_empty() {
+            let keys = quiz_question
+                .answers
+                .keys()
+                .map(|a| (quiz_question.id.clone(), a.clone()))
+                .collect::<Vec<_>>();
+            let pairs = quiz_question
+                .answers
+                .values()
+                .map(|a| {
+                    (
+                        a.localized_name(&language).to_string(),
+                        student_answer.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            TextSimilarityService::compare_batch_or_lexical(
+                format!("{workspace}/{}", quiz.id),
+                pairs,
+            )
+            .await
+            .map(|scores| {
+                keys.into_iter()
+                    .zip(scores)
+                    .collect::<HashMap<(String, String), usize>>()
+            })
         } else {
-            (count * 100) / quiz_rec_guard.students.len()
+            None
         };
+        let (matched, similarity) = score_open_answer(
+            quiz_question,
+            &student_answer,
+            &language,
+            similarities.as_ref(),
+        );
 
-        (quiz_rec_guard.clone(), progress)
+        QuizActivityFeedback {
+            question: quiz_question.id.clone(),
+            correct: !student_answer.is_empty()
+                && quiz.grade.calc_similarity(similarity)
+                && contains_keywords(&student_answer, &open_answer.keywords),
+            answers: HashSet::new(),
+            reference: quiz_question
+                .answers
+                .get(&matched)
+                .map(|a| a.localized_name(&language).to_string())
+                .unwrap_or_default(),
+            explanation: quiz_question.explanation.clone(),
+        }
+    } else {
+        let answers = quiz_question.correct_answers();
+        QuizActivityFeedback {
+            question: quiz_question.id.clone(),
+            correct: answers == question.answered,
+            answers,
+            reference: String::new(),
+            explanation: quiz_question.explanation.clone(),
+        }
     };
-    Store::upsert(snapshot).await?;
 
-    let tasks_arc = Store::find::<Tasks>(activity.workspace, TASKS).await?;
-    let snapshot = {
-        let mut tasks_guard = tasks_arc.write().await;
-        let task = tasks_guard
-            .get_mut(&activity.task)
-            .ok_or((StatusCode::NOT_FOUND, "task-not-found"))?;
-        task.progress = progress;
-        tasks_guard.clone()
+    Ok(Json(feedback).into_response())
+}
+
+pub fn grade_quiz_student(
+    quiz: &Quiz,
+    categories: &IndexMap<String, QuizRecordCategory>,
+    answers: &[HashMap<String, HashSet<String>>],
+    open: &HashMap<String, QuizOpenResult>,
+    served: &[usize],
+) -> (usize, Vec<usize>) {
+    let mut total_grade = 0;
+    let mut fail_important = false;
+    let mut results = Vec::with_capacity(categories.len());
+
+    for (idx, category) in categories.values().enumerate() {
+        let quiz_category = quiz.categories.get(&category.id);
+        let answered = answers.get(idx);
+        let correct = answered
+            .map(|answered| {
+                answered
+                    .iter()
+                    .filter(|(question_id, answered)| {
+                        if let Some(result) = open.get(*question_id) {
+                            return result.is_passed();
+                        }
+                        quiz_category
+                            .and_then(|c| c.questions.get(*question_id))
+                            .is_some_and(|q| !q.is_open() && q.correct_answers() == **answered)
+                    })
+                    .count()
+            })
+            .unwrap_or_default();
+        let count = served
+            .get(idx)
+            .copied()
+            .unwrap_or(category.count)
+            .max(answered.map_or(0, |a| a.len()));
+
+        let score = if count > 0 {
+            ((correct as f64) / (count as f64) * 100.0) as usize
+        } else {
+            0
+        };
+        let grade = quiz.grade.calc(score);
+        if quiz_category.is_some_and(|c| c.important) && grade < 3 {
+            fail_important = true;
+        }
+        results.push(score);
+        total_grade += grade;
+    }
+
+    let categories_count = categories.len().max(1);
+    let grade = if !fail_important {
+        ((total_grade as f64) / (categories_count as f64) + 0.5).floor() as usize
+    } else {
+        2
     };
-    Store::upsert(snapshot).await?;
+    (grade, results)
+}
 
-    Ok(())
+pub fn score_open_answer(
+    question: &QuizQuestion,
+    student_answer: &str,
+    language: &str,
+    similarities: Option<&HashMap<(String, String), usize>>,
+) -> (String, usize) {
+    let strategy = question.open_answer().strategy;
+    question
+        .answers
+        .values()
+        .map(|answer| {
+            let reference = answer.localized_name(language);
+            let semantic = similarities
+                .and_then(|s| s.get(&(question.id.clone(), answer.id.clone())))
+                .copied();
+            let similarity = match strategy {
+                QuizMatchStrategy::Exact => exact_similarity(reference, student_answer),
+                QuizMatchStrategy::Fuzzy => fuzzy_similarity(reference, student_answer),
+                QuizMatchStrategy::Semantic => {
+                    semantic.unwrap_or_else(|| fuzzy_similarity(reference, student_answer))
+                }
+                QuizMatchStrategy::Combined => semantic
+                    .unwrap_or_default()
+                    .max(fuzzy_similarity(reference, student_answer)),
+            };
+            (answer.id.clone(), similarity)
+        })
+        .max_by_key(|(_, similarity)| *similarity)
+        .unwrap_or_default()
 }
 
-async fn generate_category_questions(
+pub fn generate_category_questions(
     quiz: &Quiz,
     id: &str,
     count: usize,
+    rules: &[QuizAssemblyRule],
+    language: &str,
+    rng: &mut impl Rng,
 ) -> Vec<QuizActivityQuestion> {
-    let mut ids = quiz
-        .categories
-        .get(id)
-        .map(|c| c.questions.keys().collect::<Vec<_>>())
-        .unwrap_or_default();
-    ids.shuffle(&mut rand::rng());
+    let Some(category) = quiz.categories.get(id) else {
+        return vec![];
+    };
 
-    let count = count.min(ids.len());
-    let selected_questions = ids.iter().take(count);
+    let selected_questions = if rules.is_empty() {
+        let mut ids = category.questions.keys().collect::<Vec<_>>();
+        ids.shuffle(rng);
+        ids.truncate(count);
+        ids
+    } else {
+        let mut ids = category.assemble(rules, |candidates| candidates.shuffle(rng));
+        ids.truncate(count);
+        ids
+    };
 
     let mut questions = Vec::new();
-    for &q in selected_questions {
-        if let Some(question) = generate_question(quiz, id, q).await {
+    for q in selected_questions {
+        if let Some(question) = generate_question(quiz, id, q, language, rng) {
             questions.push(question)
         }
     }
@@ -444,23 +1002,31 @@ async fn generate_category_questions(
     questions
 }
 
-async fn generate_question(
+fn generate_question(
     quiz: &Quiz,
     category_id: &str,
     question_id: &str,
+    language: &str,
+    rng: &mut impl Rng,
 ) -> Option<QuizActivityQuestion> {
+    let is_open = quiz
+        .categories
+        .get(category_id)
+        .and_then(|c| c.questions.get(question_id))
+        .is_some_and(|q| q.is_open());
     let mut answers = quiz
         .categories
         .get(category_id)
         .and_then(|c| c.questions.get(question_id))
+        .filter(|_| !is_open)
         .and_then(|q| Some(q.answers.values().collect::<Vec<_>>()))
         .unwrap_or_default();
-    answers.shuffle(&mut rand::rng());
+    answers.shuffle(rng);
 
-    let kind = if answers.iter().filter(|a| a.correct).count() == 1 && answers.len() > 1 {
-        QuizActivityQuestionKind::Single
-    } else if answers.len() == 1 {
+    let kind = if is_open {
         QuizActivityQuestionKind::Open
+    } else if answers.iter().filter(|a| a.correct).count() == 1 && answers.len() > 1 {
+        QuizActivityQuestionKind::Single
     } else {
         QuizActivityQuestionKind::Multiple
     };
@@ -472,8 +1038,8 @@ async fn generate_question(
             (
                 a.id.clone(),
                 QuizActivityAnswer {
-                    id: a.id,
-                    name: a.name,
+                    id: a.id.clone(),
+                    name: a.localized_name(language).to_string(),
                     img: a.img,
                 },
             )
@@ -488,7 +1054,7 @@ async fn generate_question(
                 id: q.id.clone(),
                 category: category_id.to_string(),
                 kind,
-                name: q.name.clone(),
+                name: q.localized_name(language).to_string(),
                 img: q.img,
                 answers,
                 answered: Default::default(),
@@ -48,6 +48,7 @@ pub async fn update_quiz(
         attempts,
         duration,
         grade,
+        languages,
         categories,
     } = payload;
     let quiz_arc = Store::find::<Quiz>(&session.workspace, quiz_id).await?;
@@ -58,6 +59,7 @@ pub async fn update_quiz(
         quiz_guard.attempts = attempts;
         quiz_guard.duration = duration;
         quiz_guard.grade = grade;
+        quiz_guard.languages = languages;
         if !categories.is_empty() {
             quiz_guard.categories = categories.into_iter().map(|c| (c.id.clone(), c)).collect();
             quiz_guard
@@ -188,7 +190,16 @@ pub async fn update_quiz_question(
 ) -> Result<Json<QuizQuestion>> {
     session.checked_admin()?;
     let quiz_arc = Store::find::<Quiz>(&session.workspace, quiz_id).await?;
-    let UpdateQuizQuestionPayload { name, img, answers } = payload;
+    let UpdateQuizQuestionPayload {
+        name,
+        img,
+        answers,
+        translations,
+        tags,
+        difficulty,
+        open,
+        explanation,
+    } = payload;
     let question = QuizQuestion {
         id: question_id,
         name,
@@ -197,6 +208,11 @@ pub async fn update_quiz_question(
             .into_iter()
             .map(|a| (a.id.clone(), a))
             .collect::<IndexMap<String, QuizAnswer>>(),
+        translations,
+        tags,
+        difficulty,
+        open,
+        explanation: explanation.trim().to_string(),
     };
 
     let snapshot = {
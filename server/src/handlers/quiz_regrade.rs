@@ -0,0 +1,395 @@
+use crate::{handlers::*, middleware::*, repositories::*, services::*};
+use ::axum::{Json, extract::Path};
+use ::shared::{common::*, models::*, payloads::*, utils::*};
+use ::std::collections::{HashMap, HashSet};
+
+/// Semantic scores keyed by record language and student answer, each holding the
+/// `(question, reference answer)` scores `score_open_answer` looks up.
+type Similarities = HashMap<(String, String), HashMap<(String, String), usize>>;
+
+pub async fn regrade_quiz_record(
+    session: Session,
+    Path(task_id): Path<String>,
+    Json(payload): Json<RegradeQuizPayload>,
+) -> Result<Json<QuizRegrade>> {
+    session.checked_supervisor()?;
+    let RegradeQuizPayload {
+        refresh,
+        key,
+        dry_run,
+    } = payload;
+
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, &task_id).await?;
+    let (original, sources, languages, open_answers) = {
+        let quiz_rec_guard = quiz_rec_arc.read().await;
+        let mut languages = vec![String::new()];
+        languages.extend(quiz_rec_guard.languages.iter().cloned());
+        let open_answers = quiz_rec_guard
+            .students
+            .values()
+            .flat_map(|s| {
+                let attempt_languages = attempt_languages(s, &languages);
+                s.open.iter().flat_map(move |(question_id, result)| {
+                    attempt_languages.iter().map(move |language| {
+                        (question_id.clone(), result.answer.clone(), language.clone())
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        (
+            QuizResultRepository::quiz(&quiz_rec_guard).await?,
+            quiz_rec_guard
+                .sources()
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>(),
+            languages,
+            open_answers,
+        )
+    };
+    let mut quiz = original.clone();
+    if refresh {
+        for source in sources {
+            let current = Store::find::<Quiz>(&session.workspace, &source)
+                .await?
+                .read()
+                .await
+                .clone();
+            refresh_answer_key(&mut quiz, &current);
+        }
+    }
+    amend_answer_key(&mut quiz, &key)?;
+
+    let rescored = quiz.changed_open_questions(&original);
+    let similarities = open_similarities(&session.workspace, &quiz, &rescored, open_answers).await;
+    let rescore = |quiz_rec: &QuizRecord| {
+        regrade_changes(quiz_rec, &quiz, &rescored, &languages, &similarities)
+    };
+
+    if dry_run {
+        let (changes, _) = rescore(&*quiz_rec_arc.read().await);
+        return Ok(Json(QuizRegrade::new(&session.username, changes)));
+    }
+
+    let regrade = QuizResultRepository::update_all(&session.workspace, &task_id, |quiz_rec| {
+        let (changes, mut rescored_open) = rescore(quiz_rec);
+        for (student_id, student) in quiz_rec.students.iter_mut() {
+            if let Some(open) = rescored_open.remove(student_id) {
+                student.open = open;
+            }
+        }
+        let regrade = QuizRegrade::new(&session.username, changes);
+        for change in regrade.changes.iter() {
+            let Some(idx) = quiz_rec.students.get_index_of(&change.student) else {
+                continue;
+            };
+            quiz_rec.students[idx].grade = change.grade_after;
+            quiz_rec.results.set_row(idx, change.results_after.clone());
+        }
+        quiz_rec.snapshot = Some(quiz.clone());
+        quiz_rec.regrades.push(regrade.clone());
+        quiz_rec.metadata.update(&session.username);
+        Ok(regrade)
+    })
+    .await?;
+
+    Ok(Json(regrade))
+}
+
+/// Grade changes of every student who made an attempt, along with the re-scored open results
+/// of the students whose open answers were affected.
+fn regrade_changes(
+    quiz_rec: &QuizRecord,
+    quiz: &Quiz,
+    rescored: &HashSet<String>,
+    languages: &[String],
+    similarities: &Similarities,
+) -> (
+    Vec<QuizRegradeChange>,
+    HashMap<String, HashMap<String, QuizOpenResult>>,
+) {
+    let mut changes = Vec::new();
+    let mut rescored_open = HashMap::new();
+    for (idx, student) in quiz_rec.students.values().enumerate() {
+        if student.attempts == 0 {
+            continue;
+        }
+        let mut open = student.open.clone();
+        let mut open_changed = false;
+        for (question_id, result) in open.iter_mut() {
+            if !rescored.contains(question_id) {
+                continue;
+            }
+            let Some(question) = quiz
+                .categories
+                .get(&result.category)
+                .and_then(|c| c.questions.get(question_id))
+            else {
+                continue;
+            };
+            let (matched, similarity) = attempt_languages(student, languages)
+                .iter()
+                .map(|language| {
+                    let scores = similarities.get(&(language.clone(), result.answer.clone()));
+                    score_open_answer(question, &result.answer, language, scores)
+                })
+                .max_by_key(|(_, similarity)| *similarity)
+                .unwrap_or_default();
+            let passed = quiz.grade.calc_similarity(similarity)
+                && contains_keywords(&result.answer, &question.open_answer().keywords);
+            if (&matched, similarity, passed) != (&result.matched, result.similarity, result.passed)
+            {
+                result.matched = matched;
+                result.similarity = similarity;
+                result.passed = passed;
+                open_changed = true;
+            }
+        }
+
+        let answers = quiz_rec
+            .answers
+            .get_row(idx)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        let results_before = quiz_rec
+            .results
+            .get_row(idx)
+            .into_iter()
+            .copied()
+            .collect::<Vec<_>>();
+        let (grade_after, results_after) =
+            grade_quiz_student(quiz, &quiz_rec.categories, &answers, &open, &student.served);
+        if open_changed {
+            rescored_open.insert(student.id.clone(), open);
+        }
+        if grade_after == student.grade && results_after == results_before {
+            continue;
+        }
+        changes.push(QuizRegradeChange {
+            student: student.id.clone(),
+            student_name: student.name.clone(),
+            grade_before: student.grade,
+            grade_after,
+            results_before,
+            results_after,
+        });
+    }
+    (changes, rescored_open)
+}
+
+/// Languages an open answer is re-scored in: the one of the attempt, or every record language
+/// for attempts made before the language was kept.
+fn attempt_languages<'a>(student: &'a QuizRecordStudent, languages: &'a [String]) -> &'a [String] {
+    match &student.language {
+        Some(language) => std::slice::from_ref(language),
+        None => languages,
+    }
+}
+
+/// Scores the students' `(question, answer, language)` open answers to the re-scored semantic
+/// questions. Empty when the similarity service is off or busy.
+async fn open_similarities(
+    workspace: &str,
+    quiz: &Quiz,
+    rescored: &HashSet<String>,
+    open_answers: Vec<(String, String, String)>,
+) -> Similarities {
+    let questions = quiz
+        .categories
+        .values()
+        .flat_map(|c| c.questions.values())
+        .filter(|q| rescored.contains(&q.id) && q.open_answer().strategy.is_semantic())
+        .map(|q| (q.id.clone(), q))
+        .collect::<HashMap<_, _>>();
+
+    let mut keys = Vec::new();
+    let mut pairs = Vec::new();
+    for (question_id, answer, language) in open_answers.into_iter().collect::<HashSet<_>>() {
+        let Some(question) = questions.get(&question_id) else {
+            continue;
+        };
+        for reference in question.answers.values() {
+            keys.push((
+                (language.clone(), answer.clone()),
+                (question_id.clone(), reference.id.clone()),
+            ));
+            pairs.push((
+                reference.localized_name(&language).to_string(),
+                answer.clone(),
+            ));
+        }
+    }
+    if pairs.is_empty() {
+        return Similarities::new();
+    }
+
+    let Some(scores) =
+        TextSimilarityService::compare_batch_or_lexical(format!("{workspace}/{}", quiz.id), pairs)
+            .await
+    else {
+        return Similarities::new();
+    };
+    let mut similarities = Similarities::new();
+    for ((scope, key), score) in keys.into_iter().zip(scores) {
+        similarities.entry(scope).or_default().insert(key, score);
+    }
+    similarities
+}
+
+fn refresh_answer_key(quiz: &mut Quiz, current: &Quiz) {
+    for category in quiz.categories.values_mut() {
+        let Some(current_category) = current.categories.get(&category.id) else {
+            continue;
+        };
+        for question in category.questions.values_mut() {
+            let Some(current_question) = current_category.questions.get(&question.id) else {
+                continue;
+            };
+            question.answers = current_question.answers.clone();
+            question.open = current_question.open.clone();
+        }
+    }
+}
+
+fn amend_answer_key(quiz: &mut Quiz, key: &HashMap<String, HashSet<String>>) -> Result<()> {
+    for (question_id, correct) in key.iter() {
+        let question = quiz
+            .categories
+            .values_mut()
+            .find_map(|c| c.questions.get_mut(question_id))
+            .ok_or((StatusCode::NOT_FOUND, "question-not-found"))?;
+        if question.is_open() || correct.is_empty() {
+            Err((StatusCode::BAD_REQUEST, "bad-request"))?
+        }
+        for answer in question.answers.values_mut() {
+            answer.correct = correct.contains(&answer.id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::indexmap::IndexMap;
+
+    fn quiz(translation: &str) -> Quiz {
+        let answer = QuizAnswer {
+            id: "a1".to_string(),
+            name: "Київ".to_string(),
+            correct: true,
+            translations: HashMap::from([("en".to_string(), translation.to_string())]),
+            ..Default::default()
+        };
+        let question = QuizQuestion {
+            id: "q1".to_string(),
+            answers: IndexMap::from([("a1".to_string(), answer)]),
+            open: Some(QuizOpenAnswer {
+                strategy: QuizMatchStrategy::Exact,
+                keywords: vec![],
+            }),
+            ..Default::default()
+        };
+        let category = QuizCategory {
+            id: "c1".to_string(),
+            questions: IndexMap::from([("q1".to_string(), question)]),
+            ..Default::default()
+        };
+        Quiz {
+            categories: IndexMap::from([("c1".to_string(), category)]),
+            ..Default::default()
+        }
+    }
+
+    fn student(id: &str, attempts: usize, language: Option<&str>) -> QuizRecordStudent {
+        let open = QuizOpenResult {
+            category: "c1".to_string(),
+            answer: "Kyiv".to_string(),
+            matched: "a1".to_string(),
+            ..Default::default()
+        };
+        QuizRecordStudent {
+            id: id.to_string(),
+            name: id.to_string(),
+            attempts,
+            grade: 2,
+            served: vec![1],
+            open: HashMap::from([("q1".to_string(), open)]),
+            language: language.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn record(students: Vec<QuizRecordStudent>) -> QuizRecord {
+        let category = QuizRecordCategory {
+            id: "c1".to_string(),
+            count: 1,
+            ..Default::default()
+        };
+        let cell = HashMap::from([("q1".to_string(), HashSet::from(["Kyiv".to_string()]))]);
+        QuizRecord {
+            categories: IndexMap::from([("c1".to_string(), category)]),
+            answers: Grid::new(students.len(), 1, cell),
+            results: Grid::new(students.len(), 1, 0),
+            students: students.into_iter().map(|s| (s.id.clone(), s)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn regrade_rescores_in_attempt_language() {
+        let quiz_rec = record(vec![
+            student("uk", 1, Some("")),
+            student("en", 1, Some("en")),
+            student("legacy", 1, None),
+            student("absent", 0, Some("en")),
+        ]);
+        let languages = vec![String::new(), "en".to_string()];
+        let rescored = HashSet::from(["q1".to_string()]);
+        let (changes, open) = regrade_changes(
+            &quiz_rec,
+            &quiz("Kyiv"),
+            &rescored,
+            &languages,
+            &Similarities::new(),
+        );
+
+        let changed = changes
+            .iter()
+            .map(|c| c.student.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(changed, ["en", "legacy"]);
+        for change in changes.iter() {
+            assert_eq!((change.grade_before, change.grade_after), (2, 5));
+            assert_eq!(change.results_after, [100]);
+        }
+        assert_eq!(open.len(), 2);
+        assert!(open["en"]["q1"].passed);
+        assert_eq!(open["legacy"]["q1"].similarity, 100);
+    }
+
+    #[test]
+    fn regrade_skips_unchanged_questions() {
+        let quiz_rec = record(vec![student("en", 1, Some("en"))]);
+        let (changes, open) = regrade_changes(
+            &quiz_rec,
+            &quiz("Kyiv"),
+            &HashSet::new(),
+            &["en".to_string()],
+            &Similarities::new(),
+        );
+        assert!(changes.is_empty());
+        assert!(open.is_empty());
+
+        let (changes, open) = regrade_changes(
+            &quiz_rec,
+            &quiz("Kiev"),
+            &HashSet::from(["q1".to_string()]),
+            &["en".to_string()],
+            &Similarities::new(),
+        );
+        assert!(changes.is_empty());
+        assert!(open.is_empty());
+    }
+}
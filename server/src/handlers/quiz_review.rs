@@ -0,0 +1,110 @@
+use crate::{handlers::*, middleware::*, repositories::*};
+use ::axum::{
+    Json,
+    extract::{Path, Query},
+};
+use ::shared::{common::*, models::*, payloads::*};
+
+const REVIEW_MARGIN: usize = 15;
+
+pub async fn list_quiz_reviews(
+    session: Session,
+    Path(task_id): Path<String>,
+    Query(query): Query<QuizReviewQuery>,
+) -> Result<Json<Vec<QuizReviewItem>>> {
+    session.checked_supervisor()?;
+    let quiz_rec = QuizResultRepository::find(&session.workspace, &task_id)
+        .await?
+        .read()
+        .await
+        .clone();
+    let quiz = QuizResultRepository::quiz(&quiz_rec).await?;
+    let threshold = quiz.grade.similarity;
+
+    let mut items = Vec::new();
+    for student in quiz_rec.students.values() {
+        for (question_id, result) in student.open.iter() {
+            let near_threshold =
+                result.review.is_none() && result.similarity.abs_diff(threshold) <= REVIEW_MARGIN;
+            if !query.all && !near_threshold {
+                continue;
+            }
+            let question = quiz
+                .categories
+                .get(&result.category)
+                .and_then(|c| c.questions.get(question_id));
+            items.push(QuizReviewItem {
+                student: student.id.clone(),
+                student_name: student.name.clone(),
+                question: question_id.clone(),
+                question_name: question.map(|q| q.name.clone()).unwrap_or_default(),
+                accepted_answers: question
+                    .map(|q| q.answers.values().map(|a| a.name.clone()).collect())
+                    .unwrap_or_default(),
+                result: result.clone(),
+            });
+        }
+    }
+    items.sort_by(|a, b| {
+        a.student_name
+            .cmp(&b.student_name)
+            .then(a.question_name.cmp(&b.question_name))
+    });
+
+    Ok(Json(items))
+}
+
+pub async fn review_quiz_answer(
+    session: Session,
+    Path(task_id): Path<String>,
+    Json(payload): Json<ReviewQuizAnswerPayload>,
+) -> Result<Json<QuizRecordStudent>> {
+    session.checked_supervisor()?;
+    let ReviewQuizAnswerPayload {
+        student,
+        question,
+        accepted,
+    } = payload;
+
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, &task_id).await?;
+    let quiz = QuizResultRepository::quiz(&*quiz_rec_arc.read().await).await?;
+
+    // `update_student` takes the student lock itself and folds in any pending entry.
+    let username = session.username.clone();
+    let student = QuizResultRepository::update_student(
+        &session.workspace,
+        &task_id,
+        &student,
+        |quiz_rec| {
+            let student_idx = quiz_rec
+                .students
+                .get_index_of(&student)
+                .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
+            let answers = quiz_rec
+                .answers
+                .get_row(student_idx)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            let categories = quiz_rec.categories.clone();
+
+            let student = &mut quiz_rec.students[student_idx];
+            let result = student
+                .open
+                .get_mut(&question)
+                .ok_or((StatusCode::NOT_FOUND, "answer-not-found"))?;
+            result.review = Some(QuizOpenReview::new(accepted, &username));
+            let (grade, results) =
+                grade_quiz_student(&quiz, &categories, &answers, &student.open, &student.served);
+            student.grade = grade;
+            let student = student.clone();
+
+            quiz_rec.results.set_row(student_idx, results);
+            quiz_rec.metadata.update(&username);
+            Ok(student)
+        },
+    )
+    .await?;
+
+    Ok(Json(student))
+}
@@ -0,0 +1,168 @@
+use crate::{middleware::*, repositories::*, services::*};
+use ::axum::{Json, extract::Path};
+use ::shared::{common::*, models::*, payloads::*};
+use ::std::collections::HashSet;
+
+pub async fn add_quiz_students(
+    session: Session,
+    Path(task_id): Path<String>,
+    Json(payload): Json<AddQuizStudentsPayload>,
+) -> Result<Json<Vec<QuizRecordStudent>>> {
+    session.checked_supervisor()?;
+    let ids = payload.students.into_iter().collect::<HashSet<_>>();
+    TaskRepository::get(&session.workspace, &task_id).await?;
+
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, &task_id).await?;
+    let record_node = quiz_rec_arc.read().await.node.clone();
+    let nodes = Store::find::<Workspace>(&session.workspace, &session.workspace)
+        .await?
+        .read()
+        .await
+        .unit_tree
+        .node_descendants(&record_node);
+    let allowed = session
+        .nodes()
+        .await?
+        .map(|nodes| nodes.into_iter().collect::<HashSet<_>>());
+
+    let mut students_vec = StudentRepository::list_by_filter(&session.workspace, Some(nodes))
+        .await?
+        .into_iter()
+        .filter(|s| ids.contains(&s.id))
+        .filter(|s| allowed.as_ref().is_none_or(|nodes| nodes.contains(&s.node)))
+        .collect::<Vec<_>>();
+    if students_vec.is_empty() {
+        Err((StatusCode::BAD_REQUEST, "no-students-found"))?
+    }
+    students_vec.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+    let added = QuizResultRepository::update_all(&session.workspace, &task_id, |quiz_rec| {
+        let mut added = Vec::with_capacity(students_vec.len());
+        for s in students_vec {
+            let student = QuizRecordStudent {
+                id: s.id,
+                rank: s.rank,
+                name: s.name,
+                node: s.node,
+                ..Default::default()
+            };
+            if quiz_rec.add_student(student.clone()) {
+                added.push(student);
+            }
+        }
+        quiz_rec.metadata.update(&session.username);
+        Ok(added)
+    })
+    .await?;
+
+    Ok(Json(added))
+}
+
+pub async fn remove_quiz_student(
+    session: Session,
+    Path((task_id, student_id)): Path<(String, String)>,
+) -> Result<Json<String>> {
+    session.checked_supervisor()?;
+    TaskRepository::get(&session.workspace, &task_id).await?;
+
+    QuizResultRepository::update_student(&session.workspace, &task_id, &student_id, |quiz_rec| {
+        quiz_rec.remove_student(&student_id);
+        quiz_rec.metadata.update(&session.username);
+        Ok(())
+    })
+    .await?;
+
+    Ok(Json(student_id))
+}
+
+pub async fn update_quiz_student(
+    session: Session,
+    Path((task_id, student_id)): Path<(String, String)>,
+    Json(payload): Json<UpdateQuizStudentPayload>,
+) -> Result<Json<QuizRecordStudent>> {
+    session.checked_supervisor()?;
+    TaskRepository::get(&session.workspace, &task_id).await?;
+    let UpdateQuizStudentPayload {
+        reset,
+        extra_attempts,
+        extra_time,
+        excused,
+    } = payload;
+    if extra_time.is_some_and(|t| t < 0) {
+        Err((StatusCode::BAD_REQUEST, "bad-request"))?
+    }
+
+    let student = QuizResultRepository::update_student(
+        &session.workspace,
+        &task_id,
+        &student_id,
+        |quiz_rec| {
+            if reset {
+                quiz_rec.reset_student(&student_id);
+            }
+            let student = quiz_rec
+                .students
+                .get_mut(&student_id)
+                .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
+            if let Some(extra_attempts) = extra_attempts {
+                student.extra_attempts = extra_attempts;
+            }
+            if let Some(extra_time) = extra_time {
+                student.extra_time = extra_time;
+            }
+            if let Some(excused) = excused {
+                student.excused = excused;
+            }
+            let student = student.clone();
+            quiz_rec.metadata.update(&session.username);
+            Ok(student)
+        },
+    )
+    .await?;
+
+    Ok(Json(student))
+}
+
+pub async fn override_quiz_grade(
+    session: Session,
+    Path((task_id, student_id)): Path<(String, String)>,
+    Json(payload): Json<OverrideQuizGradePayload>,
+) -> Result<Json<QuizRecordStudent>> {
+    session.checked_supervisor()?;
+    let OverrideQuizGradePayload {
+        grade,
+        results,
+        reason,
+    } = payload;
+    let clear = grade.is_none() && results.is_empty();
+    if grade.is_some_and(|g| !(2..=5).contains(&g)) || results.values().any(|&r| r > 100) {
+        Err((StatusCode::BAD_REQUEST, "bad-request"))?
+    }
+    if !clear && reason.trim().is_empty() {
+        Err((StatusCode::BAD_REQUEST, "override-reason-required"))?
+    }
+
+    let student = QuizResultRepository::update_student(
+        &session.workspace,
+        &task_id,
+        &student_id,
+        |quiz_rec| {
+            if results.keys().any(|c| !quiz_rec.categories.contains_key(c)) {
+                Err((StatusCode::BAD_REQUEST, "bad-request"))?
+            }
+            let student = quiz_rec
+                .students
+                .get_mut(&student_id)
+                .ok_or((StatusCode::NOT_FOUND, "student-not-found"))?;
+            student.grade_override = (!clear).then(|| {
+                QuizGradeOverride::new(grade, results, reason.trim(), &session.username)
+            });
+            let student = student.clone();
+            quiz_rec.metadata.update(&session.username);
+            Ok(student)
+        },
+    )
+    .await?;
+
+    Ok(Json(student))
+}
@@ -0,0 +1,164 @@
+use crate::{handlers::*, middleware::*, repositories::*, services::*};
+use ::axum::{
+    Json,
+    extract::{Multipart, Path},
+};
+use ::indexmap::IndexMap;
+use ::rand::{SeedableRng, prelude::SliceRandom, rngs::StdRng};
+use ::shared::{common::*, models::*, payloads::*};
+use ::std::collections::HashSet;
+
+pub async fn create_quiz_variants(
+    session: Session,
+    Path(task_id): Path<String>,
+    Json(payload): Json<CreateQuizVariantsPayload>,
+) -> Result<Json<Vec<QuizVariant>>> {
+    session.checked_supervisor()?;
+    let CreateQuizVariantsPayload { count, seed } = payload;
+    if count == 0 {
+        Err((StatusCode::BAD_REQUEST, "bad-request"))?
+    }
+    let seed = seed.unwrap_or_else(rand::random::<u64>);
+
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, &task_id).await?;
+    let (quiz, categories) = {
+        let quiz_rec_guard = quiz_rec_arc.read().await;
+        (
+            QuizResultRepository::quiz(&quiz_rec_guard).await?,
+            quiz_rec_guard.categories.clone(),
+        )
+    };
+
+    let mut variants = Vec::with_capacity(count);
+    for id in 1..=count {
+        let variant_seed = seed.wrapping_add(id as u64);
+        let mut rng = StdRng::seed_from_u64(variant_seed);
+        let mut questions = Vec::new();
+        for category in categories.values() {
+            questions.extend(generate_category_questions(
+                &quiz,
+                &category.id,
+                category.count,
+                &category.rules,
+                "",
+                &mut rng,
+            ));
+        }
+        questions.shuffle(&mut rng);
+
+        if questions.len() > AnswerSheetLayout::capacity() {
+            Err((StatusCode::BAD_REQUEST, "answer-sheet-overflow"))?
+        }
+        if questions
+            .iter()
+            .any(|q| q.answers.len() > AnswerSheetLayout::MAX_ANSWERS)
+        {
+            Err((StatusCode::BAD_REQUEST, "answer-sheet-too-many-answers"))?
+        }
+
+        variants.push(QuizVariant {
+            id,
+            seed: variant_seed,
+            questions,
+        });
+    }
+
+    QuizResultRepository::update_all(&session.workspace, &task_id, |quiz_rec| {
+        quiz_rec.variants = variants.clone();
+        quiz_rec.metadata.update(&session.username);
+        Ok(())
+    })
+    .await?;
+
+    Ok(Json(variants))
+}
+
+pub async fn submit_quiz_variant(
+    session: Session,
+    Path(task_id): Path<String>,
+    Json(payload): Json<QuizVariantSheet>,
+) -> Result<()> {
+    session.checked_supervisor()?;
+    let QuizVariantSheet {
+        variant,
+        student,
+        mut answers,
+        ..
+    } = payload;
+
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, &task_id).await?;
+    let activity = {
+        let quiz_rec_guard = quiz_rec_arc.read().await;
+        if !quiz_rec_guard.students.contains_key(&student) {
+            Err((StatusCode::NOT_FOUND, "student-not-found"))?
+        }
+        let variant = quiz_rec_guard
+            .variants
+            .iter()
+            .find(|v| v.id == variant)
+            .ok_or((StatusCode::NOT_FOUND, "variant-not-found"))?;
+
+        QuizActivity {
+            workspace: quiz_rec_guard.workspace.clone(),
+            task: quiz_rec_guard.id.clone(),
+            quiz: quiz_rec_guard.assets().to_string(),
+            duration: quiz_rec_guard.duration,
+            student,
+            language: String::new(),
+            practice: quiz_rec_guard.practice,
+            questions: variant
+                .questions
+                .iter()
+                .cloned()
+                .map(|mut q| {
+                    q.answered = answers.remove(&q.id).unwrap_or_default();
+                    (q.id.clone(), q)
+                })
+                .collect::<IndexMap<String, QuizActivityQuestion>>(),
+        }
+    };
+
+    enter_quiz_activity(activity).await
+}
+
+pub async fn scan_quiz_variant(
+    session: Session,
+    Path(task_id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Json<QuizVariantSheet>> {
+    session.checked_supervisor()?;
+    let data = UploadService::read_bytes(&mut multipart).await?;
+
+    let scanned = AnswerSheetService::scan(data).await?;
+    let mut sheet = QuizVariantSheet::from_code(&scanned.code)
+        .ok_or((StatusCode::BAD_REQUEST, "answer-sheet-not-recognized"))?;
+    if sheet.task != task_id {
+        Err((StatusCode::BAD_REQUEST, "answer-sheet-wrong-task"))?
+    }
+
+    let quiz_rec_arc = QuizResultRepository::find(&session.workspace, &task_id).await?;
+    let quiz_rec_guard = quiz_rec_arc.read().await;
+    let variant = quiz_rec_guard
+        .variants
+        .iter()
+        .find(|v| v.id == sheet.variant)
+        .ok_or((StatusCode::NOT_FOUND, "variant-not-found"))?;
+
+    for (idx, question) in variant.questions.iter().enumerate() {
+        if question.kind == QuizActivityQuestionKind::Open {
+            continue;
+        }
+        let marked = question
+            .answers
+            .keys()
+            .enumerate()
+            .filter(|(answer_idx, _)| scanned.is_marked(idx, *answer_idx))
+            .map(|(_, id)| id.clone())
+            .collect::<HashSet<_>>();
+        if !marked.is_empty() {
+            sheet.answers.insert(question.id.clone(), marked);
+        }
+    }
+
+    Ok(Json(sheet))
+}
@@ -0,0 +1,23 @@
+use crate::{middleware::*, services::*};
+use ::axum::Json;
+use ::shared::{common::*, models::*, payloads::*};
+
+pub async fn migrate_storage(
+    connection: Connection,
+    session: Session,
+    Json(payload): Json<MigrateStoragePayload>,
+) -> Result<Json<MigrateStorageSummary>> {
+    connection.checked()?;
+    connection.checked_local()?;
+    session.checked_admin()?;
+    // The in-memory backend is for tests, migrating into it loses all data on restart.
+    if payload.backend == StorageKind::Memory {
+        Err((StatusCode::BAD_REQUEST, "storage-not-persistent"))?
+    }
+
+    let (workspaces, records) = Store::migrate(payload.backend).await?;
+    Ok(Json(MigrateStorageSummary {
+        workspaces,
+        records,
+    }))
+}
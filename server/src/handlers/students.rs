@@ -1,6 +1,7 @@
 use crate::{middleware::*, repositories::*, services::*};
 use ::axum::{Json, extract::Path};
 use ::shared::{common::*, models::*, payloads::*, utils::*};
+use ::std::collections::{HashMap, HashSet};
 
 pub async fn list_students(session: Session) -> Result<Json<Vec<Student>>> {
     let node_id = session.node.clone();
@@ -54,6 +55,144 @@ pub async fn add_students(
     Ok(Json(students))
 }
 
+pub async fn import_students(
+    session: Session,
+    Json(payload): Json<ImportStudentsPayload>,
+) -> Result<Json<ImportStudentsSummary>> {
+    session.checked_supervisor()?;
+    let ImportStudentsPayload {
+        node,
+        dry_run,
+        students,
+    } = payload;
+
+    let allowed = session
+        .nodes()
+        .await?
+        .map(|nodes| nodes.into_iter().collect::<HashSet<_>>());
+    let in_scope = |node: &str| allowed.as_ref().is_none_or(|nodes| nodes.contains(node));
+    if !in_scope(&node) {
+        Err((StatusCode::FORBIDDEN, "forbidden"))?
+    }
+
+    let ws_arc = Store::find::<Workspace>(&session.workspace, &session.workspace).await?;
+    let (paths, node_names) = {
+        let ws_guard = ws_arc.read().await;
+        if !ws_guard.unit_tree.contains_key(&node) {
+            Err((StatusCode::NOT_FOUND, "not-found"))?
+        }
+        let mut paths = HashMap::<String, String>::new();
+        let mut node_names = HashMap::<String, Option<String>>::new();
+        for tree_node in ws_guard.unit_tree.values() {
+            paths.insert(
+                normalize_name(ws_guard.unit_tree.node_path(&tree_node.id)),
+                tree_node.id.clone(),
+            );
+            node_names
+                .entry(normalize_name(&tree_node.name))
+                .and_modify(|id| *id = None)
+                .or_insert_with(|| Some(tree_node.id.clone()));
+        }
+        (paths, node_names)
+    };
+
+    let students_arc = Store::find::<Students>(&session.workspace, STUDENTS).await?;
+    let mut students_guard = students_arc.write().await;
+    let mut snapshot = students_guard.clone();
+
+    let mut by_rank_and_name = HashMap::<(String, String), String>::new();
+    let mut by_name = HashMap::<String, Option<String>>::new();
+    for student in snapshot.values() {
+        let name = normalize_name(&student.name);
+        let rank = normalize_name(student.rank.as_deref().unwrap_or_default());
+        by_rank_and_name.insert((rank, name.clone()), student.id.clone());
+        by_name
+            .entry(name)
+            .and_modify(|id| *id = None)
+            .or_insert_with(|| Some(student.id.clone()));
+    }
+
+    let mut summary = ImportStudentsSummary::default();
+    let mut seen = HashSet::<(String, String)>::new();
+    for row in students {
+        let name = row.name.split_whitespace().collect::<Vec<_>>().join(" ");
+        if name.is_empty() {
+            summary.skipped += 1;
+            continue;
+        }
+        let rank = row
+            .rank
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+
+        let target = if row.path.trim().is_empty() {
+            Some(node.clone())
+        } else {
+            let path = normalize_name(&row.path);
+            paths
+                .get(&path)
+                .cloned()
+                .or_else(|| node_names.get(&path).cloned().flatten())
+        };
+        let Some(target) = target.filter(|target| in_scope(target)) else {
+            summary.unresolved += 1;
+            continue;
+        };
+
+        let key = (
+            normalize_name(rank.as_deref().unwrap_or_default()),
+            normalize_name(&name),
+        );
+        if !seen.insert(key.clone()) {
+            summary.skipped += 1;
+            continue;
+        }
+
+        let existing = by_rank_and_name
+            .get(&key)
+            .cloned()
+            .or_else(|| by_name.get(&key.1).cloned().flatten());
+        if let Some(student) = existing.and_then(|id| snapshot.get_mut(&id)) {
+            if !in_scope(&student.node) {
+                summary.skipped += 1;
+                continue;
+            }
+            let rank_changed = rank.is_some() && rank != student.rank;
+            if !rank_changed && student.node == target {
+                summary.skipped += 1;
+                continue;
+            }
+            if rank_changed {
+                student.rank = rank;
+            }
+            student.node = target;
+            summary.updated += 1;
+            continue;
+        }
+
+        let student = Student {
+            id: safe_nanoid!(),
+            node: target,
+            rank,
+            name,
+        };
+        snapshot.insert(student.id.clone(), student);
+        summary.created += 1;
+    }
+
+    if dry_run || summary.created + summary.updated == 0 {
+        return Ok(Json(summary));
+    }
+
+    snapshot.sort_unstable_by(|_, a, _, b| a.name.cmp(&b.name));
+    *students_guard = snapshot.clone();
+    drop(students_guard);
+
+    Store::upsert(snapshot).await?;
+
+    Ok(Json(summary))
+}
+
 pub async fn remove_students(session: Session, Json(payload): Json<Vec<String>>) -> Result<()> {
     let payload = (!payload.is_empty()).then_some(payload);
     StudentRepository::batch_remove(&session.workspace, payload, None).await
@@ -4,6 +4,7 @@ use ::axum::{
     response::{IntoResponse, Response},
 };
 use ::indexmap::IndexMap;
+use ::rand::prelude::SliceRandom;
 use ::shared::{common::*, models::*, payloads::*};
 
 pub async fn get_survey_record(session: &Session, id: impl Into<String>) -> Result<Response> {
@@ -30,6 +31,7 @@ pub async fn create_survey_record(session: &Session, payload: CreateTaskPayload)
         name,
         path,
         categories,
+        ..
     } = payload;
 
     let survey_arc = Store::find::<Survey>(&session.workspace, id).await?;
@@ -45,12 +47,21 @@ pub async fn create_survey_record(session: &Session, payload: CreateTaskPayload)
                 continue;
             };
 
-            if c.questions.is_empty() && c.answers.is_empty() {
-                continue;
-            }
-
             let questions_count = c.questions.len().max(1);
-            let answers_count = c.answers.len().max(1);
+            let answers_count = match c.kind {
+                SurveyCategoryKind::Choice if c.questions.is_empty() && c.answers.is_empty() => {
+                    continue;
+                }
+                SurveyCategoryKind::Choice => c.answers.len().max(1),
+                SurveyCategoryKind::Text | SurveyCategoryKind::Numeric => 1,
+                SurveyCategoryKind::Scale if c.questions.is_empty() => continue,
+                SurveyCategoryKind::Scale => {
+                    let (min, max) = SurveyCategoryKind::scale_range(c.min, c.max);
+                    (max - min + 1) as usize
+                }
+                SurveyCategoryKind::Ranking if c.questions.len() < 2 => continue,
+                SurveyCategoryKind::Ranking => c.questions.len(),
+            };
 
             let category_id = c.id.clone();
             task_categories.insert(
@@ -61,6 +72,22 @@ pub async fn create_survey_record(session: &Session, payload: CreateTaskPayload)
                     questions: c.questions.clone(),
                     answers: c.answers.clone(),
                     results: Grid::<usize>::new(questions_count, answers_count, 0),
+                    kind: c.kind,
+                    min: c.min,
+                    max: c.max,
+                    responses: Default::default(),
+                    values: Default::default(),
+                    // Conditions on categories left out of the task can never be met.
+                    conditions: c
+                        .conditions
+                        .iter()
+                        .filter(|condition| task_categories.contains_key(&condition.category))
+                        .cloned()
+                        .collect(),
+                    required: c.required,
+                    max_selections: c.max_selections,
+                    total: 0,
+                    segment: c.segment,
                 },
             );
         }
@@ -74,6 +101,10 @@ pub async fn create_survey_record(session: &Session, payload: CreateTaskPayload)
             path,
             total: 0,
             categories: task_categories,
+            segments: vec![],
+            min_segment_size: survey_guard
+                .min_segment_size
+                .unwrap_or(DEFAULT_MIN_SEGMENT_SIZE),
             metadata: Metadata::new(&session.username),
         }
     };
@@ -86,6 +117,9 @@ pub async fn create_survey_record(session: &Session, payload: CreateTaskPayload)
         node: record.node.clone(),
         path: record.path.clone(),
         progress: 0,
+        opens_at: None,
+        closes_at: None,
+        practice: false,
         metadata: record.metadata.clone(),
     };
 
@@ -111,6 +145,9 @@ pub async fn get_survey_categories(
                 count: 0,
                 total: 0,
                 checked: true,
+                tags: vec![],
+                rules: vec![],
+                ..Default::default()
             })
             .collect::<Vec<_>>()
     };
@@ -149,8 +186,11 @@ pub async fn get_survey_activity(
     let survey_rec_arc = Store::find::<SurveyRecord>(&ws_id, &task_id).await?;
     let mut snapshot = { survey_rec_arc.read().await.clone() };
     for category in snapshot.categories.values_mut() {
-        category.results.fill(0)
+        category.results.fill(0);
+        category.responses.clear();
+        category.values.clear();
     }
+    snapshot.segments.clear();
 
     Ok(Json(snapshot).into_response())
 }
@@ -160,9 +200,87 @@ pub async fn update_survey_activity(activity: SurveyRecord) -> Result<()> {
 
     let snapshot = {
         let mut survey_rec_guard = survey_rec_arc.write().await;
-        for survey_cat in survey_rec_guard.categories.values_mut() {
-            if let Some(category) = activity.categories.get(&survey_cat.id) {
-                survey_cat.results.concat(&category.results)
+        let visible = survey_rec_guard.visible_categories(&activity);
+
+        // Everything is validated before the record is touched, so a rejected
+        // submission leaves no partial votes behind.
+        let mut submissions = Vec::with_capacity(visible.len());
+        for survey_cat in survey_rec_guard.categories.values() {
+            if !visible.contains(&survey_cat.id) {
+                continue;
+            }
+            let submission = activity
+                .categories
+                .get(&survey_cat.id)
+                .map(|category| normalize_survey_category(survey_cat, category))
+                .transpose()?;
+            if survey_cat.required && !submission.as_ref().is_some_and(|c| c.is_complete()) {
+                Err((StatusCode::BAD_REQUEST, "survey-required-missing"))?
+            }
+            if let Some(submission) = submission.filter(|c| c.is_answered()) {
+                submissions.push(submission);
+            }
+        }
+
+        // Records created before per-category counts saw every category.
+        let total = survey_rec_guard.total;
+        if survey_rec_guard.categories.values().all(|c| c.total == 0) {
+            survey_rec_guard.categories.values_mut().for_each(|c| c.total = total);
+        }
+
+        let segments = submissions
+            .iter()
+            .filter(|c| {
+                survey_rec_guard
+                    .categories
+                    .get(&c.id)
+                    .is_some_and(|stored| stored.segment)
+            })
+            .filter_map(|c| Some((c.id.clone(), c.selected_option()?.clone())))
+            .collect::<Vec<_>>();
+        for (category, option) in segments {
+            let idx = match survey_rec_guard
+                .segments
+                .iter()
+                .position(|s| s.category == category && s.option == option)
+            {
+                Some(idx) => idx,
+                None => {
+                    survey_rec_guard.segments.push(SurveySegment {
+                        category,
+                        option,
+                        ..Default::default()
+                    });
+                    survey_rec_guard.segments.len() - 1
+                }
+            };
+            let segment = &mut survey_rec_guard.segments[idx];
+            segment.total += 1;
+            // Only counts are broken down, free text and numbers stay aggregated.
+            for submission in submissions.iter().filter(|c| {
+                !matches!(c.kind, SurveyCategoryKind::Text | SurveyCategoryKind::Numeric)
+            }) {
+                let counts = segment
+                    .categories
+                    .entry(submission.id.clone())
+                    .or_insert_with(|| SurveySegmentCategory {
+                        id: submission.id.clone(),
+                        total: 0,
+                        results: Grid::<usize>::new(
+                            submission.results.rows(),
+                            submission.results.cols(),
+                            0,
+                        ),
+                    });
+                counts.results.merge(&submission.results);
+                counts.total += 1;
+            }
+        }
+
+        for submission in submissions {
+            if let Some(survey_cat) = survey_rec_guard.categories.get_mut(&submission.id) {
+                merge_survey_category(survey_cat, submission);
+                survey_cat.total += 1;
             }
         }
         survey_rec_guard.total += 1;
@@ -184,3 +302,250 @@ pub async fn update_survey_activity(activity: SurveyRecord) -> Result<()> {
 
     Ok(())
 }
+
+const MAX_RESPONSE_LENGTH: usize = 2000;
+
+/// Checks a submitted category against the stored one and returns it with
+/// 0/1 votes, trimmed texts and one value per question.
+fn normalize_survey_category(
+    target: &SurveyRecordCategory,
+    source: &SurveyRecordCategory,
+) -> Result<SurveyRecordCategory> {
+    let rows = target.results.rows();
+    let cols = target.results.cols();
+    let mut normalized = SurveyRecordCategory {
+        id: target.id.clone(),
+        kind: target.kind,
+        answers: target.answers.clone(),
+        results: Grid::<usize>::new(rows, cols, 0),
+        responses: vec![vec![]; rows],
+        values: vec![vec![]; rows],
+        ..Default::default()
+    };
+
+    match target.kind {
+        SurveyCategoryKind::Text => {
+            if source.responses.len() > rows || source.responses.iter().any(|r| r.len() > 1) {
+                Err((StatusCode::BAD_REQUEST, "survey-malformed"))?
+            }
+            for (row, response) in source.responses.iter().enumerate() {
+                let Some(text) = response.first().map(|t| t.trim()) else {
+                    continue;
+                };
+                if text.chars().count() > MAX_RESPONSE_LENGTH {
+                    Err((StatusCode::BAD_REQUEST, "survey-response-too-long"))?
+                }
+                if !text.is_empty() {
+                    normalized.responses[row].push(text.to_string());
+                    normalized.results.set(row, 0, 1);
+                }
+            }
+        }
+        SurveyCategoryKind::Numeric => {
+            if source.values.len() > rows || source.values.iter().any(|v| v.len() > 1) {
+                Err((StatusCode::BAD_REQUEST, "survey-malformed"))?
+            }
+            for (row, value) in source.values.iter().enumerate() {
+                let Some(&value) = value.first() else {
+                    continue;
+                };
+                if !value.is_finite() {
+                    Err((StatusCode::BAD_REQUEST, "survey-malformed"))?
+                }
+                if target.min.is_some_and(|min| value < min as f64)
+                    || target.max.is_some_and(|max| value > max as f64)
+                {
+                    Err((StatusCode::BAD_REQUEST, "survey-value-out-of-range"))?
+                }
+                normalized.values[row].push(value);
+                normalized.results.set(row, 0, 1);
+            }
+        }
+        SurveyCategoryKind::Choice | SurveyCategoryKind::Scale | SurveyCategoryKind::Ranking => {
+            if source.results.rows() != rows || source.results.cols() != cols {
+                Err((StatusCode::BAD_REQUEST, "survey-malformed"))?
+            }
+            for row in 0..rows {
+                let mut selected = 0;
+                for col in 0..cols {
+                    match *source.results.get(row, col) {
+                        0 => {}
+                        1 => {
+                            normalized.results.set(row, col, 1);
+                            selected += 1;
+                        }
+                        _ => Err((StatusCode::BAD_REQUEST, "survey-malformed"))?,
+                    }
+                }
+                if selected > 1 {
+                    Err((StatusCode::BAD_REQUEST, "survey-too-many-selections"))?
+                }
+            }
+            if target
+                .max_selections
+                .is_some_and(|max| normalized.selected_count() > max)
+            {
+                Err((StatusCode::BAD_REQUEST, "survey-too-many-selections"))?
+            }
+            // A ranking is either untouched or gives every item its own position.
+            if target.kind == SurveyCategoryKind::Ranking && normalized.is_answered() {
+                let distinct = (0..cols)
+                    .all(|col| normalized.results.get_col(col).iter().filter(|v| ***v > 0).count() == 1);
+                if !normalized.is_complete() || !distinct {
+                    Err((StatusCode::BAD_REQUEST, "survey-invalid-ranking"))?
+                }
+            }
+        }
+    }
+
+    Ok(normalized)
+}
+
+fn merge_survey_category(target: &mut SurveyRecordCategory, source: SurveyRecordCategory) {
+    let rows = target.results.rows();
+    let mut rng = rand::rng();
+
+    target.results.merge(&source.results);
+    match target.kind {
+        SurveyCategoryKind::Text => {
+            target.responses.resize(rows, vec![]);
+            for (responses, submitted) in target.responses.iter_mut().zip(source.responses) {
+                if submitted.is_empty() {
+                    continue;
+                }
+                responses.extend(submitted);
+                responses.shuffle(&mut rng);
+            }
+        }
+        SurveyCategoryKind::Numeric => {
+            target.values.resize(rows, vec![]);
+            for (values, submitted) in target.values.iter_mut().zip(source.values) {
+                if submitted.is_empty() {
+                    continue;
+                }
+                values.extend(submitted);
+                values.shuffle(&mut rng);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(kind: SurveyCategoryKind, rows: usize, cols: usize) -> SurveyRecordCategory {
+        let items = |prefix: &str, count: usize| {
+            (0..count)
+                .map(|i| {
+                    let id = format!("{prefix}{i}");
+                    let item = SurveyCategoryItem {
+                        id: id.clone(),
+                        ..Default::default()
+                    };
+                    (id, item)
+                })
+                .collect::<IndexMap<_, _>>()
+        };
+        SurveyRecordCategory {
+            id: "c1".to_string(),
+            kind,
+            questions: items("q", rows),
+            answers: items("a", cols),
+            results: Grid::new(rows, cols, 0),
+            ..Default::default()
+        }
+    }
+
+    fn submitted(
+        target: &SurveyRecordCategory,
+        cells: &[(usize, usize, usize)],
+    ) -> SurveyRecordCategory {
+        let mut source = target.clone();
+        for (row, col, value) in cells {
+            source.results.set(*row, *col, *value);
+        }
+        source
+    }
+
+    fn error_code(result: Result<SurveyRecordCategory>) -> String {
+        result.err().map(|e| e.to_string()).unwrap_or_default()
+    }
+
+    #[test]
+    fn normalize_limits_selections() {
+        let mut target = category(SurveyCategoryKind::Choice, 3, 1);
+        target.answers.clear();
+        target.max_selections = Some(2);
+
+        let source = submitted(&target, &[(0, 0, 1), (2, 0, 1)]);
+        let normalized = normalize_survey_category(&target, &source).ok().unwrap();
+        assert_eq!(normalized.selected_count(), 2);
+
+        let source = submitted(&target, &[(0, 0, 1), (1, 0, 1), (2, 0, 1)]);
+        let code = error_code(normalize_survey_category(&target, &source));
+        assert_eq!(code, "survey-too-many-selections");
+
+        let source = submitted(&target, &[(0, 0, 2)]);
+        let code = error_code(normalize_survey_category(&target, &source));
+        assert_eq!(code, "survey-malformed");
+    }
+
+    #[test]
+    fn normalize_rejects_several_options_in_a_row() {
+        let target = category(SurveyCategoryKind::Choice, 1, 3);
+        let source = submitted(&target, &[(0, 0, 1), (0, 2, 1)]);
+        let code = error_code(normalize_survey_category(&target, &source));
+        assert_eq!(code, "survey-too-many-selections");
+
+        let mut source = target.clone();
+        source.results = Grid::new(2, 3, 0);
+        let code = error_code(normalize_survey_category(&target, &source));
+        assert_eq!(code, "survey-malformed");
+    }
+
+    #[test]
+    fn normalize_checks_rankings() {
+        let target = category(SurveyCategoryKind::Ranking, 3, 3);
+        assert!(normalize_survey_category(&target, &target).is_ok());
+
+        let source = submitted(&target, &[(0, 1, 1), (1, 0, 1), (2, 2, 1)]);
+        assert!(normalize_survey_category(&target, &source).is_ok());
+
+        let partial = submitted(&target, &[(0, 1, 1), (1, 0, 1)]);
+        let code = error_code(normalize_survey_category(&target, &partial));
+        assert_eq!(code, "survey-invalid-ranking");
+
+        let shared = submitted(&target, &[(0, 1, 1), (1, 1, 1), (2, 2, 1)]);
+        let code = error_code(normalize_survey_category(&target, &shared));
+        assert_eq!(code, "survey-invalid-ranking");
+    }
+
+    #[test]
+    fn normalize_trims_text_and_bounds_numbers() {
+        let target = category(SurveyCategoryKind::Text, 2, 1);
+        let mut source = target.clone();
+        source.responses = vec![vec!["  так  ".to_string()], vec!["   ".to_string()]];
+        let normalized = normalize_survey_category(&target, &source).ok().unwrap();
+        assert_eq!(normalized.responses, [vec!["так".to_string()], vec![]]);
+        assert_eq!(normalized.selected_count(), 1);
+
+        source.responses = vec![vec!["x".repeat(MAX_RESPONSE_LENGTH + 1)]];
+        let code = error_code(normalize_survey_category(&target, &source));
+        assert_eq!(code, "survey-response-too-long");
+
+        let mut target = category(SurveyCategoryKind::Numeric, 1, 1);
+        target.min = Some(0);
+        target.max = Some(10);
+        let mut source = target.clone();
+        source.values = vec![vec![10.0]];
+        assert!(normalize_survey_category(&target, &source).is_ok());
+        source.values = vec![vec![10.5]];
+        let code = error_code(normalize_survey_category(&target, &source));
+        assert_eq!(code, "survey-value-out-of-range");
+        source.values = vec![vec![f64::NAN]];
+        let code = error_code(normalize_survey_category(&target, &source));
+        assert_eq!(code, "survey-malformed");
+    }
+}
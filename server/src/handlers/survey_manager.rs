@@ -41,12 +41,16 @@ pub async fn update_survey(
         name,
         node,
         categories,
+        min_segment_size,
     } = payload;
     let survey_arc = Store::find::<Survey>(&session.workspace, survey_id).await?;
     let snapshot = {
         let mut survey_guard = survey_arc.write().await;
         survey_guard.name = name;
         survey_guard.node = node;
+        if let Some(min_segment_size) = min_segment_size {
+            survey_guard.min_segment_size = Some(min_segment_size.max(1));
+        }
         if !categories.is_empty() {
             let categories = categories
                 .into_iter()
@@ -100,18 +104,59 @@ pub async fn update_survey_category(
         order,
         answers,
         questions,
+        kind,
+        min,
+        max,
+        conditions,
+        required,
+        max_selections,
+        segment,
     } = payload;
 
+    if min.zip(max).is_some_and(|(min, max)| min >= max) {
+        Err((StatusCode::BAD_REQUEST, "invalid-range"))?
+    }
+
+    // Only choice categories use the answer list, and a selection limit only
+    // applies to a plain list of options.
+    let answers = if kind == SurveyCategoryKind::Choice { answers } else { Vec::new() };
+    let max_selections = max_selections
+        .filter(|&max| max > 0 && kind == SurveyCategoryKind::Choice && answers.is_empty());
+    // Segments come from a single choice: answers without questions.
+    let segment = segment
+        && kind == SurveyCategoryKind::Choice
+        && questions.is_empty()
+        && !answers.is_empty();
     let answers = answers.into_iter().map(|a| (a.id.clone(), a)).collect();
     let questions = questions.into_iter().map(|q| (q.id.clone(), q)).collect();
 
     let (snapshot, category) = {
         let mut survey_guard = survey_arc.write().await;
+        let conditions = conditions
+            .into_iter()
+            .filter(|condition| {
+                condition.category != category_id
+                    && survey_guard.categories.get(&condition.category).is_some_and(|c| {
+                        c.kind == SurveyCategoryKind::Choice
+                            && (c.answers.contains_key(&condition.option)
+                                || c.answers.is_empty()
+                                    && c.questions.contains_key(&condition.option))
+                    })
+            })
+            .collect::<Vec<_>>();
+
         let category = if let Some(category) = survey_guard.categories.get_mut(&category_id) {
             category.name = name;
             category.order = order;
             category.answers = answers;
             category.questions = questions;
+            category.kind = kind;
+            category.min = min;
+            category.max = max;
+            category.conditions = conditions;
+            category.required = required;
+            category.max_selections = max_selections;
+            category.segment = segment;
 
             category.clone()
         } else {
@@ -121,7 +166,13 @@ pub async fn update_survey_category(
                 order,
                 answers,
                 questions,
-                ..Default::default()
+                kind,
+                min,
+                max,
+                conditions,
+                required,
+                max_selections,
+                segment,
             };
 
             survey_guard
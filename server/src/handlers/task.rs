@@ -27,12 +27,16 @@ pub async fn create_task(
     Json(payload): Json<CreateTaskPayload>,
 ) -> Result<Json<Task>> {
     let kind = EntityKind::from_str(&kind).map_err(|_| (StatusCode::BAD_REQUEST, "bad-request"))?;
+    let (opens_at, closes_at) = (payload.opens_at, payload.closes_at);
+    check_task_window(opens_at, closes_at)?;
 
-    let task = match kind {
+    let mut task = match kind {
         EntityKind::Quiz => create_quiz_record(&session, payload).await?,
         EntityKind::Survey => create_survey_record(&session, payload).await?,
         _ => Err((StatusCode::BAD_REQUEST, "bad-request"))?,
     };
+    task.opens_at = opens_at;
+    task.closes_at = closes_at;
 
     TaskRepository::upsert(&session.workspace, task.clone()).await?;
     Ok(Json(task))
@@ -48,6 +52,13 @@ pub async fn delete_task(
     }
 
     Store::delete(&session.workspace, &task_id).await?;
+    if kind == EntityKind::QuizRecord {
+        for record in Store::scan(&session.workspace, &task_id).await? {
+            Store::delete(&session.workspace, &record.id).await?;
+        }
+        QuizResultRepository::evict(&session.workspace, &task_id);
+    }
+    ImageService::remove_entities(&session.workspace, vec![task_id.clone()]).await?;
     TaskRepository::delete(&session.workspace, Some(task_id), None).await
 }
 
@@ -67,25 +78,40 @@ pub async fn get_task_categories(
 }
 
 pub async fn finish_task(session: Session, Path(task_id): Path<String>) -> Result<Json<String>> {
-    let task = TaskRepository::get(&session.workspace, &task_id).await?;
-    if task.kind != EntityKind::QuizRecord && task.kind != EntityKind::SurveyRecord {
-        Err((StatusCode::BAD_REQUEST, "bad-request"))?
-    }
-    let mut metadata = task.metadata;
-    metadata.update(&session.username);
-    let entity = match task.kind {
-        EntityKind::QuizRecord |
-        EntityKind::SurveyRecord => Entity {
-            id: task.id,
-            name: task.name,
-            kind: task.kind,
-            node: task.node,
-            path: task.path,
-            metadata,
-        },
-        _ => Err((StatusCode::BAD_REQUEST, "not-found"))?,
-    };
-    TaskRepository::delete(&session.workspace, Some(task_id.clone()), None).await?;
-    EntityRepository::upsert(&session.workspace, entity).await?;
+    TaskRepository::finish(&session.workspace, &task_id, &session.username).await?;
     Ok(Json(task_id))
-}
\ No newline at end of file
+}
+
+pub async fn reopen_task(session: Session, Path(entity_id): Path<String>) -> Result<Json<Task>> {
+    session.checked_supervisor()?;
+    let task = TaskRepository::reopen(&session.workspace, &entity_id, &session.username).await?;
+    Ok(Json(task))
+}
+
+pub async fn update_task_schedule(
+    session: Session,
+    Path(task_id): Path<String>,
+    Json(payload): Json<UpdateTaskSchedulePayload>,
+) -> Result<Json<Task>> {
+    session.checked_supervisor()?;
+    let UpdateTaskSchedulePayload {
+        opens_at,
+        closes_at,
+    } = payload;
+    check_task_window(opens_at, closes_at)?;
+
+    let mut task = TaskRepository::get(&session.workspace, &task_id).await?;
+    task.opens_at = opens_at;
+    task.closes_at = closes_at;
+    TaskRepository::upsert(&session.workspace, task.clone()).await?;
+    Ok(Json(task))
+}
+
+fn check_task_window(opens_at: Option<i64>, closes_at: Option<i64>) -> Result<()> {
+    if let (Some(opens_at), Some(closes_at)) = (opens_at, closes_at)
+        && closes_at <= opens_at
+    {
+        Err((StatusCode::BAD_REQUEST, "task-window-invalid"))?
+    }
+    Ok(())
+}
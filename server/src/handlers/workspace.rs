@@ -4,8 +4,7 @@ use ::futures::{StreamExt, TryStreamExt, stream};
 use ::indexmap::IndexMap;
 use ::shared::{common::*, models::*, payloads::*, services::*, utils::*};
 use ::std::{collections::HashSet, str::FromStr};
-use ::tokio::{fs, task::spawn_blocking};
-use crate::common::State;
+use ::tokio::task::spawn_blocking;
 
 pub async fn list_workspaces(connection: Connection) -> Result<Json<Vec<WorkspaceMetadata>>> {
     connection.checked()?;
@@ -213,28 +212,23 @@ fn get_mut_tree(
 }
 
 pub async fn init_workspace_meta(workspace: &Workspace) -> Result<()> {
-    let path = Store::get_path(&workspace.id, WORKSPACE);
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).await.map_err(map_log_err)?;
-    }
     let metadata = WorkspaceMetadata {
         id: workspace.id.clone(),
         name: workspace.name.clone(),
         version: 0
     };
     let encrypted = Store::encrypt_binary(&workspace.id, metadata, false).await?;
-    fs::write(&path, encrypted).await.map_err(map_log_err)
+    Store::save_raw(vec![StorageRecord {
+        workspace: workspace.id.clone(),
+        id: WORKSPACE.to_string(),
+        kind: None,
+        node: String::new(),
+        data: encrypted,
+    }])
+    .await
 }
 async fn load_workspaces_meta() -> Result<Vec<WorkspaceMetadata>> {
-    let mut rd = fs::read_dir(State::path().join("workspaces")).await.map_err(map_log_err)?;
-    let mut names = Vec::with_capacity(128);
-    while let Some(entry) = rd.next_entry().await.map_err(map_log_err)? {
-        if entry.file_type().await.map_err(map_log_err)?.is_dir() {
-            if let Ok(ws) = entry.file_name().into_string() {
-                names.push(ws);
-            }
-        }
-    }
+    let names = Store::workspaces().await?;
 
     let cpu = std::thread::available_parallelism()
         .map(|n| n.get())
@@ -243,16 +237,14 @@ async fn load_workspaces_meta() -> Result<Vec<WorkspaceMetadata>> {
 
     let metas = stream::iter(names)
         .map(|workspace| async move {
-            let path = Store::get_path(&workspace, WORKSPACE);
-            match fs::read(&path).await {
-                Ok(encrypted) => {
+            match Store::load_raw(&workspace, WORKSPACE).await? {
+                Some(encrypted) => {
                     let meta =
                         Store::decrypt_binary::<WorkspaceMetadata>(workspace, encrypted, false)
                             .await?;
                     Ok::<Option<WorkspaceMetadata>, Error>(Some(meta))
                 }
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
-                Err(e) => Err(map_log_err(e)),
+                None => Ok(None),
             }
         })
         .buffer_unordered(concurrency)
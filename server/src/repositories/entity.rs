@@ -6,7 +6,7 @@ pub struct EntityRepository;
 
 impl EntityRepository {
     pub async fn init(workspace: Entity) -> Result<()> {
-        if !Store::get_path(&workspace.id, ENTITIES).exists() {
+        if !Store::exists(&workspace.id, ENTITIES).await {
             let entities = Entities::new(workspace);
             Store::upsert(entities).await?;
         };
@@ -19,8 +19,15 @@ impl EntityRepository {
         ids: Option<Vec<String>>,
         nodes: Option<Vec<String>>,
     ) -> Result<Vec<Entity>> {
-        let entities_arc = Store::find::<Entities>(workspace, ENTITIES).await?;
+        let ws_id = workspace.into();
+        let entities_arc = Store::find::<Entities>(&ws_id, ENTITIES).await?;
 
+        let ids = match ids {
+            None if kind.is_some() || nodes.is_some() => {
+                Store::query(&ws_id, kind.clone(), nodes.clone()).await?
+            }
+            ids => ids,
+        };
         let kinds = kind.map(|vec| vec.into_iter().collect::<HashSet<EntityKind>>());
         let ids = ids.map(|vec| vec.into_iter().collect::<HashSet<String>>());
         let nodes = nodes.map(|vec| vec.into_iter().collect::<HashSet<String>>());
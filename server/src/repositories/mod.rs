@@ -1,9 +1,11 @@
 mod entity;
+mod quiz_result;
 mod student;
 mod task;
 
 pub use self::{
     entity::*,
+    quiz_result::*,
     student::*,
     task::*,
 };
\ No newline at end of file
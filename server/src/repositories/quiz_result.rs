@@ -0,0 +1,278 @@
+use crate::services::*;
+use ::dashmap::{DashMap, DashSet};
+use ::shared::{common::*, models::*};
+use ::std::sync::{Arc, LazyLock};
+use ::tokio::sync::{Mutex, RwLock};
+
+const COMPACT_THRESHOLD: usize = 64;
+
+static STUDENT_LOCKS: LazyLock<DashMap<String, Arc<Mutex<()>>>> = LazyLock::new(DashMap::new);
+static PENDING: LazyLock<DashMap<String, usize>> = LazyLock::new(DashMap::new);
+/// Tasks scanned for entries since startup, later reads only compact once entries are appended.
+static SCANNED: LazyLock<DashSet<String>> = LazyLock::new(DashSet::new);
+
+pub struct QuizResultRepository;
+
+impl QuizResultRepository {
+    pub async fn find(
+        workspace: impl Into<String>,
+        task_id: impl Into<String>,
+    ) -> Result<Arc<RwLock<QuizRecord>>> {
+        let ws_id = workspace.into();
+        let task_id = task_id.into();
+        let key = format!("{ws_id}{task_id}");
+        let quiz_rec_arc = Store::find::<QuizRecord>(&ws_id, &task_id).await?;
+        // Legacy markers are migrated before any entry is folded in, so new answers never go
+        // through the migration.
+        let migrated = {
+            let mut quiz_rec_guard = quiz_rec_arc.write().await;
+            quiz_rec_guard
+                .migrate_open_markers()
+                .then(|| quiz_rec_guard.clone())
+        };
+        if let Some(snapshot) = migrated {
+            Store::upsert(snapshot).await?;
+        }
+        if !SCANNED.contains(&key) || PENDING.get(&key).is_some_and(|pending| *pending > 0) {
+            Self::compact(&ws_id, &task_id).await?;
+            return Store::find::<QuizRecord>(ws_id, task_id).await;
+        }
+        Ok(quiz_rec_arc)
+    }
+
+    pub async fn quiz(quiz_rec: &QuizRecord) -> Result<Quiz> {
+        if let Some(snapshot) = &quiz_rec.snapshot {
+            return Ok(snapshot.clone());
+        }
+        let quiz_arc = Store::find::<Quiz>(&quiz_rec.workspace, &quiz_rec.quiz).await?;
+        Ok(quiz_arc.read().await.clone())
+    }
+
+    /// Question tags of the categories the record draws from, sorted and deduplicated.
+    pub async fn tags(quiz_rec: &QuizRecord) -> Result<Vec<String>> {
+        let quiz = Self::quiz(quiz_rec).await?;
+        let mut tags = quiz
+            .categories
+            .values()
+            .filter(|c| quiz_rec.categories.contains_key(&c.id))
+            .flat_map(|c| c.questions.values())
+            .flat_map(|q| q.tags.iter().cloned())
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+        Ok(tags)
+    }
+
+    pub async fn entry(
+        workspace: impl Into<String>,
+        task_id: impl AsRef<str>,
+        student_id: impl AsRef<str>,
+    ) -> Result<Option<QuizRecordEntry>> {
+        let ws_id = workspace.into();
+        let id = entry_id(task_id.as_ref(), student_id.as_ref());
+        match Store::load_raw(&ws_id, id).await? {
+            Some(data) => Ok(Some(Store::decrypt_binary(ws_id, data, false).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn lock(
+        workspace: impl AsRef<str>,
+        task_id: impl AsRef<str>,
+        student_id: impl AsRef<str>,
+    ) -> Arc<Mutex<()>> {
+        let key = format!(
+            "{}{}",
+            workspace.as_ref(),
+            entry_id(task_id.as_ref(), student_id.as_ref())
+        );
+        STUDENT_LOCKS.entry(key).or_default().clone()
+    }
+
+    pub async fn append(
+        workspace: impl Into<String>,
+        task_id: impl Into<String>,
+        entry: QuizRecordEntry,
+    ) -> Result<()> {
+        let ws_id = workspace.into();
+        let task_id = task_id.into();
+        let id = entry_id(&task_id, &entry.student);
+        let data = Store::encrypt_binary(&ws_id, entry, false).await?;
+        Store::save_raw(vec![StorageRecord {
+            workspace: ws_id.clone(),
+            id,
+            kind: None,
+            node: String::new(),
+            data,
+        }])
+        .await?;
+
+        let pending = {
+            let mut pending = PENDING.entry(format!("{ws_id}{task_id}")).or_default();
+            *pending += 1;
+            *pending
+        };
+        if pending >= COMPACT_THRESHOLD {
+            tokio::spawn(async move {
+                Self::compact(&ws_id, &task_id).await.ok();
+            });
+        }
+        Ok(())
+    }
+
+    /// Applies `f` to the record while holding the student's lock, folding in any pending entry
+    /// first so the change is never overwritten by a later compaction.
+    pub async fn update_student<R>(
+        workspace: impl Into<String>,
+        task_id: impl Into<String>,
+        student_id: impl Into<String>,
+        f: impl FnOnce(&mut QuizRecord) -> Result<R>,
+    ) -> Result<R> {
+        let ws_id = workspace.into();
+        let task_id = task_id.into();
+        let student_id = student_id.into();
+        let quiz_rec_arc = Self::find(&ws_id, &task_id).await?;
+
+        let lock = Self::lock(&ws_id, &task_id, &student_id);
+        let _guard = lock.lock().await;
+        let entry = Self::entry(&ws_id, &task_id, &student_id).await?;
+        let (snapshot, progress, out) = {
+            let mut quiz_rec_guard = quiz_rec_arc.write().await;
+            if !quiz_rec_guard.students.contains_key(&student_id) {
+                Err((StatusCode::NOT_FOUND, "student-not-found"))?
+            }
+            if let Some(entry) = entry.clone() {
+                quiz_rec_guard.apply_entry(entry);
+            }
+            let out = f(&mut quiz_rec_guard)?;
+            (quiz_rec_guard.clone(), quiz_rec_guard.progress(), out)
+        };
+        Store::upsert(snapshot).await?;
+        if entry.is_some() {
+            Store::delete(&ws_id, entry_id(&task_id, &student_id)).await?;
+        }
+        Self::update_progress(&ws_id, &task_id, progress).await?;
+        Ok(out)
+    }
+
+    /// Applies `f` to the record while holding every student's lock, folding in all pending
+    /// entries first, for changes that span the whole record such as a regrade.
+    pub async fn update_all<R>(
+        workspace: impl Into<String>,
+        task_id: impl Into<String>,
+        f: impl FnOnce(&mut QuizRecord) -> Result<R>,
+    ) -> Result<R> {
+        let ws_id = workspace.into();
+        let task_id = task_id.into();
+        let quiz_rec_arc = Self::find(&ws_id, &task_id).await?;
+
+        let mut student_ids = {
+            let quiz_rec_guard = quiz_rec_arc.read().await;
+            quiz_rec_guard.students.keys().cloned().collect::<Vec<_>>()
+        };
+        student_ids.sort_unstable();
+        let locks = student_ids
+            .iter()
+            .map(|student_id| Self::lock(&ws_id, &task_id, student_id))
+            .collect::<Vec<_>>();
+        let mut guards = Vec::with_capacity(locks.len());
+        for lock in locks.iter() {
+            guards.push(lock.lock().await);
+        }
+
+        let mut entries = Vec::new();
+        for record in Store::scan(&ws_id, &task_id).await? {
+            let entry =
+                Store::decrypt_binary::<QuizRecordEntry>(&ws_id, record.data.clone(), false)
+                    .await?;
+            if student_ids.binary_search(&entry.student).is_ok() {
+                entries.push((record, entry));
+            }
+        }
+
+        let (snapshot, progress, out) = {
+            let mut quiz_rec_guard = quiz_rec_arc.write().await;
+            for (_, entry) in entries.iter() {
+                quiz_rec_guard.apply_entry(entry.clone());
+            }
+            let out = f(&mut quiz_rec_guard)?;
+            (quiz_rec_guard.clone(), quiz_rec_guard.progress(), out)
+        };
+        Store::upsert(snapshot).await?;
+        for (record, _) in entries {
+            Store::delete(&ws_id, &record.id).await?;
+        }
+        Self::update_progress(&ws_id, &task_id, progress).await?;
+        Ok(out)
+    }
+
+    pub async fn compact(workspace: &str, task_id: &str) -> Result<()> {
+        let key = format!("{workspace}{task_id}");
+        PENDING.remove(&key);
+        let records = Store::scan(workspace, task_id).await?;
+        SCANNED.insert(key);
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let mut entries = Vec::with_capacity(records.len());
+        for record in records {
+            let entry =
+                Store::decrypt_binary::<QuizRecordEntry>(workspace, record.data.clone(), false)
+                    .await?;
+            entries.push((record, entry));
+        }
+
+        let quiz_rec_arc = Store::find::<QuizRecord>(workspace, task_id).await?;
+        let (snapshot, progress) = {
+            let mut quiz_rec_guard = quiz_rec_arc.write().await;
+            for (_, entry) in entries.iter() {
+                quiz_rec_guard.apply_entry(entry.clone());
+            }
+            (quiz_rec_guard.clone(), quiz_rec_guard.progress())
+        };
+        Store::upsert(snapshot).await?;
+
+        for (record, entry) in entries {
+            let lock = Self::lock(workspace, task_id, &entry.student);
+            {
+                let _guard = lock.lock().await;
+                if Store::load_raw(workspace, &record.id).await?.as_ref() == Some(&record.data) {
+                    Store::delete(workspace, &record.id).await?;
+                }
+            }
+            drop(lock);
+            STUDENT_LOCKS.remove_if(&format!("{workspace}{}", record.id), |_, lock| {
+                Arc::strong_count(lock) == 1
+            });
+        }
+
+        Self::update_progress(workspace, task_id, progress).await
+    }
+
+    /// Forgets the locks and counters of a task that takes no more submissions.
+    pub fn evict(workspace: &str, task_id: &str) {
+        let key = format!("{workspace}{task_id}");
+        let prefix = format!("{key}/");
+        STUDENT_LOCKS.retain(|k, lock| !k.starts_with(&prefix) || Arc::strong_count(lock) > 1);
+        PENDING.remove(&key);
+        SCANNED.remove(&key);
+    }
+
+    pub async fn update_progress(workspace: &str, task_id: &str, progress: usize) -> Result<()> {
+        let tasks_arc = Store::find::<Tasks>(workspace, TASKS).await?;
+        let snapshot = {
+            let mut tasks_guard = tasks_arc.write().await;
+            let Some(task) = tasks_guard.get_mut(task_id) else {
+                return Ok(());
+            };
+            task.progress = progress;
+            tasks_guard.clone()
+        };
+        Store::upsert(snapshot).await
+    }
+}
+
+fn entry_id(task_id: &str, student_id: &str) -> String {
+    format!("{task_id}/{student_id}")
+}
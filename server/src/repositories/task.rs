@@ -1,13 +1,18 @@
-use crate::services::*;
+use crate::{repositories::*, services::*};
 use ::shared::{common::*, models::*};
+use ::chrono::Utc;
 use ::std::collections::HashSet;
 
+/// Seconds past the close time or a student's deadline during which submissions are still
+/// accepted, so a student who started before the deadline does not lose the attempt.
+pub const SUBMIT_GRACE: i64 = 120;
+
 pub struct TaskRepository;
 
 impl TaskRepository {
     pub async fn init(workspace: impl AsRef<str>) -> Result<()> {
         let ws_id = workspace.as_ref();
-        if !Store::get_path(ws_id, TASKS).exists() {
+        if !Store::exists(ws_id, TASKS).await {
             let tasks = Tasks::new(ws_id);
             Store::upsert(tasks).await?;
         };
@@ -20,6 +25,23 @@ impl TaskRepository {
         task.ok_or_else(|| (StatusCode::NOT_FOUND, "not-found").into())
     }
 
+    /// Returns the task when it accepts activity right now. `grace` extends the close time, so
+    /// answers sent by students who started before the deadline are still accepted.
+    pub async fn get_open(
+        workspace: impl Into<String>,
+        task_id: impl Into<String>,
+        grace: i64,
+    ) -> Result<Task> {
+        let task = Self::get(workspace, task_id)
+            .await
+            .map_err(|_| (StatusCode::NOT_FOUND, "task-not-found"))?;
+        match task.status_with_grace(Utc::now().timestamp(), grace) {
+            TaskStatus::Scheduled => Err((StatusCode::FORBIDDEN, "task-not-open"))?,
+            TaskStatus::Closed => Err((StatusCode::FORBIDDEN, "task-closed"))?,
+            TaskStatus::Open => Ok(task),
+        }
+    }
+
     pub async fn list_by_filter(
         workspace: impl Into<String>,
         kind: Option<Vec<EntityKind>>,
@@ -95,4 +117,78 @@ impl TaskRepository {
 
         Store::upsert(snapshot).await
     }
-}
\ No newline at end of file
+
+    pub async fn finish(
+        workspace: impl Into<String>,
+        task_id: impl Into<String>,
+        username: impl Into<String>,
+    ) -> Result<()> {
+        let ws_id = workspace.into();
+        let task_id = task_id.into();
+        let task = Self::get(&ws_id, &task_id).await?;
+        if task.kind == EntityKind::QuizRecord {
+            QuizResultRepository::compact(&ws_id, &task_id).await?;
+            QuizResultRepository::evict(&ws_id, &task_id);
+        }
+        let mut metadata = task.metadata;
+        metadata.update(username);
+        let entity = match task.kind {
+            EntityKind::QuizRecord |
+            EntityKind::SurveyRecord => Entity {
+                id: task.id,
+                name: task.name,
+                kind: task.kind,
+                node: task.node,
+                path: task.path,
+                metadata,
+            },
+            _ => Err((StatusCode::BAD_REQUEST, "not-found"))?,
+        };
+        Self::delete(&ws_id, Some(task_id), None).await?;
+        EntityRepository::upsert(&ws_id, entity).await
+    }
+
+    pub async fn reopen(
+        workspace: impl Into<String>,
+        entity_id: impl Into<String>,
+        username: impl Into<String>,
+    ) -> Result<Task> {
+        let ws_id = workspace.into();
+        let entity_id = entity_id.into();
+        let entity = EntityRepository::find(&ws_id, &entity_id).await?;
+        let (progress, practice) = match entity.kind {
+            EntityKind::QuizRecord => {
+                let quiz_rec_arc = QuizResultRepository::find(&ws_id, &entity_id).await?;
+                let quiz_rec_guard = quiz_rec_arc.read().await;
+                (quiz_rec_guard.progress(), quiz_rec_guard.practice)
+            }
+            EntityKind::SurveyRecord => {
+                let total = Store::find::<SurveyRecord>(&ws_id, &entity_id)
+                    .await?
+                    .read()
+                    .await
+                    .total;
+                (total, false)
+            }
+            _ => Err((StatusCode::BAD_REQUEST, "bad-request"))?,
+        };
+        let mut metadata = entity.metadata;
+        metadata.update(username);
+        let task = Task {
+            id: entity.id,
+            workspace: ws_id.clone(),
+            kind: entity.kind,
+            name: entity.name,
+            node: entity.node,
+            path: entity.path,
+            progress,
+            opens_at: None,
+            closes_at: None,
+            practice,
+            metadata,
+        };
+        Self::upsert(&ws_id, task.clone()).await?;
+        EntityRepository::delete(&ws_id, Some(entity_id), None).await?;
+        Ok(task)
+    }
+}
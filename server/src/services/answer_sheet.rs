@@ -0,0 +1,121 @@
+use ::image::GrayImage;
+use ::shared::{common::*, models::*};
+use ::tokio::task;
+
+const MARK_RATIO: f32 = 0.35;
+const INK_LEVEL: f32 = 0.6;
+
+pub struct ScannedAnswerSheet {
+    pub code: String,
+    image: GrayImage,
+    origin: (f32, f32),
+    x_axis: (f32, f32),
+    y_axis: (f32, f32),
+    threshold: u8,
+}
+
+impl ScannedAnswerSheet {
+    pub fn is_marked(&self, question: usize, answer: usize) -> bool {
+        let (cx, cy) = AnswerSheetLayout::bubble(question, answer);
+        let radius = AnswerSheetLayout::BUBBLE_SIZE * 0.3;
+        let steps = 8;
+
+        let (mut dark, mut total) = (0, 0);
+        for i in 0..=steps {
+            for j in 0..=steps {
+                let dx = -radius + 2.0 * radius * (i as f32) / (steps as f32);
+                let dy = -radius + 2.0 * radius * (j as f32) / (steps as f32);
+                if dx * dx + dy * dy > radius * radius {
+                    continue;
+                }
+                let Some(luma) = self.sample(cx + dx, cy + dy) else {
+                    continue;
+                };
+                total += 1;
+                if luma < self.threshold {
+                    dark += 1;
+                }
+            }
+        }
+
+        total > 0 && (dark as f32) / (total as f32) >= MARK_RATIO
+    }
+
+    fn sample(&self, x: f32, y: f32) -> Option<u8> {
+        let px = self.origin.0 + self.x_axis.0 * x + self.y_axis.0 * y;
+        let py = self.origin.1 + self.x_axis.1 * x + self.y_axis.1 * y;
+        if px < 0.0 || py < 0.0 {
+            return None;
+        }
+        let (px, py) = (px.round() as u32, py.round() as u32);
+        if px >= self.image.width() || py >= self.image.height() {
+            return None;
+        }
+        Some(self.image.get_pixel(px, py).0[0])
+    }
+
+    fn paper_threshold(&self) -> u8 {
+        let mut samples = (0..AnswerSheetLayout::capacity())
+            .flat_map(|q| {
+                (0..AnswerSheetLayout::MAX_ANSWERS).map(move |a| {
+                    let (x, y) = AnswerSheetLayout::bubble(q, a);
+                    (x + AnswerSheetLayout::COLUMN_WIDTH / 2.0, y)
+                })
+            })
+            .filter_map(|(x, y)| self.sample(x, y))
+            .collect::<Vec<_>>();
+        if samples.is_empty() {
+            return 128;
+        }
+        samples.sort_unstable();
+        let paper = samples[samples.len() / 2] as f32;
+        (paper * INK_LEVEL) as u8
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct AnswerSheetService;
+
+impl AnswerSheetService {
+    pub async fn scan(data: Vec<u8>) -> Result<ScannedAnswerSheet> {
+        task::spawn_blocking(move || Self::scan_sync(&data))
+            .await
+            .map_err(map_log_err)?
+    }
+
+    fn scan_sync(data: &[u8]) -> Result<ScannedAnswerSheet> {
+        let image = ::image::load_from_memory(data)
+            .map_err(|_| (StatusCode::BAD_REQUEST, "image-read-error"))?
+            .to_luma8();
+
+        let mut prepared = rqrr::PreparedImage::prepare(image.clone());
+        let (code, bounds) = prepared
+            .detect_grids()
+            .into_iter()
+            .find_map(|grid| {
+                let (_, content) = grid.decode().ok()?;
+                QuizVariantSheet::from_code(&content).map(|_| (content, grid.bounds))
+            })
+            .ok_or((StatusCode::BAD_REQUEST, "answer-sheet-not-recognized"))?;
+
+        let [top_left, top_right, _, bottom_left] = bounds;
+        let scale = AnswerSheetLayout::QR_SIZE;
+        let mut sheet = ScannedAnswerSheet {
+            code,
+            image,
+            origin: (top_left.x as f32, top_left.y as f32),
+            x_axis: (
+                (top_right.x - top_left.x) as f32 / scale,
+                (top_right.y - top_left.y) as f32 / scale,
+            ),
+            y_axis: (
+                (bottom_left.x - top_left.x) as f32 / scale,
+                (bottom_left.y - top_left.y) as f32 / scale,
+            ),
+            threshold: 128,
+        };
+        sheet.threshold = sheet.paper_threshold();
+
+        Ok(sheet)
+    }
+}
@@ -1,12 +1,13 @@
 use crate::{common::*, repositories::*, services::*};
-use ::shared::{common::*, models::*};
+use ::serde::Deserialize;
+use ::shared::{common::*, models::*, payloads::*, utils::*};
 use ::std::{
+    collections::{HashMap, HashSet},
     fs::{self, File},
-    io::{self, Write},
+    io::{self, Read, Write},
     path::{Path, PathBuf},
-    sync::Arc,
+    time::Duration,
 };
-use ::tokio::sync::RwLock;
 use ::zip::{
     CompressionMethod, ZipArchive, ZipWriter,
     write::{ExtendedFileOptions, FileOptions},
@@ -18,6 +19,19 @@ const EXPORT_WORKSPACE: [EntityKind; 3] =
 const EXPORT_ENTITIES: [EntityKind; 3] =
     [EntityKind::QuizRecord, EntityKind::SurveyRecord, EntityKind::Json];
 
+const EXPORT_PACKAGE: [EntityKind; 2] = [EntityKind::Quiz, EntityKind::Survey];
+
+const IMPORT_USERS: &str = "users";
+
+const ARCHIVE_LIMITS: ArchiveLimits = ArchiveLimits {
+    entries: 50_000,
+    archive_size: 4 * 1024 * 1024 * 1024,
+    entry_size: 256 * 1024 * 1024,
+    compression_ratio: 100,
+    ratio_threshold: 1024 * 1024,
+};
+const STAGE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
 pub struct ExchangeService;
 
 impl ExchangeService {
@@ -44,12 +58,20 @@ impl ExchangeService {
             .find(|e| e.kind == EntityKind::Workspace)
             .ok_or((StatusCode::NOT_FOUND, "workspace-not-found"))?;
 
+        for entity in entities.iter().filter(|e| e.kind == EntityKind::QuizRecord) {
+            QuizResultRepository::compact(&ws_id, &entity.id).await?;
+        }
         let mut payload =
-            build_entities_payload(&path, &ws_id, entities.iter().map(|e| e.id.clone()));
+            stage_entities_payload(&temp_path, &ws_id, entities.iter().map(|e| e.id.clone())).await?;
         payload.push((
             path.join(format!("assets/{ws_id}")),
             "assets".to_string(),
         ));
+        if let Some(data) = Store::load_raw(&ws_id, STUDENTS).await? {
+            let students_path = temp_path.join("students.bin");
+            fs::write(&students_path, data).map_err(map_log_err)?;
+            payload.push((students_path, "students.bin".to_string()));
+        }
 
         let ws_meta = WorkspaceMetadata {
             id: ws.id.clone(),
@@ -89,8 +111,26 @@ impl ExchangeService {
         )
         .await?;
 
+        for entity in entities.iter().filter(|e| e.kind == EntityKind::QuizRecord) {
+            QuizResultRepository::compact(&ws_id, &entity.id).await?;
+        }
         let mut payload =
-            build_entities_payload(&path, &ws_id, entities.iter().map(|e| e.id.clone()));
+            stage_entities_payload(&temp_path, &ws_id, entities.iter().map(|e| e.id.clone())).await?;
+        // Segments below the k-anonymity threshold are not exported.
+        for entity in entities.iter().filter(|e| e.kind == EntityKind::SurveyRecord) {
+            let mut record = Store::find::<SurveyRecord>(&ws_id, &entity.id)
+                .await?
+                .read()
+                .await
+                .clone();
+            if record.segments.is_empty() {
+                continue;
+            }
+            record.suppress_segments();
+            let data = Store::encrypt_binary(&ws_id, record, false).await?;
+            let path = temp_path.join("entities").join(format!("{}.bin", entity.id));
+            fs::write(path, data).map_err(map_log_err)?;
+        }
 
         let ws_meta = {
             let ws_arc = Store::find::<Workspace>(&ws_id, &ws_id).await?;
@@ -117,75 +157,492 @@ impl ExchangeService {
         Ok(())
     }
 
+    /// Imports without a preview only move a workspace forward, an archive that is not newer than
+    /// the local workspace is rejected and has to be resolved through `preview` and `apply`.
     pub async fn import(src_zip: impl AsRef<Path>) -> Result<()> {
-        let path = State::path();
-        let temp_path = Self::mk_temp_dir(&path)?;
-        unzip_to_dir(src_zip, &temp_path).map_err(map_log_err)?;
-
-        let meta: WorkspaceMetadata = {
-            let text = fs::read_to_string(temp_path.join("workspace.json")).map_err(map_log_err)?;
-            serde_json::from_str(&text).map_err(map_log_err)?
-        };
-        let import_entities_vec = fs::read(temp_path.join("entities.bin")).map_err(map_log_err)?;
-        let import_entities =
-            Store::decrypt_binary::<Vec<Entity>>("", import_entities_vec, false).await?;
+        let stage_id = Self::stage(src_zip).await?;
+        if let Err(e) = Self::check_newer(&Self::staged_path(&stage_id)?).await {
+            Self::discard(&stage_id).ok();
+            return Err(e);
+        }
+        Self::apply(&stage_id, &ImportResolution::default()).await
+    }
 
-        if let Some(ws) = import_entities
+    async fn check_newer(temp_path: &Path) -> Result<()> {
+        let archive = validate_import(temp_path).await?;
+        let Some(incoming) = archive
+            .entities
             .iter()
             .find(|e| e.kind == EntityKind::Workspace)
+        else {
+            return Ok(());
+        };
+        if let Ok(ws_arc) = Store::find::<Workspace>(&incoming.id, &incoming.id).await
+            && ws_arc.read().await.metadata.updated_at >= incoming.metadata.updated_at
         {
-            Self::import_workspace(ws).await?;
+            Err((StatusCode::CONFLICT, "workspace-version-conflict"))?
         }
+        Ok(())
+    }
 
-        let Ok(entities_arc) = Store::find::<Entities>(&meta.id, ENTITIES).await else {
-            return Err((StatusCode::NOT_FOUND, "workspace-not-found"))?;
-        };
-        let (snapshot, updated_ids) =
-            upsert_imported_entities(entities_arc, &import_entities).await;
-        Store::upsert(snapshot).await?;
+    pub async fn stage(src_zip: impl AsRef<Path>) -> Result<String> {
+        Self::sweep_staged();
+        let temp_path = Self::mk_temp_dir(&State::path())?;
+        let result = async {
+            unzip_to_dir(src_zip, &temp_path)?;
+            validate_import(&temp_path).await
+        }
+        .await;
+
+        match result {
+            Ok(_) => Ok(temp_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()),
+            Err(e) => {
+                fs::remove_dir_all(&temp_path).ok();
+                Err(e)
+            }
+        }
+    }
 
-        apply_import_fs_changes(&path, &temp_path, &meta.id, updated_ids);
+    pub async fn preview(stage_id: impl AsRef<str>) -> Result<ImportPreview> {
+        let temp_path = Self::staged_path(stage_id.as_ref())?;
+        let archive = validate_import(&temp_path).await?;
+        let local = LocalImportState::load(&archive.meta.id).await;
 
-        fs::remove_dir_all(temp_path).map_err(map_log_err)?;
-        Ok(())
+        Ok(ImportPreview {
+            id: stage_id.as_ref().to_string(),
+            workspace: archive.meta.name.clone(),
+            exists: local.workspace.is_some(),
+            items: diff_import(&archive, &local),
+        })
     }
 
-    fn mk_temp_dir(root: &Path) -> Result<PathBuf> {
-        let temp_path = root.join(format!("temp/{}", safe_nanoid!()));
-        fs::create_dir_all(&temp_path).map_err(map_log_err)?;
+    pub async fn apply(stage_id: impl AsRef<str>, resolution: &ImportResolution) -> Result<()> {
+        let temp_path = Self::staged_path(stage_id.as_ref())?;
+        let result = Self::apply_staged(&temp_path, resolution).await;
+        fs::remove_dir_all(temp_path).ok();
+        result
+    }
+
+    pub fn discard(stage_id: impl AsRef<str>) -> Result<()> {
+        let temp_path = Self::staged_path(stage_id.as_ref())?;
+        fs::remove_dir_all(temp_path).map_err(map_log_err)
+    }
+
+    /// Removes imports staged for a preview but never applied or discarded.
+    fn sweep_staged() {
+        let Ok(dir) = fs::read_dir(State::path().join("temp")) else {
+            return;
+        };
+        for entry in dir.flatten() {
+            let expired = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > STAGE_TTL);
+            if expired && entry.file_type().is_ok_and(|ty| ty.is_dir()) {
+                fs::remove_dir_all(entry.path()).ok();
+            }
+        }
+    }
+
+    fn staged_path(stage_id: &str) -> Result<PathBuf> {
+        let temp_path = State::path().join("temp").join(stage_id);
+        if !is_safe_id(stage_id) || !temp_path.is_dir() {
+            Err((StatusCode::NOT_FOUND, "import-not-found"))?
+        }
         Ok(temp_path)
     }
 
-    async fn import_workspace(entity: &Entity) -> Result<()> {
-        if let Ok(ws_arc) = Store::find::<Workspace>(&entity.id, &entity.id).await {
-            if ws_arc.read().await.metadata.updated_at >= entity.metadata.updated_at {
-                return Err((StatusCode::CONFLICT, "workspace-version-conflict"))?;
+    async fn apply_staged(temp_path: &Path, resolution: &ImportResolution) -> Result<()> {
+        let archive = validate_import(temp_path).await?;
+        let ws_id = archive.meta.id.clone();
+        let local = LocalImportState::load(&ws_id).await;
+        let choices = diff_import(&archive, &local)
+            .into_iter()
+            .map(|item| {
+                let choice = resolution.choice(&item);
+                ((item.kind, item.id), choice)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let created = local.workspace.is_none();
+        if created {
+            let ws = archive
+                .entities
+                .iter()
+                .find(|e| e.kind == EntityKind::Workspace)
+                .ok_or((StatusCode::NOT_FOUND, "workspace-not-found"))?;
+            Self::create_workspace(ws).await?;
+        }
+
+        let result = Self::merge_staged(temp_path, &archive, &choices, created).await;
+        if result.is_err() && created {
+            Store::remove_workspace(&ws_id).await.ok();
+        }
+        result
+    }
+
+    async fn merge_staged(
+        temp_path: &Path,
+        archive: &ImportArchive,
+        choices: &HashMap<(String, String), ImportChoice>,
+        created: bool,
+    ) -> Result<()> {
+        let root = State::path();
+        let ws_id = archive.meta.id.as_str();
+        let choice_of = |kind: &str, id: &str| {
+            choices
+                .get(&(kind.to_string(), id.to_string()))
+                .copied()
+                .unwrap_or(ImportChoice::KeepLocal)
+        };
+
+        let entities_arc = Store::find::<Entities>(ws_id, ENTITIES).await?;
+        let mut entities_guard = entities_arc.write().await;
+        let mut snapshot = entities_guard.clone();
+        let mut moved_ids = vec![];
+        let mut workspace_choice = ImportChoice::TakeIncoming;
+
+        for entity in archive.entities.iter() {
+            let choice = choice_of(entity.kind.as_str(), &entity.id);
+            if entity.kind == EntityKind::Workspace {
+                workspace_choice = match choice {
+                    _ if created => ImportChoice::TakeIncoming,
+                    ImportChoice::KeepBoth => Err((StatusCode::BAD_REQUEST, "bad-request"))?,
+                    choice => choice,
+                };
+                if workspace_choice == ImportChoice::TakeIncoming {
+                    snapshot.insert(entity.id.clone(), entity.clone());
+                }
+                continue;
             }
-        } else {
-            let entities = Entities::new_with_id(&entity.id);
-            Store::upsert(entities).await?;
+            match choice {
+                ImportChoice::KeepLocal => {}
+                ImportChoice::TakeIncoming => {
+                    snapshot.insert(entity.id.clone(), entity.clone());
+                    moved_ids.push(entity.id.clone());
+                }
+                ImportChoice::KeepBoth => {
+                    let copy = copy_staged_entity(temp_path, ws_id, entity).await?;
+                    moved_ids.push(copy.id.clone());
+                    snapshot.insert(copy.id.clone(), copy);
+                }
+            }
+        }
 
-            let students = Students::new(&entity.id);
-            Store::upsert(students).await?;
+        let mut records = Vec::with_capacity(moved_ids.len());
+        for id in moved_ids.iter() {
+            let data = fs::read(temp_path.join(format!("entities/{id}.bin"))).map_err(map_log_err)?;
+            let entity = snapshot.get(id);
+            records.push(StorageRecord {
+                workspace: ws_id.to_string(),
+                id: id.clone(),
+                kind: entity.map(|e| e.kind),
+                node: entity.map(|e| e.node.clone()).unwrap_or_default(),
+                data,
+            });
+        }
+        let backup = ImportBackup::capture(ws_id, &entities_guard, &moved_ids).await?;
+        let applied = apply_import_assets(&root, temp_path, ws_id, &moved_ids).map_err(map_log_err)?;
+        let result = async {
+            Store::save_raw(records).await?;
+            *entities_guard = snapshot.clone();
+            drop(entities_guard);
 
-            TaskRepository::init(&entity.id).await?;
+            for id in moved_ids.iter() {
+                Store::evict(ws_id, id).await;
+            }
+            Store::upsert(snapshot).await?;
+            Self::merge_members(archive, choices, created, workspace_choice).await
+        }
+        .await;
+        if result.is_err() {
+            rollback_import_assets(applied);
+            backup.restore().await;
+        }
+        result
+    }
+
+    /// Merges the users and students of the archive into the workspace.
+    async fn merge_members(
+        archive: &ImportArchive,
+        choices: &HashMap<(String, String), ImportChoice>,
+        created: bool,
+        workspace_choice: ImportChoice,
+    ) -> Result<()> {
+        let ws_id = archive.meta.id.as_str();
+        let choice_of = |kind: &str, id: &str| {
+            choices
+                .get(&(kind.to_string(), id.to_string()))
+                .copied()
+                .unwrap_or(ImportChoice::KeepLocal)
+        };
 
-            let metadata = WorkspaceMetadata {
-                id: entity.id.clone(),
-                name: entity.name.clone(),
-                version: entity.metadata.updated_at,
+        if let Some(incoming) = &archive.workspace {
+            let local_arc = if created {
+                None
+            } else {
+                Some(Store::find::<Workspace>(ws_id, ws_id).await?)
             };
-            let encrypted = Store::encrypt_binary(&entity.id, metadata, false).await?;
-            let path = State::path().join(format!("workspaces/{id}/workspace.bin", id = entity.id));
-            if let Some(parent) = path.parent() {
-                fs::create_dir_all(parent).map_err(map_log_err)?;
+            let local = match &local_arc {
+                Some(arc) => Some(arc.read().await.clone()),
+                None => None,
+            };
+
+            let mut merged = match &local {
+                Some(local) if workspace_choice == ImportChoice::KeepLocal => local.clone(),
+                _ => incoming.clone(),
+            };
+            let mut users = local.map(|l| l.users).unwrap_or_default();
+            for user in incoming.users.values() {
+                match choice_of(IMPORT_USERS, &user.id) {
+                    ImportChoice::KeepLocal => {}
+                    ImportChoice::TakeIncoming => {
+                        users.insert(user.id.clone(), user.clone());
+                    }
+                    ImportChoice::KeepBoth => Err((StatusCode::BAD_REQUEST, "bad-request"))?,
+                }
             }
-            tokio::fs::write(&path, encrypted)
-                .await
-                .map_err(map_log_err)?;
+            merged.users = users;
+
+            if let Some(arc) = local_arc {
+                *arc.write().await = merged.clone();
+            }
+            Store::upsert(merged).await?;
         }
+
+        if let Some(incoming) = &archive.students {
+            let students_arc = Store::find::<Students>(ws_id, STUDENTS).await?;
+            let snapshot = {
+                let mut students_guard = students_arc.write().await;
+                for student in incoming.values() {
+                    match choice_of(STUDENTS, &student.id) {
+                        ImportChoice::KeepLocal => {}
+                        ImportChoice::TakeIncoming => {
+                            students_guard.insert(student.id.clone(), student.clone());
+                        }
+                        ImportChoice::KeepBoth => {
+                            let copy = Student {
+                                id: safe_nanoid!(),
+                                ..student.clone()
+                            };
+                            students_guard.insert(copy.id.clone(), copy);
+                        }
+                    }
+                }
+                students_guard.sort_unstable_by(|_, a, _, b| a.name.cmp(&b.name));
+                students_guard.clone()
+            };
+            Store::upsert(snapshot).await?;
+        }
+
         Ok(())
     }
+
+    pub async fn export_package(
+        workspace: impl Into<String>,
+        entities: Vec<String>,
+        dest_zip: impl AsRef<Path>,
+    ) -> Result<()> {
+        let ws_id = workspace.into();
+        let path = State::path();
+        let temp_path = Self::mk_temp_dir(&path)?;
+
+        let result = async {
+            let entities = EntityRepository::list_by_filter(
+                &ws_id,
+                Some(EXPORT_PACKAGE.to_vec()),
+                Some(entities),
+                None,
+            )
+            .await?;
+            if entities.is_empty() {
+                Err((StatusCode::NOT_FOUND, "entity-not-found"))?
+            }
+
+            fs::create_dir_all(temp_path.join("entities")).map_err(map_log_err)?;
+            let mut payload = Vec::with_capacity(entities.len() * 2 + 1);
+            for entity in entities.iter() {
+                let data = Store::load_raw(&ws_id, &entity.id)
+                    .await?
+                    .ok_or((StatusCode::NOT_FOUND, "entity-not-found"))?;
+                let value = Store::decrypt_binary::<serde_json::Value>(&ws_id, data, false).await?;
+                let encrypted = Store::encrypt_binary("", value, false).await?;
+                let entity_path = temp_path.join(format!("entities/{id}.bin", id = entity.id));
+                fs::write(&entity_path, encrypted).map_err(map_log_err)?;
+                payload.push((entity_path, format!("entities/{id}.bin", id = entity.id)));
+                payload.push((
+                    path.join(format!("assets/{ws_id}/{id}", id = entity.id)),
+                    format!("assets/{id}", id = entity.id),
+                ));
+            }
+
+            let encrypted = Store::encrypt_binary("", entities, false).await?;
+            let package_path = temp_path.join("package.bin");
+            fs::write(&package_path, encrypted).map_err(map_log_err)?;
+            payload.push((package_path, "package.bin".to_string()));
+
+            zip_many(&payload, dest_zip).map_err(map_log_err)
+        }
+        .await;
+
+        fs::remove_dir_all(temp_path).ok();
+        result
+    }
+
+    pub async fn import_package(
+        workspace: impl Into<String>,
+        username: impl AsRef<str>,
+        src_zip: impl AsRef<Path>,
+        target: &PackageTarget,
+    ) -> Result<Vec<Entity>> {
+        let ws_id = workspace.into();
+        let temp_path = Self::mk_temp_dir(&State::path())?;
+
+        let result = async {
+            unzip_to_dir(src_zip, &temp_path)?;
+            let entities = validate_package(&temp_path).await?;
+
+            let (quiz_tree, survey_tree) = {
+                let ws_arc = Store::find::<Workspace>(&ws_id, &ws_id).await?;
+                let ws_guard = ws_arc.read().await;
+                (ws_guard.quiz_tree.clone(), ws_guard.survey_tree.clone())
+            };
+            let node_for = |kind: EntityKind| -> Result<String> {
+                let (node, tree) = match kind {
+                    EntityKind::Quiz => (&target.quiz_node, &quiz_tree),
+                    _ => (&target.survey_node, &survey_tree),
+                };
+                node.as_ref()
+                    .filter(|node| tree.iter().any(|n| &n.id == *node))
+                    .cloned()
+                    .ok_or((StatusCode::BAD_REQUEST, "package-node-required").into())
+            };
+
+            // Everything is decoded up front, so a broken entry fails before anything is written.
+            let mut staged = Vec::with_capacity(entities.len());
+            for entity in entities.iter() {
+                let node = node_for(entity.kind)?;
+                let data = fs::read(temp_path.join(format!("entities/{id}.bin", id = entity.id)))
+                    .map_err(|_| invalid_archive())?;
+                let item = match entity.kind {
+                    EntityKind::Quiz => PackageItem::Quiz(
+                        Store::decrypt_binary::<Quiz>("", data, false)
+                            .await
+                            .map_err(|_| invalid_archive())?,
+                    ),
+                    _ => PackageItem::Survey(
+                        Store::decrypt_binary::<Survey>("", data, false)
+                            .await
+                            .map_err(|_| invalid_archive())?,
+                    ),
+                };
+                let mut images = Vec::new();
+                let assets = temp_path.join(format!("assets/{id}", id = entity.id));
+                if let Ok(files) = fs::read_dir(&assets) {
+                    for file in files.flatten() {
+                        let file_path = file.path();
+                        let Some(image_id) = file_path
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().to_string())
+                        else {
+                            continue;
+                        };
+                        images.push((image_id, file_path));
+                    }
+                }
+                staged.push((node, item, images));
+            }
+
+            let mut imported = Vec::with_capacity(staged.len());
+            let applied = async {
+                for (node, item, images) in staged {
+                    let id = safe_nanoid!();
+                    match item {
+                        PackageItem::Quiz(mut quiz) => {
+                            quiz.id = id.clone();
+                            quiz.workspace = ws_id.clone();
+                            quiz.node = node;
+                            quiz.metadata = Metadata::new(username.as_ref());
+                            imported.push(quiz.to_entity());
+                            EntityRepository::upsert(&ws_id, quiz.to_entity()).await?;
+                            Store::upsert(quiz).await?;
+                        }
+                        PackageItem::Survey(mut survey) => {
+                            survey.id = id.clone();
+                            survey.workspace = ws_id.clone();
+                            survey.node = node;
+                            survey.metadata = Metadata::new(username.as_ref());
+                            imported.push(survey.to_entity());
+                            EntityRepository::upsert(&ws_id, survey.to_entity()).await?;
+                            Store::upsert(survey).await?;
+                        }
+                    }
+                    for (image_id, file_path) in images {
+                        let data = fs::read(&file_path).map_err(map_log_err)?;
+                        ImageService::convert_bytes_and_save(data, &ws_id, &id, image_id).await?;
+                    }
+                }
+                Ok::<_, Error>(())
+            }
+            .await;
+
+            if let Err(e) = applied {
+                let ids = imported.into_iter().map(|e| e.id).collect::<Vec<_>>();
+                EntityRepository::batch_remove(&ws_id, Some(ids.clone()), None)
+                    .await
+                    .ok();
+                ImageService::remove_entities(&ws_id, ids).await.ok();
+                return Err(e);
+            }
+            Ok(imported)
+        }
+        .await;
+
+        fs::remove_dir_all(temp_path).ok();
+        result
+    }
+
+    pub fn mk_temp_file(extension: impl AsRef<str>) -> Result<PathBuf> {
+        let temp_path = State::path().join("temp");
+        fs::create_dir_all(&temp_path).map_err(map_log_err)?;
+        Ok(temp_path.join(format!("{}.{}", safe_nanoid!(), extension.as_ref())))
+    }
+
+    fn mk_temp_dir(root: &Path) -> Result<PathBuf> {
+        let temp_path = root.join(format!("temp/{}", safe_nanoid!()));
+        fs::create_dir_all(&temp_path).map_err(map_log_err)?;
+        Ok(temp_path)
+    }
+
+    async fn create_workspace(entity: &Entity) -> Result<()> {
+        let entities = Entities::new_with_id(&entity.id);
+        Store::upsert(entities).await?;
+
+        let students = Students::new(&entity.id);
+        Store::upsert(students).await?;
+
+        TaskRepository::init(&entity.id).await?;
+
+        let metadata = WorkspaceMetadata {
+            id: entity.id.clone(),
+            name: entity.name.clone(),
+            version: entity.metadata.updated_at,
+        };
+        let encrypted = Store::encrypt_binary(&entity.id, metadata, false).await?;
+        Store::save_raw(vec![StorageRecord {
+            workspace: entity.id.clone(),
+            id: WORKSPACE.to_string(),
+            kind: None,
+            node: String::new(),
+            data: encrypted,
+        }])
+        .await
+    }
 }
 
 pub fn move_dir_replace(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()> {
@@ -252,59 +709,459 @@ pub fn move_file(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()>
     }
 }
 
-fn build_entities_payload<P: AsRef<Path>, I: IntoIterator<Item = String>>(
-    root: P,
+async fn stage_entities_payload<I: IntoIterator<Item = String>>(
+    temp: &Path,
     ws_id: &str,
     ids: I,
-) -> Vec<(PathBuf, String)> {
-    let root = root.as_ref();
-    ids.into_iter()
-        .map(|id| {
-            (
-                root.join(format!("workspaces/{ws_id}/{id}.bin")),
-                format!("entities/{id}.bin"),
-            )
-        })
-        .collect()
+) -> Result<Vec<(PathBuf, String)>> {
+    let dir = temp.join("entities");
+    fs::create_dir_all(&dir).map_err(map_log_err)?;
+    let mut payload = Vec::new();
+    for id in ids {
+        let Some(data) = Store::load_raw(ws_id, &id).await? else {
+            continue;
+        };
+        let path = dir.join(format!("{id}.bin"));
+        fs::write(&path, data).map_err(map_log_err)?;
+        payload.push((path, format!("entities/{id}.bin")));
+    }
+    Ok(payload)
 }
 
-fn apply_import_fs_changes(root: &Path, temp: &Path, ws_id: &str, updated_ids: Vec<String>) {
-    for id in updated_ids {
-        let src_path = temp.join(format!("entities/{id}.bin"));
-        if src_path.exists() {
-            let dest_path = root.join(format!("workspaces/{ws_id}/{id}.bin"));
-            _ = move_file(src_path, dest_path);
+fn apply_import_assets(
+    root: &Path,
+    temp: &Path,
+    ws_id: &str,
+    updated_ids: &[String],
+) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let backup = temp.join("backup");
+    let mut applied = Vec::<(PathBuf, PathBuf)>::new();
+
+    let mut apply = || -> io::Result<()> {
+        for id in updated_ids {
+            let src = temp.join(format!("assets/{id}"));
+            let dest = root.join(format!("assets/{ws_id}/{id}"));
+            let saved = backup.join(format!("assets/{id}"));
+            if !src.exists() {
+                continue;
+            }
+            if dest.exists() {
+                move_path(&dest, &saved)?;
+            }
+            applied.push((dest.clone(), saved));
+            move_path(&src, &dest)?;
         }
+        Ok(())
+    };
 
-        let src_dir = temp.join(format!("assets/{id}"));
-        let dest_dir = root.join(format!("assets/{ws_id}/{id}"));
-        _ = move_dir_replace(src_dir, dest_dir);
+    match apply() {
+        Ok(_) => Ok(applied),
+        Err(e) => {
+            rollback_import_assets(applied);
+            Err(e)
+        }
     }
 }
 
-async fn upsert_imported_entities(
-    entities_arc: Arc<RwLock<Entities>>,
-    import_entities: &Vec<Entity>,
-) -> (Entities, Vec<String>) {
-    let mut updated = vec![];
-    let snapshot = {
-        let mut guard = entities_arc.write().await;
-        for entity in import_entities.iter() {
-            if let Some(e) = guard.get_mut(&entity.id) {
-                if entity.metadata.updated_at <= e.metadata.updated_at {
-                    continue;
+fn rollback_import_assets(applied: Vec<(PathBuf, PathBuf)>) {
+    for (dest, saved) in applied.into_iter().rev() {
+        remove_path(&dest).ok();
+        if saved.exists() {
+            move_path(&saved, &dest).ok();
+        }
+    }
+}
+
+fn move_path(src: &Path, dst: &Path) -> io::Result<()> {
+    if src.is_dir() {
+        if let Some(parent) = dst.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        move_dir_replace(src, dst)
+    } else {
+        move_file(src, dst)
+    }
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+enum PackageItem {
+    Quiz(Quiz),
+    Survey(Survey),
+}
+
+struct ImportArchive {
+    meta: WorkspaceMetadata,
+    entities: Vec<Entity>,
+    workspace: Option<Workspace>,
+    students: Option<Students>,
+}
+
+/// Records an import overwrites, kept as they were so a failed merge can put them back.
+struct ImportBackup {
+    workspace: String,
+    records: Vec<(String, Option<StorageRecord>)>,
+}
+
+impl ImportBackup {
+    async fn capture(ws_id: &str, entities: &Entities, moved_ids: &[String]) -> Result<Self> {
+        let mut records = Vec::with_capacity(moved_ids.len() + 3);
+        let fixed = [
+            (ENTITIES, EntityKind::Entities),
+            (STUDENTS, EntityKind::Students),
+            (ws_id, EntityKind::Workspace),
+        ];
+        let targets = fixed
+            .into_iter()
+            .map(|(id, kind)| (id.to_string(), Some(kind), String::new()))
+            .chain(moved_ids.iter().map(|id| {
+                let entity = entities.get(id);
+                (
+                    id.clone(),
+                    entity.map(|e| e.kind),
+                    entity.map(|e| e.node.clone()).unwrap_or_default(),
+                )
+            }));
+        for (id, kind, node) in targets {
+            let record = Store::load_raw(ws_id, &id)
+                .await?
+                .map(|data| StorageRecord {
+                    workspace: ws_id.to_string(),
+                    id: id.clone(),
+                    kind,
+                    node,
+                    data,
+                });
+            records.push((id, record));
+        }
+        Ok(Self {
+            workspace: ws_id.to_string(),
+            records,
+        })
+    }
+
+    async fn restore(self) {
+        let mut saved = Vec::with_capacity(self.records.len());
+        for (id, record) in self.records {
+            match record {
+                Some(record) => saved.push(record),
+                None => {
+                    Store::delete(&self.workspace, &id).await.ok();
                 }
-                e.metadata = entity.metadata.clone();
-                e.kind = entity.kind.clone();
-                e.name = entity.name.clone();
-            } else {
-                guard.insert(entity.id.clone(), entity.clone());
             }
-            updated.push(entity.id.clone());
         }
-        guard.clone()
+        let ids = saved.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+        Store::save_raw(saved).await.ok();
+        for id in ids {
+            Store::evict(&self.workspace, id).await;
+        }
+    }
+}
+
+struct LocalImportState {
+    workspace: Option<Workspace>,
+    entities: Option<Entities>,
+    students: Option<Students>,
+}
+
+impl LocalImportState {
+    async fn load(ws_id: &str) -> Self {
+        let workspace = match Store::find::<Workspace>(ws_id, ws_id).await {
+            Ok(arc) => Some(arc.read().await.clone()),
+            Err(_) => None,
+        };
+        let entities = match Store::find::<Entities>(ws_id, ENTITIES).await {
+            Ok(arc) => Some(arc.read().await.clone()),
+            Err(_) => None,
+        };
+        let students = match Store::find::<Students>(ws_id, STUDENTS).await {
+            Ok(arc) => Some(arc.read().await.clone()),
+            Err(_) => None,
+        };
+        Self {
+            workspace,
+            entities,
+            students,
+        }
+    }
+}
+
+fn diff_import(archive: &ImportArchive, local: &LocalImportState) -> Vec<ImportDiffItem> {
+    let incoming_newer = match (&archive.workspace, &local.workspace) {
+        (Some(incoming), Some(local)) => incoming.metadata.updated_at > local.metadata.updated_at,
+        _ => true,
     };
-    (snapshot, updated)
+    let compare = |local: Option<bool>| match local {
+        None => ImportDiffStatus::Added,
+        Some(true) => ImportDiffStatus::Unchanged,
+        Some(false) if incoming_newer => ImportDiffStatus::Updated,
+        Some(false) => ImportDiffStatus::Older,
+    };
+
+    let mut items = Vec::new();
+    for entity in archive.entities.iter() {
+        let local = local.entities.as_ref().and_then(|e| e.get(&entity.id));
+        let status = match local {
+            None => ImportDiffStatus::Added,
+            Some(l) if entity.metadata.updated_at > l.metadata.updated_at => ImportDiffStatus::Updated,
+            Some(l) if entity.metadata.updated_at < l.metadata.updated_at => ImportDiffStatus::Older,
+            Some(_) => ImportDiffStatus::Unchanged,
+        };
+        items.push(ImportDiffItem {
+            id: entity.id.clone(),
+            name: entity.name.clone(),
+            kind: entity.kind.as_str().to_string(),
+            status,
+        });
+    }
+
+    if let Some(incoming) = &archive.workspace {
+        for user in incoming.users.values() {
+            let local = local
+                .workspace
+                .as_ref()
+                .and_then(|ws| ws.users.get(&user.id))
+                .map(|l| l == user);
+            items.push(ImportDiffItem {
+                id: user.id.clone(),
+                name: user.username.clone(),
+                kind: IMPORT_USERS.to_string(),
+                status: compare(local),
+            });
+        }
+    }
+
+    if let Some(incoming) = &archive.students {
+        for student in incoming.values() {
+            let local = local
+                .students
+                .as_ref()
+                .and_then(|s| s.get(&student.id))
+                .map(|l| l == student);
+            items.push(ImportDiffItem {
+                id: student.id.clone(),
+                name: match &student.rank {
+                    Some(rank) => format!("{rank} {name}", name = student.name),
+                    None => student.name.clone(),
+                },
+                kind: STUDENTS.to_string(),
+                status: compare(local),
+            });
+        }
+    }
+
+    items
+}
+
+async fn copy_staged_entity(temp: &Path, ws_id: &str, entity: &Entity) -> Result<Entity> {
+    let id = safe_nanoid!();
+    let name = format!(
+        "{name} ({date})",
+        name = entity.name,
+        date = entity.metadata.updated_at()
+    );
+
+    let data = fs::read(temp.join(format!("entities/{id}.bin", id = entity.id))).map_err(map_log_err)?;
+    let mut value = Store::decrypt_binary::<serde_json::Value>(ws_id, data, false).await?;
+    if let Some(object) = value.as_object_mut() {
+        object.insert("id".to_string(), serde_json::Value::String(id.clone()));
+        if object.contains_key("name") {
+            object.insert("name".to_string(), serde_json::Value::String(name.clone()));
+        }
+    }
+    let encrypted = Store::encrypt_binary(ws_id, value, false).await?;
+    fs::write(temp.join(format!("entities/{id}.bin")), encrypted).map_err(map_log_err)?;
+
+    let assets = temp.join(format!("assets/{id}", id = entity.id));
+    if assets.is_dir() {
+        copy_dir_recursive(&assets, &temp.join(format!("assets/{id}"))).map_err(map_log_err)?;
+    }
+
+    Ok(Entity {
+        id,
+        name,
+        ..entity.clone()
+    })
+}
+
+async fn validate_package(temp: &Path) -> Result<Vec<Entity>> {
+    let data = fs::read(temp.join("package.bin")).map_err(|_| invalid_archive())?;
+    let entities = Store::decrypt_binary::<Vec<Entity>>("", data, false)
+        .await
+        .map_err(|_| invalid_archive())?;
+
+    let mut ids = HashSet::with_capacity(entities.len());
+    for entity in entities.iter() {
+        if !EXPORT_PACKAGE.contains(&entity.kind)
+            || !is_safe_id(&entity.id)
+            || !ids.insert(entity.id.as_str())
+            || !temp.join(format!("entities/{id}.bin", id = entity.id)).is_file()
+        {
+            Err(invalid_archive())?
+        }
+    }
+    if entities.is_empty() {
+        Err(invalid_archive())?
+    }
+    Ok(entities)
+}
+
+async fn validate_import(temp: &Path) -> Result<ImportArchive> {
+    let meta: WorkspaceMetadata = {
+        let text = fs::read_to_string(temp.join("workspace.json")).map_err(|_| invalid_archive())?;
+        serde_json::from_str(&text).map_err(|_| invalid_archive())?
+    };
+    if !is_safe_id(&meta.id) {
+        Err(invalid_archive())?
+    }
+
+    let import_entities_vec = fs::read(temp.join("entities.bin")).map_err(|_| invalid_archive())?;
+    let import_entities = Store::decrypt_binary::<Vec<Entity>>("", import_entities_vec, false)
+        .await
+        .map_err(|_| invalid_archive())?;
+
+    let mut listed = HashSet::with_capacity(import_entities.len());
+    for entity in import_entities.iter() {
+        if !is_safe_id(&entity.id) || !listed.insert(entity.id.clone()) {
+            Err(invalid_archive())?
+        }
+        if entity.kind == EntityKind::Workspace && entity.id != meta.id {
+            Err(invalid_archive())?
+        }
+        let data = fs::read(temp.join(format!("entities/{id}.bin", id = entity.id)))
+            .map_err(|_| invalid_archive())?;
+        validate_entity_file(&meta.id, entity, data).await?;
+    }
+
+    if let Ok(dir) = fs::read_dir(temp.join("entities")) {
+        for entry in dir {
+            let name = entry.map_err(map_log_err)?.file_name();
+            let id = name.to_string_lossy();
+            if !listed.contains(id.trim_end_matches(".bin")) {
+                Err(invalid_archive())?
+            }
+        }
+    }
+
+    let workspace = match import_entities.iter().any(|e| e.kind == EntityKind::Workspace) {
+        true => {
+            let data = fs::read(temp.join(format!("entities/{id}.bin", id = meta.id)))
+                .map_err(|_| invalid_archive())?;
+            let workspace = Store::decrypt_binary::<Workspace>(&meta.id, data, false)
+                .await
+                .map_err(|_| invalid_archive())?;
+            Some(workspace)
+        }
+        false => None,
+    };
+
+    let students = match fs::read(temp.join("students.bin")) {
+        Ok(data) => {
+            let students = Store::decrypt_binary::<Students>(&meta.id, data, false)
+                .await
+                .map_err(|_| invalid_archive())?;
+            if students.get_ws() != meta.id
+                || students.iter().any(|(id, s)| !is_safe_id(id) || *id != s.id)
+            {
+                Err(invalid_archive())?
+            }
+            Some(students)
+        }
+        Err(_) => None,
+    };
+
+    let images = walkdir::WalkDir::new(temp.join("assets"))
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect::<Vec<_>>();
+    tokio::task::spawn_blocking(move || {
+        images
+            .iter()
+            .all(|path| ::image::open(path).is_ok())
+            .then_some(())
+            .ok_or_else(invalid_archive)
+    })
+    .await
+    .map_err(map_log_err)??;
+
+    Ok(ImportArchive {
+        meta,
+        entities: import_entities,
+        workspace,
+        students,
+    })
+}
+
+async fn validate_entity_file(ws_id: &str, entity: &Entity, data: Vec<u8>) -> Result<()> {
+    let (id, ws) = match entity.kind {
+        EntityKind::Workspace => decrypt_entity_ids::<Workspace>(ws_id, data).await?,
+        EntityKind::Quiz => decrypt_entity_ids::<Quiz>(ws_id, data).await?,
+        EntityKind::Survey => decrypt_entity_ids::<Survey>(ws_id, data).await?,
+        EntityKind::QuizRecord => decrypt_entity_ids::<QuizRecord>(ws_id, data).await?,
+        EntityKind::SurveyRecord => decrypt_entity_ids::<SurveyRecord>(ws_id, data).await?,
+        EntityKind::Json => {
+            Store::decrypt_binary::<serde_json::Value>(ws_id, data, false)
+                .await
+                .map_err(|_| invalid_archive())?;
+            return Ok(());
+        }
+        _ => Err(invalid_archive())?,
+    };
+    if id != entity.id || ws != ws_id {
+        Err(invalid_archive())?
+    }
+    Ok(())
+}
+
+async fn decrypt_entity_ids<T: Cachable + for<'de> Deserialize<'de> + 'static>(
+    ws_id: &str,
+    data: Vec<u8>,
+) -> Result<(String, String)> {
+    let value = Store::decrypt_binary::<T>(ws_id, data, false)
+        .await
+        .map_err(|_| invalid_archive())?;
+    Ok((value.get_id(), value.get_ws()))
+}
+
+fn is_expected_entry(name: &str) -> bool {
+    let is_dir = name.ends_with('/');
+    let parts = name.trim_end_matches('/').split('/').collect::<Vec<_>>();
+    match parts.as_slice() {
+        ["workspace.json"] | ["entities.bin"] | ["students.bin"] | ["package.bin"] => !is_dir,
+        ["entities"] | ["assets"] => is_dir,
+        ["entities", file] => !is_dir && file.strip_suffix(".bin").is_some_and(is_safe_id),
+        ["assets", id] => is_dir && is_safe_id(id),
+        ["assets", id, file] => {
+            !is_dir && is_safe_id(id) && file.strip_suffix(".webp").is_some_and(is_safe_id)
+        }
+        _ => false,
+    }
+}
+
+fn is_safe_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn invalid_archive() -> Error {
+    (StatusCode::BAD_REQUEST, "import-file-corrupted").into()
+}
+
+fn oversized_archive() -> Error {
+    (StatusCode::PAYLOAD_TOO_LARGE, "import-archive-too-large").into()
 }
 
 fn zip_many<S, D, Z>(pairs: &[(S, D)], zip_path: Z) -> io::Result<()>
@@ -371,29 +1228,58 @@ where
     Ok(())
 }
 
-pub fn unzip_to_dir(zip_path: impl AsRef<Path>, dest_dir: impl AsRef<Path>) -> io::Result<()> {
-    let zip_path = zip_path.as_ref();
-    let dest_dir = dest_dir.as_ref();
-    fs::create_dir_all(dest_dir)?;
+struct ArchiveLimits {
+    entries: usize,
+    archive_size: u64,
+    entry_size: u64,
+    compression_ratio: u64,
+    ratio_threshold: u64,
+}
 
-    let file = File::open(zip_path)?;
-    let mut archive =
-        ZipArchive::new(file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+pub fn unzip_to_dir(zip_path: impl AsRef<Path>, dest_dir: impl AsRef<Path>) -> Result<()> {
+    unzip_with_limits(zip_path.as_ref(), dest_dir.as_ref(), &ARCHIVE_LIMITS)
+}
 
+fn unzip_with_limits(zip_path: &Path, dest_dir: &Path, limits: &ArchiveLimits) -> Result<()> {
+    fs::create_dir_all(dest_dir).map_err(map_log_err)?;
+
+    let file = File::open(zip_path).map_err(map_log_err)?;
+    let mut archive = ZipArchive::new(file).map_err(|_| invalid_archive())?;
+    if archive.len() > limits.entries {
+        Err(oversized_archive())?
+    }
+
+    let mut total_size = 0u64;
     for i in 0..archive.len() {
-        let mut entry = archive
-            .by_index(i)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-        let out_path = sanitize_extract_path(dest_dir, entry.name())?;
+        let mut entry = archive.by_index(i).map_err(|_| invalid_archive())?;
+        if entry.is_symlink() || !is_expected_entry(entry.name()) {
+            Err(invalid_archive())?
+        }
+        let out_path = sanitize_extract_path(dest_dir, entry.name()).map_err(map_log_err)?;
 
-        if entry.name().ends_with('/') {
-            fs::create_dir_all(&out_path)?;
-        } else {
-            if let Some(parent) = out_path.parent() {
-                fs::create_dir_all(parent)?;
-            }
-            let mut outfile = File::create(&out_path)?;
-            io::copy(&mut entry, &mut outfile)?;
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path).map_err(map_log_err)?;
+            continue;
+        }
+
+        let size = entry.size();
+        let ratio = size / entry.compressed_size().max(1);
+        total_size += size;
+        if size > limits.entry_size
+            || total_size > limits.archive_size
+            || (size > limits.ratio_threshold && ratio > limits.compression_ratio)
+        {
+            Err(oversized_archive())?
+        }
+
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent).map_err(map_log_err)?;
+        }
+        let mut outfile = File::create(&out_path).map_err(map_log_err)?;
+        let written = io::copy(&mut (&mut entry).take(size + 1), &mut outfile)
+            .map_err(|_| invalid_archive())?;
+        if written != size {
+            Err(invalid_archive())?
         }
     }
     Ok(())
@@ -466,3 +1352,199 @@ fn sanitize_extract_path(base: &Path, name: &str) -> io::Result<PathBuf> {
 fn to_io<E: std::fmt::Display>(e: E) -> io::Error {
     io::Error::new(io::ErrorKind::Other, format!("{e}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::storage::tests::temp_root;
+    use ::std::io::Cursor;
+    use ::zip::write::SimpleFileOptions;
+
+    const SMALL_LIMITS: ArchiveLimits = ArchiveLimits {
+        entries: 4,
+        archive_size: 64,
+        entry_size: 32,
+        compression_ratio: 2,
+        ratio_threshold: 16,
+    };
+
+    fn archive(name: &str, method: CompressionMethod, entries: &[(&str, &[u8])]) -> PathBuf {
+        let opts = SimpleFileOptions::default().compression_method(method);
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (entry, data) in entries {
+            if entry.ends_with('/') {
+                zip.add_directory(*entry, opts).unwrap();
+            } else {
+                zip.start_file(*entry, opts).unwrap();
+                zip.write_all(data).unwrap();
+            }
+        }
+        let path = temp_root(name).join("archive.zip");
+        fs::write(&path, zip.finish().unwrap().into_inner()).unwrap();
+        path
+    }
+
+    fn unzip_error(zip_path: &Path, limits: &ArchiveLimits) -> Option<String> {
+        let dest = zip_path.with_file_name("out");
+        unzip_with_limits(zip_path, &dest, limits)
+            .err()
+            .map(|e| e.to_string())
+    }
+
+    #[test]
+    fn unzip_extracts_expected_entries() {
+        let zip_path = archive(
+            "unzip-ok",
+            CompressionMethod::Stored,
+            &[
+                ("workspace.json", b"{}"),
+                ("entities/", b""),
+                ("entities/quiz-1.bin", b"quiz"),
+            ],
+        );
+        let dest = zip_path.with_file_name("out");
+        assert!(unzip_with_limits(&zip_path, &dest, &SMALL_LIMITS).is_ok());
+        assert_eq!(fs::read(dest.join("entities/quiz-1.bin")).unwrap(), b"quiz");
+    }
+
+    #[test]
+    fn unzip_rejects_too_many_entries() {
+        let zip_path = archive(
+            "unzip-entries",
+            CompressionMethod::Stored,
+            &[
+                ("workspace.json", b"{}"),
+                ("entities.bin", b""),
+                ("students.bin", b""),
+                ("package.bin", b""),
+                ("entities/", b""),
+            ],
+        );
+        assert_eq!(
+            unzip_error(&zip_path, &SMALL_LIMITS).as_deref(),
+            Some("import-archive-too-large")
+        );
+    }
+
+    #[test]
+    fn unzip_rejects_oversized_entry() {
+        let zip_path = archive(
+            "unzip-entry-size",
+            CompressionMethod::Stored,
+            &[("entities.bin", &[1; 33])],
+        );
+        assert_eq!(
+            unzip_error(&zip_path, &SMALL_LIMITS).as_deref(),
+            Some("import-archive-too-large")
+        );
+    }
+
+    #[test]
+    fn unzip_rejects_oversized_archive() {
+        let zip_path = archive(
+            "unzip-total-size",
+            CompressionMethod::Stored,
+            &[
+                ("entities.bin", &[1; 30]),
+                ("students.bin", &[1; 30]),
+                ("package.bin", &[1; 30]),
+            ],
+        );
+        assert_eq!(
+            unzip_error(&zip_path, &SMALL_LIMITS).as_deref(),
+            Some("import-archive-too-large")
+        );
+    }
+
+    #[test]
+    fn unzip_rejects_high_compression_ratio() {
+        let zip_path = archive(
+            "unzip-ratio",
+            CompressionMethod::Deflated,
+            &[("entities.bin", &[0; 32])],
+        );
+        assert_eq!(
+            unzip_error(&zip_path, &SMALL_LIMITS).as_deref(),
+            Some("import-archive-too-large")
+        );
+
+        let zip_path = archive(
+            "unzip-ratio-stored",
+            CompressionMethod::Stored,
+            &[("entities.bin", &[0; 32])],
+        );
+        assert_eq!(unzip_error(&zip_path, &SMALL_LIMITS), None);
+    }
+
+    #[test]
+    fn unzip_rejects_path_traversal() {
+        for name in [
+            "../workspace.json",
+            "/workspace.json",
+            "./workspace.json",
+            "entities/../../quiz-1.bin",
+            "assets/img-1/../../../img-1.webp",
+            "C:\\workspace.json",
+        ] {
+            let zip_path = archive(
+                "unzip-traversal",
+                CompressionMethod::Stored,
+                &[(name, b"{}")],
+            );
+            assert_eq!(
+                unzip_error(&zip_path, &ARCHIVE_LIMITS).as_deref(),
+                Some("import-file-corrupted"),
+                "{name}"
+            );
+            let root = zip_path.parent().unwrap();
+            assert!(!root.join("workspace.json").exists(), "{name}");
+            assert!(!root.join("quiz-1.bin").exists(), "{name}");
+        }
+    }
+
+    #[test]
+    fn unzip_rejects_symlinks() {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.add_symlink("entities.bin", "../../secret", SimpleFileOptions::default())
+            .unwrap();
+        let zip_path = temp_root("unzip-symlink").join("archive.zip");
+        fs::write(&zip_path, zip.finish().unwrap().into_inner()).unwrap();
+        assert_eq!(
+            unzip_error(&zip_path, &ARCHIVE_LIMITS).as_deref(),
+            Some("import-file-corrupted")
+        );
+    }
+
+    #[test]
+    fn sanitize_extract_path_stays_inside_base() {
+        let base = Path::new("/tmp/import");
+        for name in ["../../etc/passwd", "/etc/passwd", "entities/../../../x.bin"] {
+            let path = sanitize_extract_path(base, name).unwrap();
+            assert!(path.starts_with(base), "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn validate_import_rejects_unsafe_workspace_id() {
+        let temp = temp_root("validate-import");
+        assert_eq!(
+            validate_import(&temp)
+                .await
+                .err()
+                .map(|e| e.to_string())
+                .as_deref(),
+            Some("import-file-corrupted")
+        );
+
+        let meta = r#"{"id":"../other","name":"Other"}"#;
+        fs::write(temp.join("workspace.json"), meta).unwrap();
+        assert_eq!(
+            validate_import(&temp)
+                .await
+                .err()
+                .map(|e| e.to_string())
+                .as_deref(),
+            Some("import-file-corrupted")
+        );
+    }
+}
@@ -6,7 +6,7 @@ use ::std::{
     collections::HashSet,
     fs::File,
     io::{BufWriter, Write},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 use ::tokio::{fs, task};
@@ -25,19 +25,44 @@ impl ImageService {
         id: impl AsRef<str>,
     ) -> Result<()> {
         let input_path = input_path.as_ref().to_owned();
+        let output_path = Self::output_path(workspace, entity, id).await?;
+
+        task::spawn_blocking(move || {
+            let img = image::open(&input_path).map_err(map_log_err)?;
+            Self::resize_and_convert_to_webp(img, &output_path, MAX_DIM)
+        })
+        .await
+        .map_err(map_log_err)?
+    }
+
+    pub async fn convert_bytes_and_save(
+        data: Vec<u8>,
+        workspace: impl AsRef<str>,
+        entity: impl AsRef<str>,
+        id: impl AsRef<str>,
+    ) -> Result<()> {
+        let output_path = Self::output_path(workspace, entity, id).await?;
+
+        task::spawn_blocking(move || {
+            let img = image::load_from_memory(&data).map_err(map_log_err)?;
+            Self::resize_and_convert_to_webp(img, &output_path, MAX_DIM)
+        })
+        .await
+        .map_err(map_log_err)?
+    }
+
+    async fn output_path(
+        workspace: impl AsRef<str>,
+        entity: impl AsRef<str>,
+        id: impl AsRef<str>,
+    ) -> Result<PathBuf> {
         let dir = State::path().join(format!(
             "assets/{workspace}/{entity}",
             workspace = workspace.as_ref(),
             entity = entity.as_ref()
         ));
         fs::create_dir_all(&dir).await.map_err(map_log_err)?;
-        let output_path = dir.join(format!("{id}.webp", id = id.as_ref()));
-
-        task::spawn_blocking(move || {
-            Self::resize_and_convert_to_webp(&input_path, &output_path, MAX_DIM)
-        })
-        .await
-        .map_err(map_log_err)?
+        Ok(dir.join(format!("{id}.webp", id = id.as_ref())))
     }
 
     pub async fn remove(
@@ -112,7 +137,29 @@ impl ImageService {
             .join("assets")
             .join(destination_workspace)
             .join(destination_entity);
+        Self::copy_dir(src, dst, None)
+    }
 
+    /// Copies only the images named in `ids`, e.g. the questions and answers a snapshot keeps.
+    pub fn copy_selected_images(
+        source_workspace: impl AsRef<Path>,
+        source_entity: impl AsRef<Path>,
+        destination_workspace: impl AsRef<Path>,
+        destination_entity: impl AsRef<Path>,
+        ids: HashSet<String>,
+    ) -> Result<()> {
+        let src = State::path()
+            .join("assets")
+            .join(source_workspace)
+            .join(source_entity);
+        let dst = State::path()
+            .join("assets")
+            .join(destination_workspace)
+            .join(destination_entity);
+        Self::copy_dir(src, dst, Some(ids))
+    }
+
+    fn copy_dir(src: PathBuf, dst: PathBuf, ids: Option<HashSet<String>>) -> Result<()> {
         task::spawn(async move {
             if let Err(e) = fs::create_dir_all(&dst).await {
                 error!("create_dir_all error: {e}");
@@ -139,7 +186,16 @@ impl ImageService {
                 if !ft.is_file() {
                     continue;
                 }
-                paths.push(ent.path());
+                let path = ent.path();
+                if let Some(ids) = ids.as_ref()
+                    && !path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| ids.contains(stem))
+                {
+                    continue;
+                }
+                paths.push(path);
             }
 
             let sem = Arc::new(tokio::sync::Semaphore::new(8));
@@ -175,12 +231,10 @@ impl ImageService {
     }
 
     fn resize_and_convert_to_webp(
-        input_path: impl AsRef<Path>,
+        img: DynamicImage,
         output_path: impl AsRef<Path>,
         max_dim: u32,
     ) -> Result<()> {
-        let img: DynamicImage = image::open(&input_path).map_err(map_log_err)?;
-
         let resized = if img.width() > max_dim || img.height() > max_dim {
             img.resize(max_dim, max_dim, FilterType::Lanczos3)
         } else {
@@ -1,11 +1,19 @@
 mod store;
+mod storage;
 mod image;
 mod exchange;
 mod text_similarity;
+mod answer_sheet;
+mod upload;
+mod task_scheduler;
 
 pub use self::{
     store::*,
+    storage::*,
     image::*,
     exchange::*,
     text_similarity::*,
+    answer_sheet::*,
+    upload::*,
+    task_scheduler::*,
 };
\ No newline at end of file
@@ -0,0 +1,161 @@
+use super::*;
+use ::std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn path(&self, workspace: &str, id: &str) -> PathBuf {
+        self.root.join(format!("{workspace}/{id}.bin"))
+    }
+}
+
+impl StorageBackend for FileStorage {
+    fn load(&self, workspace: &str, id: &str) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.path(workspace, id)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(map_log_err(e)),
+        }
+    }
+
+    fn save(&self, records: Vec<StorageRecord>) -> Result<()> {
+        let mut staged = Vec::with_capacity(records.len());
+        for record in records {
+            let path = self.path(&record.workspace, &record.id);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(map_log_err)?;
+            }
+            let tmp = path.with_extension("tmp");
+            write_synced(&tmp, &record.data).map_err(map_log_err)?;
+            staged.push((tmp, path));
+        }
+
+        for (tmp, path) in staged {
+            if fs::rename(&tmp, &path).is_err() {
+                fs::remove_file(&path).ok();
+                fs::rename(&tmp, &path).map_err(map_log_err)?;
+            }
+            if let Some(parent) = path.parent()
+                && let Ok(dir) = fs::File::open(parent)
+            {
+                dir.sync_all().ok();
+            }
+        }
+        Ok(())
+    }
+
+    fn delete(&self, workspace: &str, id: &str) -> Result<()> {
+        fs::remove_file(self.path(workspace, id)).ok();
+        Ok(())
+    }
+
+    fn remove_workspace(&self, workspace: &str) -> Result<Vec<String>> {
+        let path = self.root.join(workspace);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let ids = list_ids(&path)?;
+        fs::remove_dir_all(path).map_err(map_log_err)?;
+        Ok(ids)
+    }
+
+    fn workspaces(&self) -> Result<Vec<String>> {
+        let mut workspaces = Vec::new();
+        let Ok(dir) = fs::read_dir(&self.root) else {
+            return Ok(workspaces);
+        };
+        for entry in dir.flatten() {
+            if entry.file_type().is_ok_and(|ty| ty.is_dir())
+                && let Ok(name) = entry.file_name().into_string()
+            {
+                workspaces.push(name);
+            }
+        }
+        Ok(workspaces)
+    }
+
+    fn records(&self, workspace: &str) -> Result<Vec<StorageRecord>> {
+        let ids = list_ids(&self.root.join(workspace))?;
+        let mut records = Vec::with_capacity(ids.len());
+        for id in ids {
+            let data = fs::read(self.path(workspace, &id)).map_err(map_log_err)?;
+            records.push(StorageRecord {
+                workspace: workspace.to_string(),
+                id,
+                kind: None,
+                node: String::new(),
+                data,
+            });
+        }
+        Ok(records)
+    }
+
+    fn scan(&self, workspace: &str, group: &str) -> Result<Vec<StorageRecord>> {
+        let path = self.root.join(format!("{workspace}/{group}"));
+        if !path.is_dir() {
+            return Ok(vec![]);
+        }
+        let mut records = Vec::new();
+        for id in list_ids(&path)? {
+            let id = format!("{group}/{id}");
+            let Some(data) = self.load(workspace, &id)? else {
+                continue;
+            };
+            records.push(StorageRecord {
+                workspace: workspace.to_string(),
+                id,
+                kind: None,
+                node: String::new(),
+                data,
+            });
+        }
+        Ok(records)
+    }
+
+    fn remove_group(&self, workspace: &str, group: &str) -> Result<()> {
+        let path = self.root.join(format!("{workspace}/{group}"));
+        if path.is_dir() {
+            fs::remove_dir_all(path).map_err(map_log_err)?;
+        }
+        Ok(())
+    }
+
+    fn exists(&self, workspace: &str, id: &str) -> Result<bool> {
+        Ok(self.path(workspace, id).is_file())
+    }
+}
+
+fn list_ids(path: &Path) -> Result<Vec<String>> {
+    let mut ids = Vec::new();
+    for entry in fs::read_dir(path).map_err(map_log_err)?.flatten() {
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if entry.file_type().is_ok_and(|ty| ty.is_dir()) {
+            ids.extend(
+                list_ids(&entry.path())?
+                    .into_iter()
+                    .map(|id| format!("{name}/{id}")),
+            );
+        } else if let Some(id) = name.strip_suffix(".bin") {
+            ids.push(id.to_string());
+        }
+    }
+    Ok(ids)
+}
+
+fn write_synced(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(data)?;
+    file.sync_all()
+}
@@ -0,0 +1,112 @@
+use super::*;
+use ::std::{
+    collections::{HashMap, HashSet},
+    sync::RwLock,
+};
+
+#[derive(Default)]
+pub struct MemoryStorage {
+    inner: RwLock<HashMap<String, HashMap<String, StorageRecord>>>,
+}
+
+impl StorageBackend for MemoryStorage {
+    fn load(&self, workspace: &str, id: &str) -> Result<Option<Vec<u8>>> {
+        let guard = self.inner.read().map_err(map_log_err)?;
+        Ok(guard
+            .get(workspace)
+            .and_then(|records| records.get(id))
+            .map(|record| record.data.clone()))
+    }
+
+    fn save(&self, records: Vec<StorageRecord>) -> Result<()> {
+        let mut guard = self.inner.write().map_err(map_log_err)?;
+        for record in records {
+            guard
+                .entry(record.workspace.clone())
+                .or_default()
+                .insert(record.id.clone(), record);
+        }
+        Ok(())
+    }
+
+    fn delete(&self, workspace: &str, id: &str) -> Result<()> {
+        let mut guard = self.inner.write().map_err(map_log_err)?;
+        if let Some(records) = guard.get_mut(workspace) {
+            records.remove(id);
+        }
+        Ok(())
+    }
+
+    fn remove_workspace(&self, workspace: &str) -> Result<Vec<String>> {
+        let mut guard = self.inner.write().map_err(map_log_err)?;
+        Ok(guard
+            .remove(workspace)
+            .map(|records| records.into_keys().collect())
+            .unwrap_or_default())
+    }
+
+    fn workspaces(&self) -> Result<Vec<String>> {
+        let guard = self.inner.read().map_err(map_log_err)?;
+        Ok(guard.keys().cloned().collect())
+    }
+
+    fn records(&self, workspace: &str) -> Result<Vec<StorageRecord>> {
+        let guard = self.inner.read().map_err(map_log_err)?;
+        Ok(guard
+            .get(workspace)
+            .map(|records| records.values().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    fn scan(&self, workspace: &str, group: &str) -> Result<Vec<StorageRecord>> {
+        let guard = self.inner.read().map_err(map_log_err)?;
+        let prefix = format!("{group}/");
+        Ok(guard
+            .get(workspace)
+            .map(|records| {
+                records
+                    .values()
+                    .filter(|r| r.id.starts_with(&prefix))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn remove_group(&self, workspace: &str, group: &str) -> Result<()> {
+        let mut guard = self.inner.write().map_err(map_log_err)?;
+        let prefix = format!("{group}/");
+        if let Some(records) = guard.get_mut(workspace) {
+            records.retain(|id, _| !id.starts_with(&prefix));
+        }
+        Ok(())
+    }
+
+    fn query(
+        &self,
+        workspace: &str,
+        kinds: Option<&[EntityKind]>,
+        nodes: Option<&[String]>,
+    ) -> Result<Option<Vec<String>>> {
+        let guard = self.inner.read().map_err(map_log_err)?;
+        let kinds = kinds.map(|k| k.iter().copied().collect::<HashSet<_>>());
+        let nodes = nodes.map(|n| n.iter().collect::<HashSet<_>>());
+        Ok(Some(
+            guard
+                .get(workspace)
+                .map(|records| {
+                    records
+                        .values()
+                        .filter(|r| {
+                            kinds
+                                .as_ref()
+                                .is_none_or(|set| r.kind.is_some_and(|k| set.contains(&k)))
+                                && nodes.as_ref().is_none_or(|set| set.contains(&r.node))
+                        })
+                        .map(|r| r.id.clone())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        ))
+    }
+}
@@ -0,0 +1,163 @@
+mod file;
+mod memory;
+mod sqlite;
+
+pub use self::{file::*, memory::*, sqlite::*};
+use ::shared::{common::*, models::*};
+use ::std::{path::Path, sync::Arc};
+
+#[derive(Debug, Clone)]
+pub struct StorageRecord {
+    pub workspace: String,
+    pub id: String,
+    pub kind: Option<EntityKind>,
+    pub node: String,
+    pub data: Vec<u8>,
+}
+
+pub trait StorageBackend: Send + Sync {
+    fn load(&self, workspace: &str, id: &str) -> Result<Option<Vec<u8>>>;
+    fn save(&self, records: Vec<StorageRecord>) -> Result<()>;
+    fn delete(&self, workspace: &str, id: &str) -> Result<()>;
+    fn remove_workspace(&self, workspace: &str) -> Result<Vec<String>>;
+    fn workspaces(&self) -> Result<Vec<String>>;
+    fn records(&self, workspace: &str) -> Result<Vec<StorageRecord>>;
+    fn scan(&self, workspace: &str, group: &str) -> Result<Vec<StorageRecord>>;
+    fn remove_group(&self, workspace: &str, group: &str) -> Result<()>;
+
+    fn exists(&self, workspace: &str, id: &str) -> Result<bool> {
+        Ok(self.load(workspace, id)?.is_some())
+    }
+
+    fn query(
+        &self,
+        _workspace: &str,
+        _kinds: Option<&[EntityKind]>,
+        _nodes: Option<&[String]>,
+    ) -> Result<Option<Vec<String>>> {
+        Ok(None)
+    }
+}
+
+pub fn open_storage(kind: StorageKind, root: &Path) -> Result<Arc<dyn StorageBackend>> {
+    Ok(match kind {
+        StorageKind::File => Arc::new(FileStorage::new(root.join("workspaces"))),
+        StorageKind::Sqlite => Arc::new(SqliteStorage::open(root.join("store.db"))?),
+        StorageKind::Memory => Arc::new(MemoryStorage::default()),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ::std::{
+        env, fs,
+        path::PathBuf,
+        process,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    pub fn temp_root(name: &str) -> PathBuf {
+        let idx = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = env::temp_dir().join(format!("maes-{name}-{}-{idx}", process::id()));
+        fs::remove_dir_all(&path).ok();
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    pub fn record(
+        workspace: &str,
+        id: &str,
+        kind: Option<EntityKind>,
+        data: &[u8],
+    ) -> StorageRecord {
+        StorageRecord {
+            workspace: workspace.to_string(),
+            id: id.to_string(),
+            kind,
+            node: String::new(),
+            data: data.to_vec(),
+        }
+    }
+
+    fn sorted(mut items: Vec<String>) -> Vec<String> {
+        items.sort_unstable();
+        items
+    }
+
+    fn round_trip(storage: &dyn StorageBackend) {
+        storage
+            .save(vec![
+                record("ws", "entities", Some(EntityKind::Entities), b"entities"),
+                record("ws", "quiz", Some(EntityKind::Quiz), b"quiz"),
+                record("ws", "task/student", None, b"entry"),
+            ])
+            .unwrap();
+
+        assert_eq!(storage.load("ws", "quiz").unwrap(), Some(b"quiz".to_vec()));
+        assert_eq!(storage.load("ws", "missing").unwrap(), None);
+        assert!(storage.exists("ws", "entities").unwrap());
+        assert_eq!(storage.workspaces().unwrap(), vec!["ws".to_string()]);
+        assert_eq!(
+            sorted(
+                storage
+                    .records("ws")
+                    .unwrap()
+                    .into_iter()
+                    .map(|r| r.id)
+                    .collect()
+            ),
+            vec!["entities", "quiz", "task/student"]
+        );
+
+        storage
+            .save(vec![record(
+                "ws",
+                "quiz",
+                Some(EntityKind::Quiz),
+                b"quiz-2",
+            )])
+            .unwrap();
+        assert_eq!(
+            storage.load("ws", "quiz").unwrap(),
+            Some(b"quiz-2".to_vec())
+        );
+
+        let group = storage.scan("ws", "task").unwrap();
+        assert_eq!(group.len(), 1);
+        assert_eq!(group[0].id, "task/student");
+        assert_eq!(group[0].data, b"entry");
+        storage.remove_group("ws", "task").unwrap();
+        assert!(storage.scan("ws", "task").unwrap().is_empty());
+
+        storage.delete("ws", "quiz").unwrap();
+        assert_eq!(storage.load("ws", "quiz").unwrap(), None);
+
+        assert_eq!(
+            sorted(storage.remove_workspace("ws").unwrap()),
+            vec!["entities"]
+        );
+        assert!(storage.workspaces().unwrap().is_empty());
+    }
+
+    #[test]
+    fn memory_round_trip() {
+        round_trip(&MemoryStorage::default());
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let root = temp_root("file");
+        round_trip(&FileStorage::new(root.join("workspaces")));
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn sqlite_round_trip() {
+        let root = temp_root("sqlite");
+        round_trip(&SqliteStorage::open(root.join("store.db")).unwrap());
+        fs::remove_dir_all(root).ok();
+    }
+}
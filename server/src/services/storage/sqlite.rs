@@ -0,0 +1,210 @@
+use super::*;
+use ::rusqlite::{Connection, OptionalExtension, params, params_from_iter, types::Value};
+use ::std::{path::Path, sync::Mutex};
+
+const SCHEMA: &str = "
+    PRAGMA journal_mode = WAL;
+    PRAGMA synchronous = NORMAL;
+    CREATE TABLE IF NOT EXISTS records (
+        workspace TEXT NOT NULL,
+        id TEXT NOT NULL,
+        kind TEXT,
+        node TEXT NOT NULL DEFAULT '',
+        data BLOB NOT NULL,
+        PRIMARY KEY (workspace, id)
+    ) WITHOUT ROWID;
+    CREATE INDEX IF NOT EXISTS records_kind ON records (workspace, kind);
+    CREATE INDEX IF NOT EXISTS records_node ON records (workspace, node);
+";
+
+pub struct SqliteStorage {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStorage {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path).map_err(map_log_err)?;
+        conn.execute_batch(SCHEMA).map_err(map_log_err)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+}
+
+impl StorageBackend for SqliteStorage {
+    fn load(&self, workspace: &str, id: &str) -> Result<Option<Vec<u8>>> {
+        let conn = self.conn.lock().map_err(map_log_err)?;
+        conn.query_row(
+            "SELECT data FROM records WHERE workspace = ?1 AND id = ?2",
+            params![workspace, id],
+            |row| row.get::<_, Vec<u8>>(0),
+        )
+        .optional()
+        .map_err(map_log_err)
+    }
+
+    fn save(&self, records: Vec<StorageRecord>) -> Result<()> {
+        let mut conn = self.conn.lock().map_err(map_log_err)?;
+        let tx = conn.transaction().map_err(map_log_err)?;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "INSERT INTO records (workspace, id, kind, node, data) VALUES (?1, ?2, ?3, ?4, ?5)
+                     ON CONFLICT (workspace, id) DO UPDATE SET
+                        kind = excluded.kind, node = excluded.node, data = excluded.data",
+                )
+                .map_err(map_log_err)?;
+            for record in records.iter() {
+                stmt.execute(params![
+                    record.workspace,
+                    record.id,
+                    record.kind.map(|k| k.as_str()),
+                    record.node,
+                    record.data,
+                ])
+                .map_err(map_log_err)?;
+            }
+        }
+        tx.commit().map_err(map_log_err)
+    }
+
+    fn delete(&self, workspace: &str, id: &str) -> Result<()> {
+        let conn = self.conn.lock().map_err(map_log_err)?;
+        conn.execute(
+            "DELETE FROM records WHERE workspace = ?1 AND id = ?2",
+            params![workspace, id],
+        )
+        .map_err(map_log_err)?;
+        Ok(())
+    }
+
+    fn remove_workspace(&self, workspace: &str) -> Result<Vec<String>> {
+        let mut conn = self.conn.lock().map_err(map_log_err)?;
+        let tx = conn.transaction().map_err(map_log_err)?;
+        let ids = {
+            let mut stmt = tx
+                .prepare("SELECT id FROM records WHERE workspace = ?1")
+                .map_err(map_log_err)?;
+            stmt.query_map(params![workspace], |row| row.get::<_, String>(0))
+                .map_err(map_log_err)?
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(map_log_err)?
+        };
+        tx.execute("DELETE FROM records WHERE workspace = ?1", params![workspace])
+            .map_err(map_log_err)?;
+        tx.commit().map_err(map_log_err)?;
+        Ok(ids)
+    }
+
+    fn workspaces(&self) -> Result<Vec<String>> {
+        let conn = self.conn.lock().map_err(map_log_err)?;
+        let mut stmt = conn
+            .prepare("SELECT DISTINCT workspace FROM records")
+            .map_err(map_log_err)?;
+        stmt.query_map([], |row| row.get::<_, String>(0))
+            .map_err(map_log_err)?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(map_log_err)
+    }
+
+    fn records(&self, workspace: &str) -> Result<Vec<StorageRecord>> {
+        let conn = self.conn.lock().map_err(map_log_err)?;
+        let mut stmt = conn
+            .prepare("SELECT id, kind, node, data FROM records WHERE workspace = ?1")
+            .map_err(map_log_err)?;
+        stmt.query_map(params![workspace], |row| {
+            Ok(StorageRecord {
+                workspace: workspace.to_string(),
+                id: row.get(0)?,
+                kind: row
+                    .get::<_, Option<String>>(1)?
+                    .and_then(|kind| kind.parse().ok()),
+                node: row.get(2)?,
+                data: row.get(3)?,
+            })
+        })
+        .map_err(map_log_err)?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(map_log_err)
+    }
+
+    fn scan(&self, workspace: &str, group: &str) -> Result<Vec<StorageRecord>> {
+        let conn = self.conn.lock().map_err(map_log_err)?;
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT id, data FROM records WHERE workspace = ?1 AND id > ?2 AND id < ?3",
+            )
+            .map_err(map_log_err)?;
+        let (lower, upper) = group_range(group);
+        stmt.query_map(params![workspace, lower, upper], |row| {
+            Ok(StorageRecord {
+                workspace: workspace.to_string(),
+                id: row.get(0)?,
+                kind: None,
+                node: String::new(),
+                data: row.get(1)?,
+            })
+        })
+        .map_err(map_log_err)?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(map_log_err)
+    }
+
+    fn remove_group(&self, workspace: &str, group: &str) -> Result<()> {
+        let conn = self.conn.lock().map_err(map_log_err)?;
+        let (lower, upper) = group_range(group);
+        conn.execute(
+            "DELETE FROM records WHERE workspace = ?1 AND id > ?2 AND id < ?3",
+            params![workspace, lower, upper],
+        )
+        .map_err(map_log_err)?;
+        Ok(())
+    }
+
+    fn exists(&self, workspace: &str, id: &str) -> Result<bool> {
+        let conn = self.conn.lock().map_err(map_log_err)?;
+        conn.query_row(
+            "SELECT 1 FROM records WHERE workspace = ?1 AND id = ?2",
+            params![workspace, id],
+            |_| Ok(()),
+        )
+        .optional()
+        .map(|row| row.is_some())
+        .map_err(map_log_err)
+    }
+
+    fn query(
+        &self,
+        workspace: &str,
+        kinds: Option<&[EntityKind]>,
+        nodes: Option<&[String]>,
+    ) -> Result<Option<Vec<String>>> {
+        let mut sql = "SELECT id FROM records WHERE workspace = ?".to_string();
+        let mut values = vec![Value::Text(workspace.to_string())];
+        if let Some(kinds) = kinds {
+            sql.push_str(&format!(" AND kind IN ({})", placeholders(kinds.len())));
+            values.extend(kinds.iter().map(|k| Value::Text(k.as_str().to_string())));
+        }
+        if let Some(nodes) = nodes {
+            sql.push_str(&format!(" AND node IN ({})", placeholders(nodes.len())));
+            values.extend(nodes.iter().map(|n| Value::Text(n.clone())));
+        }
+
+        let conn = self.conn.lock().map_err(map_log_err)?;
+        let mut stmt = conn.prepare(&sql).map_err(map_log_err)?;
+        stmt.query_map(params_from_iter(values), |row| row.get::<_, String>(0))
+            .map_err(map_log_err)?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map(Some)
+            .map_err(map_log_err)
+    }
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+// '0' directly follows '/' so the range covers exactly the "{group}/" prefix.
+fn group_range(group: &str) -> (String, String) {
+    (format!("{group}/"), format!("{group}0"))
+}
@@ -1,25 +1,36 @@
-use crate::{common::*};
+use crate::{common::*, services::*};
 use ::moka::future::Cache;
 use ::serde::{Deserialize, Serialize};
-use ::shared::{common::*, services::*, utils::*};
+use ::shared::{common::*, models::*, services::*, utils::*};
 use ::std::{
     any::Any,
-    collections::HashSet,
-    path::{Path, PathBuf},
-    sync::{Arc, LazyLock},
+    collections::{HashMap, HashSet},
+    sync::{Arc, LazyLock, RwLock as StdRwLock},
 };
-use ::tokio::{fs, io::AsyncWriteExt, sync::RwLock, task::spawn_blocking};
+use ::tokio::{sync::RwLock, task::spawn_blocking};
 
 static CIPHER: LazyLock<Arc<Cipher>> = LazyLock::new(|| {
     Arc::new(Cipher::init().unwrap_or_else(|e| panic!("Cipher init failed: {e}")))
 });
 static CACHE: LazyLock<Cache<String, Arc<dyn Any + Send + Sync>>> =
     LazyLock::new(|| Cache::builder().max_capacity(1_000).build());
+static STORAGE: StdRwLock<Option<Arc<dyn StorageBackend>>> = StdRwLock::new(None);
+static STORAGE_KIND: StdRwLock<StorageKind> = StdRwLock::new(StorageKind::File);
+/// Writes share the barrier, a migration holds it exclusively while it copies
+/// the data and swaps the backend, so no write can land on the old one.
+static WRITE_BARRIER: LazyLock<RwLock<()>> = LazyLock::new(|| RwLock::new(()));
 
 #[derive(Copy, Clone)]
 pub struct Store;
 
 impl Store {
+    pub fn init(kind: StorageKind) -> Result<()> {
+        let storage = open_storage(kind, State::path())?;
+        *STORAGE.write().map_err(map_log_err)? = Some(storage);
+        *STORAGE_KIND.write().map_err(map_log_err)? = kind;
+        Ok(())
+    }
+
     pub async fn find<T: Cachable + for<'de> Deserialize<'de> + 'static>(
         workspace: impl Into<String>,
         id: impl Into<String>,
@@ -34,11 +45,11 @@ impl Store {
             return Ok(arc.clone());
         }
 
-        let path = Self::get_path(&ws_id, &id);
-
         let erased = CACHE
             .try_get_with(cache_id.clone(), async move {
-                let data = fs::read(path).await.map_err(map_log_err)?;
+                let data = Self::load_raw(&ws_id, &id)
+                    .await?
+                    .ok_or((StatusCode::NOT_FOUND, "file-not-found"))?;
                 let val = Self::decrypt_binary::<T>(ws_id, data, false)
                     .await
                     .map_err(map_log_err)?;
@@ -61,33 +72,45 @@ impl Store {
     ) -> Result<()> {
         let ws_id = payload.get_ws();
         let id = payload.get_id();
+        let node = payload.get_node();
         let cache_id = format!("{ws_id}{id}");
-        let path = Self::get_path(&ws_id, &id);
 
         if !CACHE.contains_key(&cache_id) {
             put_cached(cache_id, payload.clone()).await;
         }
 
-        tokio::spawn(async move {
-            if let Ok(data) = Self::encrypt_binary::<T>(ws_id, payload, false)
-                .await
-                .map_err(map_log_err)
-            {
-                save_atomic(path, &data).await.map_err(map_log_err).ok();
-            }
-        });
-        Ok(())
+        let data = Self::encrypt_binary::<T>(&ws_id, payload, false).await?;
+        Self::save_raw(vec![StorageRecord {
+            workspace: ws_id,
+            id,
+            kind: Some(T::kind()),
+            node,
+            data,
+        }])
+        .await
     }
 
     pub async fn delete(workspace: impl AsRef<str>, id: impl AsRef<str>) -> Result<()> {
-        let ws_id = workspace.as_ref();
-        let id = id.as_ref();
+        let ws_id = workspace.as_ref().to_string();
+        let id = id.as_ref().to_string();
         let cache_id = format!("{ws_id}{id}");
-        fs::remove_file(Self::get_path(ws_id, id)).await.ok();
+        let _barrier = WRITE_BARRIER.read().await;
+        let storage = storage()?;
+        spawn_blocking(move || {
+            storage.delete(&ws_id, &id)?;
+            storage.remove_group(&ws_id, &id)
+        })
+        .await
+        .map_err(map_log_err)?
+        .ok();
         pop_cached(cache_id).await;
         Ok(())
     }
 
+    pub async fn evict(workspace: impl AsRef<str>, id: impl AsRef<str>) {
+        pop_cached(format!("{}{}", workspace.as_ref(), id.as_ref())).await;
+    }
+
     pub async fn batch_remove(workspace: impl Into<String>, ids: Vec<String>) -> Result<()> {
         let ws_id = workspace.into();
         let ids = ids.into_iter().collect::<HashSet<String>>();
@@ -103,28 +126,104 @@ impl Store {
     pub async fn remove_workspace(workspace: impl Into<String>) -> Result<()> {
         let ws_id = workspace.into();
         tokio::spawn(async move {
-            let path = State::path().join(format!("workspaces/{ws_id}"));
-            if !path.exists() {
+            let _barrier = WRITE_BARRIER.read().await;
+            let Ok(storage) = storage() else {
                 return;
+            };
+            let ws = ws_id.clone();
+            let Ok(Ok(ids)) = spawn_blocking(move || storage.remove_workspace(&ws)).await else {
+                return;
+            };
+            for id in ids {
+                pop_cached(format!("{ws_id}{id}")).await;
             }
-            if let Ok(mut dir) = fs::read_dir(&path).await {
-                while let Ok(Some(entry)) = dir.next_entry().await {
-                    let Ok(filename) = entry.file_name().into_string() else {
-                        continue;
-                    };
-                    pop_cached(format!(
-                        "{ws_id}{id}",
-                        id = filename.trim_end_matches(".bin")
-                    ))
-                    .await;
-                }
-            }
-            fs::remove_dir_all(path).await.map_err(map_log_err).ok();
             pop_cached(&ws_id).await;
         });
         Ok(())
     }
 
+    pub async fn load_raw(
+        workspace: impl Into<String>,
+        id: impl Into<String>,
+    ) -> Result<Option<Vec<u8>>> {
+        let ws_id = workspace.into();
+        let id = id.into();
+        let storage = storage()?;
+        spawn_blocking(move || storage.load(&ws_id, &id))
+            .await
+            .map_err(map_log_err)?
+    }
+
+    pub async fn save_raw(records: Vec<StorageRecord>) -> Result<()> {
+        let _barrier = WRITE_BARRIER.read().await;
+        let storage = storage()?;
+        spawn_blocking(move || storage.save(records))
+            .await
+            .map_err(map_log_err)?
+    }
+
+    pub async fn scan(
+        workspace: impl Into<String>,
+        group: impl Into<String>,
+    ) -> Result<Vec<StorageRecord>> {
+        let ws_id = workspace.into();
+        let group = group.into();
+        let storage = storage()?;
+        spawn_blocking(move || storage.scan(&ws_id, &group))
+            .await
+            .map_err(map_log_err)?
+    }
+
+    pub async fn exists(workspace: impl Into<String>, id: impl Into<String>) -> bool {
+        let ws_id = workspace.into();
+        let id = id.into();
+        let Ok(storage) = storage() else {
+            return false;
+        };
+        spawn_blocking(move || storage.exists(&ws_id, &id))
+            .await
+            .is_ok_and(|exists| exists.unwrap_or_default())
+    }
+
+    pub async fn workspaces() -> Result<Vec<String>> {
+        let storage = storage()?;
+        spawn_blocking(move || storage.workspaces())
+            .await
+            .map_err(map_log_err)?
+    }
+
+    pub async fn query(
+        workspace: impl Into<String>,
+        kinds: Option<Vec<EntityKind>>,
+        nodes: Option<Vec<String>>,
+    ) -> Result<Option<Vec<String>>> {
+        let ws_id = workspace.into();
+        let storage = storage()?;
+        spawn_blocking(move || storage.query(&ws_id, kinds.as_deref(), nodes.as_deref()))
+            .await
+            .map_err(map_log_err)?
+    }
+
+    pub async fn migrate(kind: StorageKind) -> Result<(usize, usize)> {
+        if *STORAGE_KIND.read().map_err(map_log_err)? == kind {
+            Err((StatusCode::CONFLICT, "storage-already-active"))?
+        }
+        let _barrier = WRITE_BARRIER.write().await;
+        let source = storage()?;
+        let target = open_storage(kind, State::path())?;
+
+        let migrated = spawn_blocking({
+            let target = target.clone();
+            move || copy_storage(source.as_ref(), target.as_ref())
+        })
+        .await
+        .map_err(map_log_err)??;
+
+        *STORAGE.write().map_err(map_log_err)? = Some(target);
+        *STORAGE_KIND.write().map_err(map_log_err)? = kind;
+        Ok(migrated)
+    }
+
     pub async fn encrypt_json<T: Serialize + Send + 'static>(
         workspace: impl Into<String>,
         data: T,
@@ -172,10 +271,68 @@ impl Store {
         .await
         .map_err(map_log_err)?
     }
+}
+
+/// Replaces everything in `target` with the records of `source`. Rows left in
+/// the target by an earlier migration are stale and are cleared first.
+fn copy_storage(
+    source: &dyn StorageBackend,
+    target: &dyn StorageBackend,
+) -> Result<(usize, usize)> {
+    for ws_id in target.workspaces()? {
+        target.remove_workspace(&ws_id)?;
+    }
+
+    let workspaces = source.workspaces()?;
+    let mut count = 0;
+    for ws_id in workspaces.iter() {
+        let mut records = source.records(ws_id)?;
+        let index = records
+            .iter()
+            .find(|r| r.id == ENTITIES && r.kind.is_none())
+            .and_then(|r| {
+                CIPHER
+                    .get(ws_id)
+                    .and_then(|c| c.decrypt_binary::<Entities>(&r.data, false))
+                    .ok()
+            })
+            .map(|entities| {
+                entities
+                    .values()
+                    .map(|e| (e.id.clone(), (e.kind, e.node.clone())))
+                    .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+
+        for record in records.iter_mut().filter(|r| r.kind.is_none()) {
+            let (kind, node) = match record.id.as_str() {
+                ENTITIES => (Some(EntityKind::Entities), String::new()),
+                STUDENTS => (Some(EntityKind::Students), String::new()),
+                TASKS => (Some(EntityKind::Tasks), String::new()),
+                id if id == ws_id.as_str() => (Some(EntityKind::Workspace), String::new()),
+                id => index
+                    .get(id)
+                    .map(|(kind, node)| (Some(*kind), node.clone()))
+                    .unwrap_or_default(),
+            };
+            record.kind = kind;
+            record.node = node;
+        }
+        count += records.len();
+        target.save(records)?;
+    }
+    Ok((workspaces.len(), count))
+}
 
-    pub fn get_path(workspace: impl AsRef<str>, id: impl AsRef<str>) -> PathBuf {
-        State::path().join(format!("workspaces/{workspace}/{id}.bin", workspace = workspace.as_ref(), id = id.as_ref()))
+fn storage() -> Result<Arc<dyn StorageBackend>> {
+    if let Some(storage) = STORAGE.read().map_err(map_log_err)?.as_ref() {
+        return Ok(storage.clone());
     }
+    let mut guard = STORAGE.write().map_err(map_log_err)?;
+    let storage = guard
+        .get_or_insert_with(|| Arc::new(FileStorage::new(State::path().join("workspaces"))))
+        .clone();
+    Ok(storage)
 }
 
 async fn put_cached<T: Send + Sync + 'static>(id: impl Into<String>, value: T) {
@@ -193,34 +350,52 @@ async fn pop_cached(id: impl AsRef<str>) {
     CACHE.invalidate(id.as_ref()).await;
 }
 
-async fn save_atomic<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<()> {
-    let path = path.as_ref();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::storage::tests::{record, temp_root};
 
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).await.map_err(map_log_err)?;
-    }
+    #[test]
+    fn copy_storage_replaces_target() {
+        let source = MemoryStorage::default();
+        source
+            .save(vec![
+                record("ws", ENTITIES, Some(EntityKind::Entities), b"entities"),
+                record("ws", TASKS, None, b"tasks"),
+                record("ws", "quiz", Some(EntityKind::Quiz), b"quiz"),
+                record("other", "survey", Some(EntityKind::Survey), b"survey"),
+            ])
+            .unwrap();
 
-    let tmp = path.with_extension("tmp");
+        let root = temp_root("migrate");
+        let target = SqliteStorage::open(root.join("store.db")).unwrap();
+        target
+            .save(vec![record(
+                "stale",
+                "quiz",
+                Some(EntityKind::Quiz),
+                b"stale",
+            )])
+            .unwrap();
 
-    {
-        let mut f = fs::File::create(&tmp).await.map_err(map_log_err)?;
-        f.write_all(data).await.map_err(map_log_err)?;
-        f.sync_all().await.map_err(map_log_err)?;
-    }
+        assert_eq!(copy_storage(&source, &target).unwrap(), (2, 4));
 
-    let rename_res = fs::rename(&tmp, path).await;
-    if let Err(_e) = rename_res {
-        if fs::try_exists(path).await.unwrap_or(false) {
-            _ = fs::remove_file(path).await;
-        }
-        fs::rename(&tmp, path).await.map_err(map_log_err)?;
-    }
+        let mut workspaces = target.workspaces().unwrap();
+        workspaces.sort_unstable();
+        assert_eq!(workspaces, vec!["other", "ws"]);
+        assert_eq!(target.load("ws", "quiz").unwrap(), Some(b"quiz".to_vec()));
+        assert_eq!(
+            target.load("other", "survey").unwrap(),
+            Some(b"survey".to_vec())
+        );
+        let tasks = target
+            .records("ws")
+            .unwrap()
+            .into_iter()
+            .find(|r| r.id == TASKS)
+            .unwrap();
+        assert_eq!(tasks.kind, Some(EntityKind::Tasks));
 
-    if let Some(parent) = path.parent() {
-        if let Ok(dir) = fs::File::open(parent).await {
-            _ = dir.sync_all().await;
-        }
+        ::std::fs::remove_dir_all(root).ok();
     }
-
-    Ok(())
 }
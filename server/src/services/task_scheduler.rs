@@ -0,0 +1,54 @@
+use crate::{repositories::*, services::*};
+use ::chrono::Utc;
+use ::shared::{common::*, models::*};
+use ::std::time::Duration;
+use ::tokio::time::{MissedTickBehavior, interval};
+use ::tracing::error;
+
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(30);
+const SCHEDULER_USER: &str = "scheduler";
+
+#[derive(Copy, Clone)]
+pub struct TaskSchedulerService;
+
+impl TaskSchedulerService {
+    pub fn init() {
+        tokio::spawn(async move {
+            let mut ticker = interval(SCHEDULER_INTERVAL);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = Self::finish_expired().await {
+                    error!("task scheduler failed: {e}");
+                }
+            }
+        });
+    }
+
+    /// Moves every task whose close time (plus the submission grace) has passed into `Entities`.
+    pub async fn finish_expired() -> Result<usize> {
+        let now = Utc::now().timestamp();
+        let mut count = 0;
+        for ws_id in Store::workspaces().await? {
+            if !Store::exists(&ws_id, TASKS).await {
+                continue;
+            }
+            let expired = {
+                let tasks_arc = Store::find::<Tasks>(&ws_id, TASKS).await?;
+                let tasks_guard = tasks_arc.read().await;
+                tasks_guard
+                    .values()
+                    .filter(|t| t.status_with_grace(now, SUBMIT_GRACE) == TaskStatus::Closed)
+                    .map(|t| t.id.clone())
+                    .collect::<Vec<_>>()
+            };
+            for task_id in expired {
+                match TaskRepository::finish(&ws_id, &task_id, SCHEDULER_USER).await {
+                    Ok(()) => count += 1,
+                    Err(e) => error!("failed to finish task {task_id}: {e}"),
+                }
+            }
+        }
+        Ok(count)
+    }
+}
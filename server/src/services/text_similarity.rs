@@ -6,44 +6,49 @@ use ::onnxruntime::{
     tensor::OrtOwnedTensor,
 };
 use ::shared::common::*;
-use ::std::{env, path::Path, sync::Mutex};
+use ::std::{
+    collections::{HashMap, VecDeque},
+    env,
+    path::Path,
+    sync::OnceLock,
+    time::Duration,
+};
 use ::tokenizers::{Tokenizer, tokenizer::EncodeInput};
-use ::tokio::sync::{mpsc, oneshot};
-use ::tracing::error;
+use ::tokio::{
+    sync::{mpsc, oneshot},
+    time::timeout,
+};
+use ::tracing::{error, warn};
 
 const MAX_LEN: usize = 256;
+const QUEUE_CAPACITY: usize = 64;
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+const REFERENCE_CACHE_CAPACITY: usize = 4096;
 
 #[derive(Copy, Clone)]
 pub struct TextSimilarityService;
 
-type AsyncJob = (String, String, oneshot::Sender<Result<usize>>);
-
-use std::sync::mpsc as std_mpsc;
-type SyncReply = std_mpsc::SyncSender<usize>;
-
-enum AnyJob {
-    Async(AsyncJob),
-    Sync(String, String, SyncReply),
+struct Job {
+    scope: String,
+    pairs: Vec<(String, String)>,
+    reply: oneshot::Sender<Result<Vec<usize>>>,
 }
 
-static SENDER_ANY: Mutex<Option<mpsc::UnboundedSender<AnyJob>>> = Mutex::new(None);
+static SENDER: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
 
 impl TextSimilarityService {
     pub async fn init() -> Result<()> {
-        let mut guard = SENDER_ANY.lock().unwrap();
-        if guard.is_some() {
-            return Err(Error::from("text-similarity already initialized"));
-        }
-
         let exe = env::current_exe().map_err(map_log_err)?;
         let exe_dir = exe.parent().ok_or_else(|| Error::from("text-similarity: exe dir not found"))?;
         let model_dir = exe_dir.join("assets");
-        let (tx_any, mut rx_any) = mpsc::unbounded_channel::<AnyJob>();
-        *guard = Some(tx_any.clone());
-        drop(guard);
+        let (tx, mut rx) = mpsc::channel::<Job>(QUEUE_CAPACITY);
+        SENDER
+            .set(tx)
+            .map_err(|_| Error::from("text-similarity already initialized"))?;
 
         std::thread::spawn(move || {
-            if let Err(e) = worker_loop_any(&model_dir, &mut rx_any) {
+            if let Err(e) = worker_loop(&model_dir, &mut rx) {
                 error!("text-similarity worker failed: {e}");
             }
         });
@@ -52,36 +57,82 @@ impl TextSimilarityService {
     }
 
     pub async fn compare(a: impl Into<String>, b: impl Into<String>) -> Result<usize> {
-        let tx = {
-            let guard = SENDER_ANY.lock().unwrap();
-            guard
-                .as_ref()
-                .cloned()
-                .ok_or_else(|| Error::from("text-similarity not initialized"))?
+        let scores = Self::compare_batch("", vec![(a.into(), b.into())]).await?;
+        Ok(scores.and_then(|s| s.into_iter().next()).unwrap_or_default())
+    }
+
+    /// Scores `(reference, answer)` pairs in a single model run. Reference embeddings are cached
+    /// per `scope` (usually the quiz id). Returns `None` when the model is not available.
+    pub async fn compare_batch(
+        scope: impl Into<String>,
+        pairs: Vec<(String, String)>,
+    ) -> Result<Option<Vec<usize>>> {
+        let Some(tx) = SENDER.get().filter(|tx| !tx.is_closed()).cloned() else {
+            return Ok(None);
         };
+        if pairs.is_empty() {
+            return Ok(Some(vec![]));
+        }
 
-        let (resp_tx, resp_rx) = oneshot::channel();
-        tx.send(AnyJob::Async((a.into(), b.into(), resp_tx)))
+        let (reply, reply_rx) = oneshot::channel();
+        let job = Job {
+            scope: scope.into(),
+            pairs,
+            reply,
+        };
+        timeout(QUEUE_TIMEOUT, tx.send(job))
+            .await
+            .map_err(|_| (StatusCode::SERVICE_UNAVAILABLE, "similarity-busy"))?
             .map_err(|_| Error::from("worker channel closed"))?;
-        resp_rx.await.map_err(|_| Error::from("worker dropped"))?
+        timeout(REPLY_TIMEOUT, reply_rx)
+            .await
+            .map_err(|_| (StatusCode::SERVICE_UNAVAILABLE, "similarity-timeout"))?
+            .map_err(|_| Error::from("worker dropped"))?
+            .map(Some)
     }
 
-    pub fn compare_sync(a: impl Into<String>, b: impl Into<String>) -> usize {
-        let tx = {
-            let guard = SENDER_ANY.lock().unwrap();
-            match guard.as_ref().cloned() {
-                Some(tx) => tx,
-                None => return 0,
-            }
-        };
+    /// Like [`Self::compare_batch`], but a busy or timed out queue is logged and reported as
+    /// `None`, so the caller falls back to lexical scoring instead of failing the request.
+    pub async fn compare_batch_or_lexical(
+        scope: impl Into<String>,
+        pairs: Vec<(String, String)>,
+    ) -> Option<Vec<usize>> {
+        Self::compare_batch(scope, pairs).await.unwrap_or_else(|e| {
+            warn!("text-similarity unavailable, using lexical scoring: {e}");
+            None
+        })
+    }
+}
+
+#[derive(Default)]
+struct ReferenceCache {
+    embeddings: HashMap<String, Vec<f32>>,
+    order: VecDeque<String>,
+}
+
+impl ReferenceCache {
+    fn key(scope: &str, text: &str) -> String {
+        format!("{scope}|{text}")
+    }
+
+    fn get(&self, scope: &str, text: &str) -> Option<&Vec<f32>> {
+        self.embeddings.get(&Self::key(scope, text))
+    }
 
-        let (reply_tx, reply_rx) = std_mpsc::sync_channel(1);
-        _ = tx.send(AnyJob::Sync(a.into(), b.into(), reply_tx));
-        reply_rx.recv().unwrap_or(0)
+    fn insert(&mut self, scope: &str, text: &str, embedding: Vec<f32>) {
+        let key = Self::key(scope, text);
+        if self.embeddings.insert(key.clone(), embedding).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > REFERENCE_CACHE_CAPACITY {
+            if let Some(key) = self.order.pop_front() {
+                self.embeddings.remove(&key);
+            }
+        }
     }
 }
 
-fn worker_loop_any(model_dir: &Path, rx: &mut mpsc::UnboundedReceiver<AnyJob>) -> Result<()> {
+fn worker_loop(model_dir: &Path, rx: &mut mpsc::Receiver<Job>) -> Result<()> {
     let env = Environment::builder()
         .with_name("maes")
         .build()
@@ -106,39 +157,55 @@ fn worker_loop_any(model_dir: &Path, rx: &mut mpsc::UnboundedReceiver<AnyJob>) -
         .with_model_from_file(model_path)
         .map_err(map_log_err)?;
 
+    let mut cache = ReferenceCache::default();
     while let Some(job) = rx.blocking_recv() {
-        match job {
-            AnyJob::Async((a, b, reply)) => {
-                let res = compare_impl(&tokenizer, &mut session, &a, &b, max_len).map_err(|e| {
-                    error!("compare_impl failed: {e}");
-                    e
-                });
-                _ = reply.send(res);
-            }
-            AnyJob::Sync(a, b, reply) => {
-                let res = compare_impl(&tokenizer, &mut session, &a, &b, max_len)
-                    .map(|v| v)
-                    .unwrap_or_default();
-                _ = reply.send(res);
-            }
-        }
+        let res = compare_batch_impl(&tokenizer, &mut session, &mut cache, &job, max_len)
+            .map_err(|e| {
+                error!("compare_batch_impl failed: {e}");
+                e
+            });
+        _ = job.reply.send(res);
     }
 
     Ok(())
 }
 
-fn compare_impl(
+fn compare_batch_impl(
     tokenizer: &Tokenizer,
     session: &mut Session<'static>,
-    a: &str,
-    b: &str,
+    cache: &mut ReferenceCache,
+    job: &Job,
     max_len: usize,
-) -> Result<usize> {
-    let embeddings = encode(tokenizer, session, &[a, b], max_len)?;
-    let sim = cosine_similarity(&embeddings[0], &embeddings[1]);
-    Ok((sim * 100f32).round() as usize)
-}
+) -> Result<Vec<usize>> {
+    let mut references = job
+        .pairs
+        .iter()
+        .map(|(reference, _)| reference.as_str())
+        .filter(|reference| cache.get(&job.scope, reference).is_none())
+        .collect::<Vec<_>>();
+    references.sort_unstable();
+    references.dedup();
+
+    let mut texts = references.clone();
+    texts.extend(job.pairs.iter().map(|(_, answer)| answer.as_str()));
+    let mut embeddings = encode(tokenizer, session, &texts, max_len)?.into_iter();
+
+    for reference in references {
+        if let Some(embedding) = embeddings.next() {
+            cache.insert(&job.scope, reference, embedding);
+        }
+    }
 
+    let mut scores = Vec::with_capacity(job.pairs.len());
+    for ((reference, _), answer) in job.pairs.iter().zip(embeddings) {
+        let sim = cache
+            .get(&job.scope, reference)
+            .map(|reference| cosine_similarity(reference, &answer))
+            .unwrap_or_default();
+        scores.push((sim * 100f32).round() as usize);
+    }
+    Ok(scores)
+}
 
 fn encode(
     tokenizer: &Tokenizer,
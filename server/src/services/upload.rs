@@ -0,0 +1,66 @@
+use ::axum::extract::multipart::{Field, Multipart, MultipartError};
+use ::shared::common::*;
+use ::std::path::Path;
+use ::tokio::{fs, io::AsyncWriteExt};
+
+pub const IMAGE_UPLOAD_LIMIT: usize = 16 * 1024 * 1024;
+pub const ARCHIVE_UPLOAD_LIMIT: usize = 1024 * 1024 * 1024;
+
+#[derive(Copy, Clone)]
+pub struct UploadService;
+
+impl UploadService {
+    pub async fn read_bytes(multipart: &mut Multipart) -> Result<Vec<u8>> {
+        let field = Self::next_file(multipart).await?;
+        let data = field.bytes().await.map_err(map_upload_err)?;
+        if data.is_empty() {
+            Err((StatusCode::BAD_REQUEST, "upload-empty"))?
+        }
+        Ok(data.to_vec())
+    }
+
+    pub async fn save_to_file(multipart: &mut Multipart, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut field = Self::next_file(multipart).await?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(map_log_err)?;
+        }
+        let mut file = fs::File::create(path).await.map_err(map_log_err)?;
+
+        let mut written = 0;
+        let result = async {
+            while let Some(chunk) = field.chunk().await.map_err(map_upload_err)? {
+                written += chunk.len();
+                file.write_all(&chunk).await.map_err(map_log_err)?;
+            }
+            if written == 0 {
+                Err((StatusCode::BAD_REQUEST, "upload-empty"))?
+            }
+            file.flush().await.map_err(map_log_err)
+        }
+        .await;
+
+        if result.is_err() {
+            drop(file);
+            fs::remove_file(path).await.ok();
+        }
+        result
+    }
+
+    async fn next_file(multipart: &mut Multipart) -> Result<Field<'_>> {
+        while let Some(field) = multipart.next_field().await.map_err(map_upload_err)? {
+            if field.name() == Some("file") {
+                return Ok(field);
+            }
+        }
+        Err((StatusCode::BAD_REQUEST, "upload-empty").into())
+    }
+}
+
+fn map_upload_err(e: MultipartError) -> Error {
+    if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        (StatusCode::PAYLOAD_TOO_LARGE, "upload-too-large").into()
+    } else {
+        (StatusCode::BAD_REQUEST, "bad-request").into()
+    }
+}
@@ -0,0 +1,104 @@
+use ::chrono::{Datelike, TimeZone, Utc};
+use ::serde::{Deserialize, Serialize};
+use ::std::fmt;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalyticsPeriod {
+    Week,
+    #[default]
+    Month,
+    Quarter,
+}
+
+impl AnalyticsPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalyticsPeriod::Week => "week",
+            AnalyticsPeriod::Month => "month",
+            AnalyticsPeriod::Quarter => "quarter",
+        }
+    }
+
+    /// Sortable key of the period the timestamp falls in, e.g. `2025-W07`, `2025-02`, `2025-Q1`.
+    pub fn key(&self, timestamp: i64) -> String {
+        let Some(date) = Utc.timestamp_opt(timestamp, 0).single() else {
+            return String::new();
+        };
+        match self {
+            AnalyticsPeriod::Week => date.format("%G-W%V").to_string(),
+            AnalyticsPeriod::Month => date.format("%Y-%m").to_string(),
+            AnalyticsPeriod::Quarter => format!("{}-Q{}", date.year(), date.month0() / 3 + 1),
+        }
+    }
+}
+
+impl From<String> for AnalyticsPeriod {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "week" => AnalyticsPeriod::Week,
+            "quarter" => AnalyticsPeriod::Quarter,
+            _ => AnalyticsPeriod::Month,
+        }
+    }
+}
+
+impl fmt::Display for AnalyticsPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AnalyticsStats {
+    pub count: usize,
+    pub grade_a: usize,
+    pub grade_b: usize,
+    pub grade_c: usize,
+    pub grade_d: usize,
+    pub average_score: f64,
+    pub average_grade: f64,
+    pub pass_rate: f64,
+}
+
+impl AnalyticsStats {
+    pub fn add(&mut self, grade: usize, score: f64) {
+        match grade {
+            5 => self.grade_a += 1,
+            4 => self.grade_b += 1,
+            3 => self.grade_c += 1,
+            _ => self.grade_d += 1,
+        }
+        let count = self.count as f64;
+        self.count += 1;
+        self.average_score = (self.average_score * count + score) / self.count as f64;
+        self.average_grade = (self.average_grade * count + grade as f64) / self.count as f64;
+        self.pass_rate = ((self.count - self.grade_d) * 100) as f64 / self.count as f64;
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AnalyticsPeriodStats {
+    pub period: String,
+    pub stats: AnalyticsStats,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UnitAnalytics {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub has_children: bool,
+    pub total: AnalyticsStats,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub periods: Vec<AnalyticsPeriodStats>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Analytics {
+    pub node: UnitAnalytics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub units: Vec<UnitAnalytics>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub periods: Vec<String>,
+}
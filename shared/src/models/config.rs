@@ -77,4 +77,35 @@ pub struct ServerConfig {
     pub ident: String,
     pub host: String,
     pub remote: bool,
+    #[serde(default)]
+    pub storage: StorageKind,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StorageKind {
+    #[default]
+    File,
+    Sqlite,
+    Memory,
+}
+
+impl StorageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StorageKind::File => "file",
+            StorageKind::Sqlite => "sqlite",
+            StorageKind::Memory => "memory",
+        }
+    }
+}
+
+impl From<String> for StorageKind {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "sqlite" => StorageKind::Sqlite,
+            "memory" => StorageKind::Memory,
+            _ => StorageKind::File,
+        }
+    }
 }
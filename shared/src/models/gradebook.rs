@@ -0,0 +1,89 @@
+use ::serde::{Deserialize, Serialize};
+use ::std::collections::HashMap;
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradebookWeights {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub quizzes: HashMap<String, f64>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, f64>,
+}
+
+impl GradebookWeights {
+    pub fn is_empty(&self) -> bool {
+        self.quizzes.is_empty() && self.tags.is_empty()
+    }
+
+    /// A quiz weight wins over tag weights, among tags the heaviest one applies. Records matching
+    /// nothing weigh 1.
+    pub fn weight(&self, quiz: &str, tags: &[String]) -> f64 {
+        if let Some(weight) = self.quizzes.get(quiz) {
+            return *weight;
+        }
+        tags.iter()
+            .filter_map(|t| self.tags.get(t).copied())
+            .reduce(f64::max)
+            .unwrap_or(1.0)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Gradebook {
+    #[serde(default)]
+    pub weights: GradebookWeights,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub students: Vec<GradebookStudent>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradebookStudent {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
+    pub name: String,
+    pub node: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<GradebookEntry>,
+}
+
+impl GradebookStudent {
+    /// Weighted average of graded entries, `None` until the student has one.
+    pub fn average(&self) -> Option<f64> {
+        let (sum, weights) = self
+            .entries
+            .iter()
+            .filter(|e| e.grade > 0 && !e.excused && e.weight > 0.0)
+            .fold((0.0, 0.0), |(sum, weights), e| {
+                (sum + e.grade as f64 * e.weight, weights + e.weight)
+            });
+        (weights > 0.0).then(|| sum / weights)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradebookEntry {
+    pub record: String,
+    pub quiz: String,
+    pub name: String,
+    pub path: String,
+    pub date: i64,
+    #[serde(default)]
+    pub finished: bool,
+    pub grade: usize,
+    #[serde(default)]
+    pub overridden: bool,
+    pub attempts: usize,
+    #[serde(default)]
+    pub excused: bool,
+    pub weight: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<GradebookScore>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradebookScore {
+    pub name: String,
+    pub score: usize,
+}
@@ -25,6 +25,11 @@ impl<T: Clone + 'static> Grid<T> {
         &self.data[self.idx(row, col)]
     }
 
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        let idx = self.idx(row, col);
+        self.data.get_mut(idx)
+    }
+
     pub fn set(&mut self, row: usize, col: usize, val: T) {
         let idx = self.idx(row, col);
         self.data[idx] = val;
@@ -130,6 +135,28 @@ impl<T: Clone + 'static> Grid<T> {
         sum as f64 / self.cols as f64
     }
     
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn push_row(&mut self, fill: T) {
+        self.data.extend(std::iter::repeat_n(fill, self.cols));
+        self.rows += 1;
+    }
+
+    pub fn remove_row(&mut self, row: usize) {
+        if row >= self.rows {
+            return;
+        }
+        let start = self.idx(row, 0);
+        self.data.drain(start..start + self.cols);
+        self.rows -= 1;
+    }
+
     pub fn extend_rows(&mut self, other: &Self) {
         if self.cols != other.cols {
             return;
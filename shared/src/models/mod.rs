@@ -12,7 +12,10 @@ mod task;
 mod survey_record;
 mod grid;
 mod quiz_activity;
+mod quiz_variant;
 mod survey_activity;
+mod gradebook;
+mod analytics;
 
 pub use self::{
     metadata::*,
@@ -29,5 +32,8 @@ pub use self::{
     survey_record::*,
     grid::*,
     quiz_activity::*,   
+    quiz_variant::*,
     survey_activity::*, 
+    gradebook::*,
+    analytics::*,
 };
\ No newline at end of file
@@ -1,6 +1,11 @@
 use crate::{models::*, utils::*};
 use ::indexmap::IndexMap;
 use ::serde::{Deserialize, Serialize};
+use ::serde_repr::{Deserialize_repr, Serialize_repr};
+use ::std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Quiz {
@@ -11,6 +16,8 @@ pub struct Quiz {
     pub attempts: usize,
     pub duration: i64,
     pub grade: QuizGrade,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
     #[serde(
         default,
         skip_serializing_if = "IndexMap::is_empty",
@@ -30,11 +37,48 @@ impl Quiz {
             attempts: self.attempts,
             duration: self.duration,
             grade: self.grade.clone(),
+            languages: self.languages.clone(),
             categories: Default::default(),
             metadata: self.metadata.clone(),
         }
     }
 
+    /// Ids of the questions and answers that carry an image.
+    pub fn image_ids(&self) -> HashSet<String> {
+        let mut ids = HashSet::new();
+        for question in self.categories.values().flat_map(|c| c.questions.values()) {
+            if question.img {
+                ids.insert(question.id.clone());
+            }
+            ids.extend(
+                question
+                    .answers
+                    .values()
+                    .filter(|a| a.img)
+                    .map(|a| a.id.clone()),
+            );
+        }
+        ids
+    }
+
+    /// Open questions whose reference answers or matching settings differ from `original`, the
+    /// snapshot the students were scored against.
+    pub fn changed_open_questions(&self, original: &Quiz) -> HashSet<String> {
+        self.categories
+            .values()
+            .flat_map(|c| c.questions.values())
+            .filter(|q| q.is_open())
+            .filter(|q| {
+                original
+                    .categories
+                    .values()
+                    .find_map(|c| c.questions.get(&q.id))
+                    .is_none_or(|o| o.answers != q.answers || o.open != q.open)
+            })
+            .map(|q| q.id.clone())
+            .collect()
+    }
+
     pub fn to_entity(&self) -> Entity {
         Entity {
             id: self.id.clone(),
@@ -108,6 +152,63 @@ impl QuizCategory {
             questions: Default::default(),
         }
     }
+
+    /// Questions the assembly rules draw. A question goes to at most one of the rules it matches
+    /// and a rule takes at most its count. Questions are placed in the order `shuffle` leaves
+    /// them, moving placed ones to another rule when that makes room, so the draw is as large as
+    /// the rules allow whatever the order.
+    pub fn assemble<'a>(
+        &'a self,
+        rules: &[QuizAssemblyRule],
+        mut shuffle: impl FnMut(&mut Vec<&'a QuizQuestion>),
+    ) -> Vec<&'a String> {
+        let mut questions = self
+            .questions
+            .values()
+            .filter(|q| rules.iter().any(|r| r.matches(q)))
+            .collect::<Vec<_>>();
+        shuffle(&mut questions);
+
+        let mut taken = vec![Vec::<usize>::new(); rules.len()];
+        for idx in 0..questions.len() {
+            let mut visited = vec![false; rules.len()];
+            place_question(idx, &questions, rules, &mut taken, &mut visited);
+        }
+        taken
+            .into_iter()
+            .flatten()
+            .map(|idx| questions[idx])
+            .map(|q| &q.id)
+            .collect()
+    }
+}
+
+/// Places the question with a rule that has room, or frees a place by moving one of the
+/// questions a matching rule took to another rule.
+fn place_question(
+    idx: usize,
+    questions: &[&QuizQuestion],
+    rules: &[QuizAssemblyRule],
+    taken: &mut [Vec<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for (rule_idx, rule) in rules.iter().enumerate() {
+        if visited[rule_idx] || !rule.matches(questions[idx]) {
+            continue;
+        }
+        visited[rule_idx] = true;
+        if taken[rule_idx].len() < rule.count {
+            taken[rule_idx].push(idx);
+            return true;
+        }
+        for pos in 0..taken[rule_idx].len() {
+            if place_question(taken[rule_idx][pos], questions, rules, taken, visited) {
+                taken[rule_idx][pos] = idx;
+                return true;
+            }
+        }
+    }
+    false
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -121,6 +222,123 @@ pub struct QuizQuestion {
         with = "indexmap_as_vec"
     )]
     pub answers: IndexMap<String, QuizAnswer>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub translations: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub difficulty: QuizDifficulty,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open: Option<QuizOpenAnswer>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub explanation: String,
+}
+
+impl QuizQuestion {
+    pub fn localized_name(&self, language: impl AsRef<str>) -> &str {
+        localized(&self.name, &self.translations, language)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open.is_some() || self.answers.len() == 1
+    }
+
+    pub fn open_answer(&self) -> QuizOpenAnswer {
+        self.open.clone().unwrap_or_default()
+    }
+
+    pub fn correct_answers(&self) -> HashSet<String> {
+        self.answers
+            .values()
+            .filter(|a| a.correct)
+            .map(|a| a.id.clone())
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizOpenAnswer {
+    #[serde(default)]
+    pub strategy: QuizMatchStrategy,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+#[repr(u8)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+pub enum QuizMatchStrategy {
+    Exact = 1,
+    Fuzzy = 2,
+    #[default]
+    Semantic = 3,
+    Combined = 4,
+}
+
+impl QuizMatchStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuizMatchStrategy::Exact => "exact",
+            QuizMatchStrategy::Fuzzy => "fuzzy",
+            QuizMatchStrategy::Semantic => "semantic",
+            QuizMatchStrategy::Combined => "combined",
+        }
+    }
+
+    pub fn is_semantic(&self) -> bool {
+        matches!(self, QuizMatchStrategy::Semantic | QuizMatchStrategy::Combined)
+    }
+}
+
+impl From<String> for QuizMatchStrategy {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "exact" => QuizMatchStrategy::Exact,
+            "fuzzy" => QuizMatchStrategy::Fuzzy,
+            "combined" => QuizMatchStrategy::Combined,
+            _ => QuizMatchStrategy::Semantic,
+        }
+    }
+}
+
+impl fmt::Display for QuizMatchStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[repr(u8)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+pub enum QuizDifficulty {
+    Easy = 1,
+    #[default]
+    Medium = 2,
+    Hard = 3,
+}
+
+impl QuizDifficulty {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuizDifficulty::Easy => "easy",
+            QuizDifficulty::Medium => "medium",
+            QuizDifficulty::Hard => "hard",
+        }
+    }
+}
+
+impl From<String> for QuizDifficulty {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "easy" => QuizDifficulty::Easy,
+            "hard" => QuizDifficulty::Hard,
+            _ => QuizDifficulty::Medium,
+        }
+    }
+}
+
+impl fmt::Display for QuizDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -129,6 +347,14 @@ pub struct QuizAnswer {
     pub name: String,
     pub img: bool,
     pub correct: bool,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub translations: HashMap<String, String>,
+}
+
+impl QuizAnswer {
+    pub fn localized_name(&self, language: impl AsRef<str>) -> &str {
+        localized(&self.name, &self.translations, language)
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -138,4 +364,161 @@ pub struct QuizClipboard {
     pub categories: Vec<QuizCategory>,
 }
 
-fn default_similarity() -> usize { 70usize }
\ No newline at end of file
+fn default_similarity() -> usize { 70usize }
+
+fn localized<'a>(
+    name: &'a str,
+    translations: &'a HashMap<String, String>,
+    language: impl AsRef<str>,
+) -> &'a str {
+    translations
+        .get(language.as_ref())
+        .map(|s| s.as_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or(name)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(questions: &[(&str, &str)]) -> QuizCategory {
+        QuizCategory {
+            questions: questions
+                .iter()
+                .map(|(id, tags)| {
+                    let question = QuizQuestion {
+                        id: id.to_string(),
+                        tags: tags.split_whitespace().map(String::from).collect(),
+                        ..Default::default()
+                    };
+                    (id.to_string(), question)
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn rule(tag: &str, count: usize) -> QuizAssemblyRule {
+        QuizAssemblyRule {
+            count,
+            tags: vec![tag.to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn answer(id: &str, name: &str, correct: bool) -> (String, QuizAnswer) {
+        let answer = QuizAnswer {
+            id: id.to_string(),
+            name: name.to_string(),
+            correct,
+            ..Default::default()
+        };
+        (id.to_string(), answer)
+    }
+
+    fn regrade_quiz(reference: &str, strategy: QuizMatchStrategy) -> Quiz {
+        let open = QuizQuestion {
+            id: "q1".to_string(),
+            answers: IndexMap::from([answer("a1", reference, true)]),
+            open: Some(QuizOpenAnswer {
+                strategy,
+                keywords: vec![],
+            }),
+            ..Default::default()
+        };
+        let closed = QuizQuestion {
+            id: "q2".to_string(),
+            answers: IndexMap::from([answer("a1", "2", true), answer("a2", "3", false)]),
+            ..Default::default()
+        };
+        let category = QuizCategory {
+            id: "c1".to_string(),
+            questions: IndexMap::from([("q1".to_string(), open), ("q2".to_string(), closed)]),
+            ..Default::default()
+        };
+        Quiz {
+            categories: IndexMap::from([("c1".to_string(), category)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_changed_open_questions() {
+        let original = regrade_quiz("Київ", QuizMatchStrategy::Exact);
+        assert!(original.changed_open_questions(&original).is_empty());
+
+        let quiz = regrade_quiz("м. Київ", QuizMatchStrategy::Exact);
+        assert_eq!(
+            quiz.changed_open_questions(&original),
+            HashSet::from(["q1".to_string()])
+        );
+        let quiz = regrade_quiz("Київ", QuizMatchStrategy::Fuzzy);
+        assert_eq!(
+            quiz.changed_open_questions(&original),
+            HashSet::from(["q1".to_string()])
+        );
+
+        let mut quiz = original.clone();
+        let closed = &mut quiz.categories["c1"].questions["q2"];
+        closed.answers["a1"].correct = false;
+        closed.answers["a2"].correct = true;
+        assert!(quiz.changed_open_questions(&original).is_empty());
+
+        let mut original = original;
+        original.categories["c1"].questions.shift_remove("q1");
+        assert_eq!(
+            quiz.changed_open_questions(&original),
+            HashSet::from(["q1".to_string()])
+        );
+    }
+
+    /// Draw sizes for every rotation of the questions, forwards and backwards.
+    fn draw_sizes(category: &QuizCategory, rules: &[QuizAssemblyRule]) -> Vec<usize> {
+        let len = category.questions.len();
+        let mut sizes = Vec::new();
+        for shift in 0..len {
+            for reverse in [false, true] {
+                let ids = category.assemble(rules, |questions| {
+                    questions.rotate_left(shift);
+                    if reverse {
+                        questions.reverse();
+                    }
+                });
+                let unique = ids.iter().collect::<HashSet<_>>();
+                assert_eq!(unique.len(), ids.len());
+                sizes.push(ids.len());
+            }
+        }
+        sizes
+    }
+
+    #[test]
+    fn test_assemble_matches_count_whatever_the_order() {
+        let category = category(&[("q1", "x y"), ("q2", "x z"), ("q3", "y")]);
+        let rules = [rule("x", 1), rule("y", 1), rule("z", 1)];
+        let count = category.assemble(&rules, |_| {}).len();
+        assert_eq!(count, 3);
+        assert!(draw_sizes(&category, &rules).iter().all(|size| *size == 3));
+    }
+
+    #[test]
+    fn test_assemble_respects_rule_counts() {
+        let category = category(&[
+            ("q1", "x"),
+            ("q2", "x y"),
+            ("q3", "y"),
+            ("q4", "x y"),
+            ("q5", "z"),
+        ]);
+        let rules = [rule("x", 2), rule("y", 1)];
+        let ids = category.assemble(&rules, |_| {});
+        assert_eq!(ids.len(), 3);
+        assert!(!ids.contains(&&"q5".to_string()));
+        assert!(draw_sizes(&category, &rules).iter().all(|size| *size == 3));
+
+        let rules = [rule("x", 5)];
+        assert_eq!(category.assemble(&rules, |_| {}).len(), 3);
+        let rules = [rule("w", 2)];
+        assert!(category.assemble(&rules, |_| {}).is_empty());
+    }
+}
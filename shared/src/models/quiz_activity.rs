@@ -1,4 +1,4 @@
-use crate::utils::*;
+use crate::{models::*, utils::*};
 use ::indexmap::IndexMap;
 use ::serde::{Deserialize, Serialize};
 use ::serde_repr::{Deserialize_repr, Serialize_repr};
@@ -16,6 +16,14 @@ pub struct QuizActivityDetails {
     pub grade: usize,
     pub score: usize,
     pub can_take: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    #[serde(default)]
+    pub practice: bool,
+    #[serde(default)]
+    pub practice_attempts: usize,
+    #[serde(default)]
+    pub review: QuizReviewMode,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -25,6 +33,10 @@ pub struct QuizActivity {
     pub quiz: String,
     pub duration: i64,
     pub student: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub language: String,
+    #[serde(default)]
+    pub practice: bool,
     #[serde(
         default,
         skip_serializing_if = "IndexMap::is_empty",
@@ -65,3 +77,50 @@ pub struct QuizActivityAnswer {
     pub name: String,
     pub img: bool,
 }
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizActivityFeedback {
+    pub question: String,
+    pub correct: bool,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub answers: HashSet<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reference: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub explanation: String,
+}
+
+/// Student's view of the last attempt, trimmed to what the task review mode allows.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizAttemptReview {
+    pub quiz: String,
+    pub quiz_name: String,
+    pub student_name: String,
+    pub mode: QuizReviewMode,
+    pub grade: usize,
+    pub score: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<QuizAttemptCategory>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub questions: Vec<QuizAttemptQuestion>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizAttemptCategory {
+    pub name: String,
+    pub score: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizAttemptQuestion {
+    pub id: String,
+    pub category: String,
+    pub name: String,
+    pub img: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub answered: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub correct: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub explanation: String,
+}
@@ -1,10 +1,20 @@
 use crate::{models::*, utils::*};
 use ::indexmap::IndexMap;
 use ::serde::{Deserialize, Serialize};
-use ::std::collections::{HashMap, HashSet};
+use ::chrono::Utc;
+use ::std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// Format version of newly created quiz records. Records without one keep open results as
+/// `answer|similarity|passed` markers inside the answer grid.
+pub const QUIZ_RECORD_VERSION: u32 = 1;
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct QuizRecord {
+    #[serde(default)]
+    pub version: u32,
     pub id: String,
     pub workspace: String,
     pub quiz: String,
@@ -14,6 +24,8 @@ pub struct QuizRecord {
     pub duration: i64,
     pub attempts: usize,
     pub grade: QuizGrade,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
     #[serde(
         default,
         skip_serializing_if = "IndexMap::is_empty",
@@ -28,6 +40,16 @@ pub struct QuizRecord {
     pub students: IndexMap<String, QuizRecordStudent>,
     pub answers: Grid<HashMap<String, HashSet<String>>>,
     pub results: Grid<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<QuizVariant>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<Quiz>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regrades: Vec<QuizRegrade>,
+    #[serde(default)]
+    pub practice: bool,
+    #[serde(default)]
+    pub review: QuizReviewSettings,
     pub metadata: Metadata,
 }
 
@@ -43,11 +65,251 @@ impl QuizRecord {
             duration: self.duration,
             students: self.students.clone(),
             results: self.results.clone(),
+            practice: self.practice,
+            review: self.review.clone(),
             metadata: self.metadata.clone(),
             ..Default::default()
         }
     }
 
+    pub fn assets(&self) -> &str {
+        self.snapshot.as_ref().map_or(&self.quiz, |s| &s.id)
+    }
+
+    /// Source quiz of the category, categories of single-quiz records carry none.
+    pub fn category_source<'a>(&'a self, category: &'a QuizRecordCategory) -> &'a str {
+        if category.quiz.is_empty() {
+            &self.quiz
+        } else {
+            &category.quiz
+        }
+    }
+
+    /// Distinct source quizzes in category order.
+    pub fn sources(&self) -> Vec<&str> {
+        let mut sources = Vec::new();
+        for category in self.categories.values() {
+            let source = self.category_source(category);
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        sources
+    }
+
+    pub fn is_composite(&self) -> bool {
+        self.sources().len() > 1
+    }
+
+    /// Records with the same layout have the same categories, in the same order, drawn from the
+    /// same quizzes, so their grids can be stacked. Practice records never mix with graded ones.
+    pub fn same_layout(&self, other: &QuizRecord) -> bool {
+        self.practice == other.practice
+            && self.categories.len() == other.categories.len()
+            && self
+                .categories
+                .values()
+                .zip(other.categories.values())
+                .all(|(a, b)| a.id == b.id && self.category_source(a) == other.category_source(b))
+    }
+
+    /// Unions the snapshot of `other` into this one, so every question either record served stays
+    /// resolvable. Records with and without a snapshot don't mix and are left untouched.
+    pub fn merge_snapshot(&mut self, other: &QuizRecord) -> bool {
+        match (self.snapshot.as_mut(), other.snapshot.as_ref()) {
+            (None, None) => true,
+            (Some(snapshot), Some(other)) => {
+                for (id, category) in other.categories.iter() {
+                    let merged = snapshot
+                        .categories
+                        .entry(id.clone())
+                        .or_insert_with(|| category.to_base());
+                    for (question_id, question) in category.questions.iter() {
+                        merged
+                            .questions
+                            .entry(question_id.clone())
+                            .or_insert_with(|| question.clone());
+                    }
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn apply_entry(&mut self, entry: QuizRecordEntry) {
+        let Some(idx) = self.students.get_index_of(&entry.student) else {
+            return;
+        };
+        if let Some(student) = self.students.get_mut(&entry.student) {
+            student.attempts = entry.attempts;
+            student.grade = entry.grade;
+            student.served = entry.served;
+            student.open = entry.open;
+            student.language = entry.language;
+        }
+        self.answers.set_row(idx, entry.answers);
+        self.results.set_row(idx, entry.results);
+    }
+
+    /// Moves the open results of records saved before [`QUIZ_RECORD_VERSION`] out of the answer
+    /// grid, once. Returns `true` when the record changed and has to be saved.
+    pub fn migrate_open_markers(&mut self) -> bool {
+        if self.version >= QUIZ_RECORD_VERSION {
+            return false;
+        }
+        self.version = QUIZ_RECORD_VERSION;
+        let categories = self.categories.keys().cloned().collect::<Vec<_>>();
+        for (idx, student) in self.students.values_mut().enumerate() {
+            for (col, category) in categories.iter().enumerate() {
+                let Some(cell) = self.answers.get_mut(idx, col) else {
+                    continue;
+                };
+                for (question_id, answered) in cell.iter_mut() {
+                    // Legacy open cells always held the answer and a single marker.
+                    if answered.len() != 2 {
+                        continue;
+                    }
+                    let mut markers = answered
+                        .iter()
+                        .filter_map(|s| Some((s.clone(), parse_open_marker(s)?)))
+                        .collect::<Vec<_>>();
+                    if markers.len() != 1 {
+                        continue;
+                    }
+                    let (marker, (matched, similarity, passed)) = markers.remove(0);
+                    answered.remove(&marker);
+                    student.open.insert(
+                        question_id.clone(),
+                        QuizOpenResult {
+                            category: category.clone(),
+                            answer: answered.iter().next().cloned().unwrap_or_default(),
+                            matched,
+                            similarity,
+                            passed,
+                            review: None,
+                        },
+                    );
+                }
+            }
+        }
+        true
+    }
+
+    pub fn progress(&self) -> usize {
+        let total = self.students.values().filter(|s| !s.excused).count();
+        if total == 0 {
+            return 0;
+        }
+        let count = self
+            .students
+            .values()
+            .filter(|s| !s.excused && (s.final_grade() > 0 || !s.practice.is_empty()))
+            .count();
+        (count * 100) / total
+    }
+
+    /// Attempts allowed for the student, `None` when the quiz is unlimited.
+    pub fn attempts_limit(&self, student: &QuizRecordStudent) -> Option<usize> {
+        (self.attempts > 0).then(|| self.attempts + student.extra_attempts)
+    }
+
+    /// Review mode for the student right now. Correct answers stay hidden
+    /// while the task is open and the student can still make another attempt.
+    pub fn review_mode(&self, student: &QuizRecordStudent, closed: bool) -> QuizReviewMode {
+        let mode = self.review.effective(closed);
+        let attempts_left = self
+            .attempts_limit(student)
+            .is_none_or(|limit| limit > student.attempts);
+        if mode == QuizReviewMode::Answers && !closed && attempts_left {
+            QuizReviewMode::Categories
+        } else {
+            mode
+        }
+    }
+
+    pub fn duration_for(&self, student: &QuizRecordStudent) -> i64 {
+        self.duration + student.extra_time
+    }
+
+    /// Category scores of the student at `idx`, with overridden cells in place of computed ones.
+    pub fn final_results(&self, idx: usize) -> Vec<usize> {
+        let student = self.students.get_index(idx).map(|(_, s)| s);
+        self.categories
+            .keys()
+            .enumerate()
+            .map(|(col, category)| {
+                student
+                    .and_then(|s| s.result_override(category))
+                    .unwrap_or(*self.results.get(idx, col))
+            })
+            .collect()
+    }
+
+    pub fn add_student(&mut self, student: QuizRecordStudent) -> bool {
+        if self.students.contains_key(&student.id) {
+            return false;
+        }
+        self.students.insert(student.id.clone(), student);
+        self.answers.push_row(HashMap::new());
+        self.results.push_row(0);
+        true
+    }
+
+    pub fn remove_student(&mut self, student_id: &str) -> Option<QuizRecordStudent> {
+        let (idx, _, student) = self.students.shift_remove_full(student_id)?;
+        self.answers.remove_row(idx);
+        self.results.remove_row(idx);
+        Some(student)
+    }
+
+    /// Copies the record keeping only `students`, in their current order, with their answers and
+    /// results rows.
+    pub fn retain_students(&self, students: &HashSet<String>) -> Self {
+        let mut record = Self {
+            students: IndexMap::with_capacity(students.len()),
+            answers: Grid::new(0, self.categories.len(), HashMap::new()),
+            results: Grid::new(0, self.categories.len(), 0),
+            ..self.clone()
+        };
+        for (idx, student) in self.students.values().enumerate() {
+            if !students.contains(&student.id) {
+                continue;
+            }
+            record.students.insert(student.id.clone(), student.clone());
+            record.answers.push_row(HashMap::new());
+            record.results.push_row(0);
+            let row = record.students.len() - 1;
+            record
+                .answers
+                .set_row(row, self.answers.get_row(idx).into_iter().cloned().collect());
+            record
+                .results
+                .set_row(row, self.results.get_row(idx).into_iter().copied().collect());
+        }
+        for regrade in record.regrades.iter_mut() {
+            regrade.changes.retain(|c| students.contains(&c.student));
+        }
+        record
+    }
+
+    pub fn reset_student(&mut self, student_id: &str) {
+        let Some(idx) = self.students.get_index_of(student_id) else {
+            return;
+        };
+        if let Some(student) = self.students.get_mut(student_id) {
+            student.attempts = 0;
+            student.grade = 0;
+            student.served.clear();
+            student.open.clear();
+            student.language = None;
+            student.grade_override = None;
+            student.practice.clear();
+        }
+        self.answers.fill_row(idx, HashMap::new());
+        self.results.fill_row(idx, 0);
+    }
+
     pub fn to_entity(&self) -> Entity {
         Entity {
             id: self.id.clone(),
@@ -63,8 +325,14 @@ impl QuizRecord {
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct QuizRecordCategory {
     pub id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub quiz: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub quiz_name: String,
     pub name: String,
     pub count: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<QuizAssemblyRule>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -73,6 +341,334 @@ pub struct QuizRecordStudent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rank: Option<String>,
     pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub node: String,
     pub attempts: usize,
     pub grade: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<i64>,
+    #[serde(default)]
+    pub extra_attempts: usize,
+    #[serde(default)]
+    pub extra_time: i64,
+    #[serde(default)]
+    pub excused: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub served: Vec<usize>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub open: HashMap<String, QuizOpenResult>,
+    /// Language of the attempt the open results were scored in, unknown for older records.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grade_override: Option<QuizGradeOverride>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub practice: Vec<QuizPracticeAttempt>,
+}
+
+impl QuizRecordStudent {
+    pub fn is_past_deadline(&self, grace: i64) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Utc::now().timestamp() - grace >= deadline)
+    }
+
+    /// Grade set by hand when overridden, the automatic one otherwise.
+    pub fn final_grade(&self) -> usize {
+        self.grade_override
+            .as_ref()
+            .and_then(|o| o.grade)
+            .unwrap_or(self.grade)
+    }
+
+    pub fn result_override(&self, category: &str) -> Option<usize> {
+        self.grade_override
+            .as_ref()
+            .and_then(|o| o.results.get(category).copied())
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizGradeOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grade: Option<usize>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub results: HashMap<String, usize>,
+    pub reason: String,
+    pub overridden_by: String,
+    pub overridden_at: i64,
+}
+
+impl QuizGradeOverride {
+    pub fn new(
+        grade: Option<usize>,
+        results: HashMap<String, usize>,
+        reason: impl Into<String>,
+        overridden_by: impl Into<String>,
+    ) -> Self {
+        Self {
+            grade,
+            results,
+            reason: reason.into(),
+            overridden_by: overridden_by.into(),
+            overridden_at: Utc::now().timestamp(),
+        }
+    }
+}
+
+/// What students may see of their own attempt once it is submitted.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizReviewSettings {
+    #[serde(default)]
+    pub mode: QuizReviewMode,
+    #[serde(default)]
+    pub after_close: bool,
+}
+
+impl QuizReviewSettings {
+    /// Mode in effect right now, `Hidden` while the task still has to close.
+    pub fn effective(&self, closed: bool) -> QuizReviewMode {
+        if self.after_close && !closed {
+            QuizReviewMode::Hidden
+        } else {
+            self.mode
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuizReviewMode {
+    Hidden,
+    #[default]
+    Score,
+    Categories,
+    Answers,
+}
+
+impl QuizReviewMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuizReviewMode::Hidden => "hidden",
+            QuizReviewMode::Score => "score",
+            QuizReviewMode::Categories => "categories",
+            QuizReviewMode::Answers => "answers",
+        }
+    }
+}
+
+impl From<String> for QuizReviewMode {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "hidden" => QuizReviewMode::Hidden,
+            "categories" => QuizReviewMode::Categories,
+            "answers" => QuizReviewMode::Answers,
+            _ => QuizReviewMode::Score,
+        }
+    }
+}
+
+impl fmt::Display for QuizReviewMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Practice runs are kept apart from graded attempts and never change the grade.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizPracticeAttempt {
+    pub score: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub results: Vec<usize>,
+    pub submitted_at: i64,
+}
+
+impl QuizPracticeAttempt {
+    pub fn new(results: Vec<usize>) -> Self {
+        let score = if results.is_empty() {
+            0
+        } else {
+            results.iter().sum::<usize>() / results.len()
+        };
+        Self {
+            score,
+            results,
+            submitted_at: Utc::now().timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizOpenResult {
+    pub category: String,
+    pub answer: String,
+    pub matched: String,
+    pub similarity: usize,
+    pub passed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<QuizOpenReview>,
+}
+
+impl QuizOpenResult {
+    pub fn is_passed(&self) -> bool {
+        self.review.as_ref().map_or(self.passed, |r| r.accepted)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizOpenReview {
+    pub accepted: bool,
+    pub reviewed_by: String,
+    pub reviewed_at: i64,
+}
+
+impl QuizOpenReview {
+    pub fn new(accepted: bool, reviewed_by: impl Into<String>) -> Self {
+        Self {
+            accepted,
+            reviewed_by: reviewed_by.into(),
+            reviewed_at: Utc::now().timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizRegrade {
+    pub regraded_by: String,
+    pub regraded_at: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<QuizRegradeChange>,
+}
+
+impl QuizRegrade {
+    pub fn new(regraded_by: impl Into<String>, changes: Vec<QuizRegradeChange>) -> Self {
+        Self {
+            regraded_by: regraded_by.into(),
+            regraded_at: Utc::now().timestamp(),
+            changes,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizRegradeChange {
+    pub student: String,
+    pub student_name: String,
+    pub grade_before: usize,
+    pub grade_after: usize,
+    pub results_before: Vec<usize>,
+    pub results_after: Vec<usize>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizRecordEntry {
+    pub student: String,
+    pub attempts: usize,
+    pub grade: usize,
+    pub answers: Vec<HashMap<String, HashSet<String>>>,
+    pub results: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub served: Vec<usize>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub open: HashMap<String, QuizOpenResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+fn parse_open_marker(value: &str) -> Option<(String, usize, bool)> {
+    let mut parts = value.splitn(3, '|');
+    let id = parts.next().filter(|id| !id.is_empty())?;
+    let similarity = parts
+        .next()?
+        .parse::<usize>()
+        .ok()
+        .filter(|similarity| *similarity <= 100)?;
+    let passed = match parts.next()? {
+        "1" => true,
+        "0" => false,
+        _ => return None,
+    };
+    Some((id.to_string(), similarity, passed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_record(version: u32, answered: &[&str]) -> QuizRecord {
+        let mut record = QuizRecord {
+            version,
+            categories: IndexMap::from([("c1".to_string(), QuizRecordCategory::default())]),
+            students: IndexMap::from([("s1".to_string(), QuizRecordStudent::default())]),
+            answers: Grid::new(1, 1, HashMap::new()),
+            results: Grid::new(1, 1, 0),
+            ..Default::default()
+        };
+        record.answers.set(
+            0,
+            0,
+            HashMap::from([(
+                "q1".to_string(),
+                answered.iter().map(|s| s.to_string()).collect(),
+            )]),
+        );
+        record
+    }
+
+    #[test]
+    fn test_migrate_legacy_open_marker() {
+        let mut record = open_record(0, &["Київ", "a1|87|1"]);
+        assert!(record.migrate_open_markers());
+        assert_eq!(record.version, QUIZ_RECORD_VERSION);
+        assert_eq!(
+            record.answers.get(0, 0)["q1"],
+            HashSet::from(["Київ".to_string()])
+        );
+        let open = &record.students["s1"].open["q1"];
+        assert_eq!(open.category, "c1");
+        assert_eq!(open.answer, "Київ");
+        assert_eq!(open.matched, "a1");
+        assert_eq!((open.similarity, open.passed), (87, true));
+        assert!(!record.migrate_open_markers());
+    }
+
+    #[test]
+    fn test_migrate_keeps_answers_shaped_like_markers() {
+        let mut record = open_record(QUIZ_RECORD_VERSION, &["text|5|1", "a1|87|1"]);
+        assert!(!record.migrate_open_markers());
+        assert_eq!(record.answers.get(0, 0)["q1"].len(), 2);
+
+        let mut record = open_record(0, &["text|5|1"]);
+        assert!(record.migrate_open_markers());
+        assert_eq!(
+            record.answers.get(0, 0)["q1"],
+            HashSet::from(["text|5|1".to_string()])
+        );
+        assert!(record.students["s1"].open.is_empty());
+    }
+
+    #[test]
+    fn test_parse_open_marker() {
+        assert_eq!(
+            parse_open_marker("a1|100|0"),
+            Some(("a1".to_string(), 100, false))
+        );
+        assert_eq!(parse_open_marker("a1|101|1"), None);
+        assert_eq!(parse_open_marker("|50|1"), None);
+        assert_eq!(parse_open_marker("a1|50|yes"), None);
+        assert_eq!(parse_open_marker("a1|50"), None);
+    }
+
+    #[test]
+    fn test_is_past_deadline() {
+        let now = Utc::now().timestamp();
+        let student = |deadline| QuizRecordStudent {
+            deadline,
+            ..Default::default()
+        };
+        assert!(!student(None).is_past_deadline(0));
+        assert!(!student(Some(now + 3600)).is_past_deadline(0));
+        assert!(student(Some(now - 3600)).is_past_deadline(0));
+        assert!(!student(Some(now - 60)).is_past_deadline(120));
+        assert!(student(Some(now - 3600)).is_past_deadline(120));
+    }
 }
@@ -0,0 +1,144 @@
+use crate::models::*;
+use ::serde::{Deserialize, Serialize};
+use ::std::collections::{HashMap, HashSet};
+
+const ANSWER_SHEET_PREFIX: &str = "maes-sheet";
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizVariant {
+    pub id: usize,
+    pub seed: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub questions: Vec<QuizActivityQuestion>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizVariantSheet {
+    pub task: String,
+    pub variant: usize,
+    pub student: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub answers: HashMap<String, HashSet<String>>,
+}
+
+impl QuizVariantSheet {
+    pub fn code(&self) -> String {
+        format!(
+            "{ANSWER_SHEET_PREFIX}|{task}|{variant}|{student}",
+            task = self.task,
+            variant = self.variant,
+            student = self.student
+        )
+    }
+
+    pub fn from_code(code: impl AsRef<str>) -> Option<Self> {
+        let mut parts = code.as_ref().split('|');
+        if parts.next()? != ANSWER_SHEET_PREFIX {
+            return None;
+        }
+        let task = parts.next()?.to_string();
+        let variant = parts.next()?.parse::<usize>().ok()?;
+        let student = parts.next()?.to_string();
+        if task.is_empty() || student.is_empty() || parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            task,
+            variant,
+            student,
+            answers: HashMap::new(),
+        })
+    }
+}
+
+pub struct AnswerSheetLayout;
+
+// All sizes are in millimetres, measured from the top-left corner of the QR symbol,
+// so a scanned sheet can be mapped back regardless of print scale or rotation.
+impl AnswerSheetLayout {
+    pub const QR_SIZE: f32 = 30.0;
+    pub const GRID_TOP: f32 = 40.0;
+    pub const ROWS: usize = 25;
+    pub const BLOCKS: usize = 2;
+    pub const ROW_HEIGHT: f32 = 7.0;
+    pub const LABEL_WIDTH: f32 = 10.0;
+    pub const COLUMN_WIDTH: f32 = 7.5;
+    pub const BLOCK_WIDTH: f32 = 90.0;
+    pub const BUBBLE_SIZE: f32 = 5.0;
+    pub const MAX_ANSWERS: usize = 10;
+
+    pub const fn capacity() -> usize {
+        Self::ROWS * Self::BLOCKS
+    }
+
+    pub fn label(question: usize) -> (f32, f32) {
+        let (block, row) = (question / Self::ROWS, question % Self::ROWS);
+        (
+            block as f32 * Self::BLOCK_WIDTH,
+            Self::GRID_TOP + row as f32 * Self::ROW_HEIGHT,
+        )
+    }
+
+    pub fn bubble(question: usize, answer: usize) -> (f32, f32) {
+        let (left, top) = Self::label(question);
+        (
+            left + Self::LABEL_WIDTH + answer as f32 * Self::COLUMN_WIDTH + Self::BUBBLE_SIZE / 2.0,
+            top + Self::BUBBLE_SIZE / 2.0,
+        )
+    }
+
+    pub fn answer_letter(answer: usize) -> char {
+        (b'A' + (answer as u8)) as char
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sheet_code_round_trip() {
+        let sheet = QuizVariantSheet {
+            task: "t1".to_string(),
+            variant: 7,
+            student: "s1".to_string(),
+            answers: HashMap::from([("q1".to_string(), HashSet::from(["a1".to_string()]))]),
+        };
+        assert_eq!(sheet.code(), "maes-sheet|t1|7|s1");
+        let parsed = QuizVariantSheet::from_code(sheet.code()).unwrap();
+        assert_eq!((parsed.task.as_str(), parsed.variant), ("t1", 7));
+        assert_eq!(parsed.student, "s1");
+        assert!(parsed.answers.is_empty());
+    }
+
+    #[test]
+    fn test_sheet_code_rejects_foreign_payloads() {
+        for code in [
+            "",
+            "https://example.com",
+            "other|t1|7|s1",
+            "maes-sheet|t1|seven|s1",
+            "maes-sheet|t1|-1|s1",
+            "maes-sheet|t1|7",
+            "maes-sheet||7|s1",
+            "maes-sheet|t1|7|",
+            "maes-sheet|t1|7|s1|extra",
+        ] {
+            assert_eq!(QuizVariantSheet::from_code(code), None, "{code}");
+        }
+    }
+
+    #[test]
+    fn test_layout_fits_block() {
+        let (left, _) = AnswerSheetLayout::label(AnswerSheetLayout::ROWS);
+        assert_eq!(left, AnswerSheetLayout::BLOCK_WIDTH);
+        let (x, _) = AnswerSheetLayout::bubble(0, AnswerSheetLayout::MAX_ANSWERS - 1);
+        assert!(x + AnswerSheetLayout::BUBBLE_SIZE / 2.0 <= AnswerSheetLayout::BLOCK_WIDTH);
+        assert_eq!(AnswerSheetLayout::answer_letter(0), 'A');
+        assert_eq!(
+            AnswerSheetLayout::answer_letter(AnswerSheetLayout::MAX_ANSWERS - 1),
+            'J'
+        );
+    }
+}
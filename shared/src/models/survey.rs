@@ -1,6 +1,7 @@
 use crate::{models::*, utils::*};
 use ::indexmap::IndexMap;
 use ::serde::{Deserialize, Serialize};
+use ::std::fmt;
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Survey {
@@ -14,9 +15,14 @@ pub struct Survey {
         with = "indexmap_as_vec"
     )]
     pub categories: IndexMap<String, SurveyCategory>,
+    /// Smallest segment a report may break results down by.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_segment_size: Option<usize>,
     pub metadata: Metadata,
 }
 
+pub const DEFAULT_MIN_SEGMENT_SIZE: usize = 5;
+
 impl Survey {
     pub fn to_base(&self) -> Self {
         Self {
@@ -25,6 +31,7 @@ impl Survey {
             workspace: self.workspace.clone(),
             node: self.node.clone(),
             categories: Default::default(),
+            min_segment_size: self.min_segment_size,
             metadata: self.metadata.clone(),
         }
     }
@@ -58,6 +65,22 @@ pub struct SurveyCategory {
         with = "indexmap_as_vec"
     )]
     pub answers: IndexMap<String, SurveyCategoryItem>,
+    #[serde(default)]
+    pub kind: SurveyCategoryKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<SurveyCondition>,
+    #[serde(default)]
+    pub required: bool,
+    /// Upper limit of checked options for categories without answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_selections: Option<usize>,
+    /// Answers of a single choice category split the results into segments.
+    #[serde(default)]
+    pub segment: bool,
 }
 
 impl SurveyCategory {
@@ -68,10 +91,76 @@ impl SurveyCategory {
             order: self.order,
             questions: Default::default(),
             answers: Default::default(),
+            kind: self.kind,
+            min: self.min,
+            max: self.max,
+            conditions: self.conditions.clone(),
+            required: self.required,
+            max_selections: self.max_selections,
+            segment: self.segment,
+        }
+    }
+}
+
+/// `Choice` keeps the layout inferred from questions and answers; the other
+/// kinds ask for a typed response to every question.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SurveyCategoryKind {
+    #[default]
+    Choice,
+    Text,
+    Numeric,
+    Scale,
+    Ranking,
+}
+
+impl SurveyCategoryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SurveyCategoryKind::Choice => "choice",
+            SurveyCategoryKind::Text => "text",
+            SurveyCategoryKind::Numeric => "numeric",
+            SurveyCategoryKind::Scale => "scale",
+            SurveyCategoryKind::Ranking => "ranking",
+        }
+    }
+
+    /// Scale bounds fall back to a classic five-point Likert scale.
+    pub fn scale_range(min: Option<i64>, max: Option<i64>) -> (i64, i64) {
+        let min = min.unwrap_or(1);
+        let max = max.unwrap_or(5).clamp(min + 1, min + 10);
+        (min, max)
+    }
+}
+
+impl From<String> for SurveyCategoryKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "text" => SurveyCategoryKind::Text,
+            "numeric" => SurveyCategoryKind::Numeric,
+            "scale" => SurveyCategoryKind::Scale,
+            "ranking" => SurveyCategoryKind::Ranking,
+            _ => SurveyCategoryKind::Choice,
         }
     }
 }
 
+impl fmt::Display for SurveyCategoryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Shows a category only when `option` was chosen in an earlier choice
+/// category. `option` is an answer id, or a question id when the category
+/// has no answers.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct SurveyCondition {
+    pub category: String,
+    pub option: String,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct SurveyCategoryItem {
     pub id: String,
@@ -1,6 +1,7 @@
 use crate::{models::*, utils::*};
 use ::indexmap::IndexMap;
 use ::serde::{Deserialize, Serialize};
+use ::std::collections::HashSet;
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct SurveyRecord {
@@ -17,6 +18,10 @@ pub struct SurveyRecord {
         with = "indexmap_as_vec"
     )]
     pub categories: IndexMap<String, SurveyRecordCategory>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<SurveySegment>,
+    #[serde(default)]
+    pub min_segment_size: usize,
     pub metadata: Metadata,
 }
 
@@ -31,6 +36,8 @@ impl SurveyRecord {
             path: self.path.clone(),
             total: self.total,
             categories: Default::default(),
+            segments: Default::default(),
+            min_segment_size: self.min_segment_size,
             metadata: self.metadata.clone(),
         }
     }
@@ -45,6 +52,119 @@ impl SurveyRecord {
             metadata: self.metadata.clone(),
         }
     }    
+
+    /// Walks the categories in order and returns the ones whose conditions are
+    /// met by the answers in `submission`. A condition can only refer to a
+    /// category that is itself visible and comes earlier.
+    pub fn visible_categories(&self, submission: &SurveyRecord) -> HashSet<String> {
+        let mut visible = HashSet::with_capacity(self.categories.len());
+        for category in self.categories.values() {
+            let shown = category.conditions.iter().all(|condition| {
+                visible.contains(&condition.category)
+                    && submission
+                        .categories
+                        .get(&condition.category)
+                        .is_some_and(|c| c.is_selected(&condition.option))
+            });
+            if shown {
+                visible.insert(category.id.clone());
+            }
+        }
+        visible
+    }
+
+    /// Results of the respondents who chose `option` in the segment category.
+    /// Returns `None` when the segment is smaller than `min_segment_size`;
+    /// categories answered by too few of them, or not counted per segment
+    /// (free text and numbers), are left out.
+    pub fn segmented(&self, category: &str, option: &str) -> Option<Self> {
+        let threshold = self.min_segment_size.max(1);
+        let segment = self
+            .segments
+            .iter()
+            .find(|s| s.category == category && s.option == option)
+            .filter(|s| s.total >= threshold)?;
+
+        let mut record = self.clone();
+        record.segments.clear();
+        record.total = segment.total;
+        record.categories.retain(|id, _| {
+            segment
+                .categories
+                .get(id)
+                .is_some_and(|c| c.total >= threshold)
+        });
+        for category in record.categories.values_mut() {
+            if let Some(counts) = segment.categories.get(&category.id) {
+                category.results = counts.results.clone();
+                category.total = counts.total;
+            }
+        }
+        Some(record)
+    }
+
+    /// Drops segments and segment categories below `min_segment_size`, so
+    /// cells small enough to identify respondents never leave the server.
+    pub fn suppress_segments(&mut self) {
+        let threshold = self.min_segment_size.max(1);
+        self.segments.retain(|s| s.total >= threshold);
+        for segment in self.segments.iter_mut() {
+            segment.categories.retain(|_, c| c.total >= threshold);
+        }
+    }
+
+    pub fn merge_segments(&mut self, other: &Self) {
+        self.min_segment_size = self.min_segment_size.max(other.min_segment_size);
+        for other_segment in other.segments.iter() {
+            let Some(segment) = self
+                .segments
+                .iter_mut()
+                .find(|s| s.category == other_segment.category && s.option == other_segment.option)
+            else {
+                self.segments.push(other_segment.clone());
+                continue;
+            };
+            segment.total += other_segment.total;
+            for other_category in other_segment.categories.values() {
+                match segment.categories.get_mut(&other_category.id) {
+                    Some(category) => {
+                        category.results.merge(&other_category.results);
+                        category.total += other_category.total;
+                    }
+                    None => {
+                        segment
+                            .categories
+                            .insert(other_category.id.clone(), other_category.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records of the same survey whose categories match in order, kind and size.
+    pub fn same_layout(&self, other: &Self) -> bool {
+        self.survey == other.survey
+            && self.categories.len() == other.categories.len()
+            && self
+                .categories
+                .values()
+                .zip(other.categories.values())
+                .all(|(a, b)| {
+                    a.id == b.id
+                        && a.kind == b.kind
+                        && a.results.rows() == b.results.rows()
+                        && a.results.cols() == b.results.cols()
+                })
+    }
+
+    /// Records created before per-category counts fall back to the overall total.
+    pub fn category_total(&self, category: &SurveyRecordCategory) -> usize {
+        if self.categories.values().all(|c| c.total == 0) {
+            self.total
+        } else {
+            category.total
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -64,4 +184,306 @@ pub struct SurveyRecordCategory {
     )]
     pub answers: IndexMap<String, SurveyCategoryItem>,
     pub results: Grid<usize>,
+    #[serde(default)]
+    pub kind: SurveyCategoryKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
+    /// Free-text responses per question, shuffled on insert so they cannot be
+    /// matched to each other or to the order of submissions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub responses: Vec<Vec<String>>,
+    /// Numeric responses per question, stored the same way as `responses`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<SurveyCondition>,
+    #[serde(default)]
+    pub required: bool,
+    /// Upper limit of checked options for categories without answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_selections: Option<usize>,
+    /// Number of submissions that were shown this category and answered it.
+    #[serde(default)]
+    pub total: usize,
+    #[serde(default)]
+    pub segment: bool,
+}
+
+impl SurveyRecordCategory {
+    pub fn selected_option(&self) -> Option<&String> {
+        self.answers.keys().find(|id| self.is_selected(id))
+    }
+
+    pub fn is_selected(&self, option: &str) -> bool {
+        if self.kind != SurveyCategoryKind::Choice {
+            return false;
+        }
+        if let Some(col) = self.answers.get_index_of(option) {
+            return (0..self.results.rows()).any(|row| *self.results.get(row, col) > 0);
+        }
+        self.answers.is_empty()
+            && self
+                .questions
+                .get_index_of(option)
+                .is_some_and(|row| *self.results.get(row, 0) > 0)
+    }
+
+    /// Every question has a response; a list of options needs at least one checked.
+    pub fn is_complete(&self) -> bool {
+        let rows = self.results.rows();
+        let row_selected = |row: usize| self.results.get_row(row).iter().any(|v| **v > 0);
+        match self.kind {
+            SurveyCategoryKind::Text => (0..rows).all(|row| {
+                self.responses
+                    .get(row)
+                    .and_then(|r| r.first())
+                    .is_some_and(|t| !t.trim().is_empty())
+            }),
+            SurveyCategoryKind::Numeric => {
+                (0..rows).all(|row| self.values.get(row).is_some_and(|v| !v.is_empty()))
+            }
+            SurveyCategoryKind::Choice if self.answers.is_empty() => (0..rows).any(row_selected),
+            _ => (0..rows).all(row_selected),
+        }
+    }
+
+    pub fn selected_count(&self) -> usize {
+        (0..self.results.rows())
+            .map(|row| self.results.get_row(row).iter().filter(|v| ***v > 0).count())
+            .sum()
+    }
+
+    pub fn is_answered(&self) -> bool {
+        match self.kind {
+            SurveyCategoryKind::Text => self
+                .responses
+                .iter()
+                .any(|r| r.iter().any(|t| !t.trim().is_empty())),
+            SurveyCategoryKind::Numeric => self.values.iter().any(|v| !v.is_empty()),
+            _ => (0..self.results.rows())
+                .any(|row| self.results.get_row(row).iter().any(|v| **v > 0)),
+        }
+    }
+
+    /// Rows of `results` count answered questions for text and numeric
+    /// categories, scale points for scales and rank positions for rankings.
+    pub fn stats(&self, question: usize) -> SurveyStats {
+        match self.kind {
+            SurveyCategoryKind::Numeric => {
+                SurveyStats::new(self.values.get(question).cloned().unwrap_or_default())
+            }
+            SurveyCategoryKind::Scale => {
+                let (min, _) = SurveyCategoryKind::scale_range(self.min, self.max);
+                self.histogram_stats(question, min as f64)
+            }
+            SurveyCategoryKind::Ranking => self.histogram_stats(question, 1.0),
+            _ => SurveyStats::default(),
+        }
+    }
+
+    fn histogram_stats(&self, question: usize, offset: f64) -> SurveyStats {
+        if question >= self.results.rows() {
+            return SurveyStats::default();
+        }
+        let values = self
+            .results
+            .get_row(question)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(col, &count)| std::iter::repeat_n(offset + col as f64, count))
+            .collect();
+        SurveyStats::new(values)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SurveyStats {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+}
+
+impl SurveyStats {
+    pub fn new(mut values: Vec<f64>) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+
+        let count = values.len();
+        let mean = values.iter().sum::<f64>() / count as f64;
+        let median = if count % 2 == 0 {
+            (values[count / 2 - 1] + values[count / 2]) / 2.0
+        } else {
+            values[count / 2]
+        };
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count as f64;
+
+        Self {
+            count,
+            mean,
+            median,
+            std_dev: variance.sqrt(),
+        }
+    }
+}
+
+/// Cross-tabulated counts of the respondents who chose `option` in the
+/// segment category `category`.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SurveySegment {
+    pub category: String,
+    pub option: String,
+    pub total: usize,
+    #[serde(
+        default,
+        skip_serializing_if = "IndexMap::is_empty",
+        with = "indexmap_as_vec"
+    )]
+    pub categories: IndexMap<String, SurveySegmentCategory>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SurveySegmentCategory {
+    pub id: String,
+    pub total: usize,
+    pub results: Grid<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn choice(id: &str, options: &[&str], conditions: &[(&str, &str)]) -> SurveyRecordCategory {
+        let item = |id: &str| {
+            let item = SurveyCategoryItem {
+                id: id.to_string(),
+                ..Default::default()
+            };
+            (id.to_string(), item)
+        };
+        SurveyRecordCategory {
+            id: id.to_string(),
+            questions: IndexMap::from([item("q")]),
+            answers: options.iter().copied().map(item).collect(),
+            results: Grid::new(1, options.len(), 0),
+            conditions: conditions
+                .iter()
+                .map(|(category, option)| SurveyCondition {
+                    category: category.to_string(),
+                    option: option.to_string(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn survey_record(categories: Vec<SurveyRecordCategory>) -> SurveyRecord {
+        SurveyRecord {
+            categories: categories.into_iter().map(|c| (c.id.clone(), c)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn submission(record: &SurveyRecord, selected: &[(&str, &str)]) -> SurveyRecord {
+        let mut submission = record.clone();
+        for (category, option) in selected {
+            let category = &mut submission.categories[*category];
+            let col = category.answers.get_index_of(*option).unwrap();
+            category.results.set(0, col, 1);
+        }
+        submission
+    }
+
+    #[test]
+    fn test_visible_categories_follow_earlier_answers() {
+        let record = survey_record(vec![
+            choice("c1", &["yes", "no"], &[]),
+            choice("c2", &["a", "b"], &[("c1", "yes")]),
+            choice("c3", &["x"], &[("c2", "a")]),
+            choice("c4", &["x"], &[("c5", "x")]),
+            choice("c5", &["x"], &[]),
+        ]);
+
+        let answers = submission(&record, &[("c1", "yes"), ("c2", "a"), ("c5", "x")]);
+        let expected = ["c1", "c2", "c3", "c5"].map(String::from);
+        assert_eq!(record.visible_categories(&answers), HashSet::from(expected));
+
+        let answers = submission(&record, &[("c1", "no"), ("c2", "a"), ("c5", "x")]);
+        let expected = ["c1", "c5"].map(String::from);
+        assert_eq!(record.visible_categories(&answers), HashSet::from(expected));
+    }
+
+    #[test]
+    fn test_selection_counts() {
+        let mut category = choice("c1", &["a", "b", "c"], &[]);
+        assert!(!category.is_answered());
+        assert!(!category.is_complete());
+        category.results.set(0, 1, 1);
+        assert!(category.is_selected("b"));
+        assert!(!category.is_selected("a"));
+        assert_eq!(category.selected_option(), Some(&"b".to_string()));
+        assert_eq!(category.selected_count(), 1);
+        assert!(category.is_complete());
+    }
+
+    fn segment(option: &str, total: usize, categories: &[(&str, usize)]) -> SurveySegment {
+        SurveySegment {
+            category: "c1".to_string(),
+            option: option.to_string(),
+            total,
+            categories: categories
+                .iter()
+                .map(|(id, total)| {
+                    let counts = SurveySegmentCategory {
+                        id: id.to_string(),
+                        total: *total,
+                        results: Grid::new(1, 1, *total),
+                    };
+                    (id.to_string(), counts)
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_suppress_segments_at_threshold() {
+        let mut record = survey_record(vec![
+            choice("c1", &["a", "b", "c"], &[]),
+            choice("c2", &["x"], &[]),
+        ]);
+        record.min_segment_size = 3;
+        record.segments = vec![
+            segment("a", 3, &[("c1", 3), ("c2", 2)]),
+            segment("b", 2, &[("c1", 2), ("c2", 2)]),
+            segment("c", 4, &[("c1", 4), ("c2", 3)]),
+        ];
+        assert!(record.segmented("c1", "b").is_none());
+
+        record.suppress_segments();
+        let kept = record
+            .segments
+            .iter()
+            .map(|s| (s.option.as_str(), s.categories.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(kept, [("a", 1), ("c", 2)]);
+        assert!(record.segments[0].categories.contains_key("c1"));
+
+        let segmented = record.segmented("c1", "a").unwrap();
+        assert_eq!(segmented.total, 3);
+        assert_eq!(segmented.categories.keys().collect::<Vec<_>>(), ["c1"]);
+        assert!(record.segmented("c1", "b").is_none());
+    }
+
+    #[test]
+    fn test_suppress_segments_without_threshold_drops_empty() {
+        let mut record = survey_record(vec![choice("c1", &["a", "b"], &[])]);
+        record.segments = vec![segment("a", 1, &[("c1", 1)]), segment("b", 0, &[])];
+        record.suppress_segments();
+        assert_eq!(record.segments.len(), 1);
+        assert_eq!(record.segments[0].option, "a");
+    }
 }
@@ -14,9 +14,45 @@ pub struct Task {
     pub node: String,
     pub path: String,
     pub progress: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opens_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closes_at: Option<i64>,
+    #[serde(default)]
+    pub practice: bool,
     pub metadata: Metadata,
 }
 
+impl Task {
+    pub fn status(&self, now: i64) -> TaskStatus {
+        self.status_with_grace(now, 0)
+    }
+
+    /// Like [`Self::status`], but the close time is extended by `grace` seconds. The open time
+    /// is not moved, so a task never opens early or late because of the grace.
+    pub fn status_with_grace(&self, now: i64, grace: i64) -> TaskStatus {
+        if self.opens_at.is_some_and(|opens_at| now < opens_at) {
+            TaskStatus::Scheduled
+        } else if self
+            .closes_at
+            .is_some_and(|closes_at| now - grace >= closes_at)
+        {
+            TaskStatus::Closed
+        } else {
+            TaskStatus::Open
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    Scheduled,
+    #[default]
+    Open,
+    Closed,
+}
+
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Tasks {
     workspace: String,
@@ -60,11 +96,111 @@ impl Cachable for Tasks {
     }
 }
 
-#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct TaskCategory {
     pub id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub quiz: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub quiz_name: String,
     pub name: String,
     pub checked: bool,
     pub count: usize,
     pub total: usize,
-}
\ No newline at end of file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<QuizAssemblyRule>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizAssemblyRule {
+    pub count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<QuizDifficulty>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_tags: Vec<String>,
+}
+
+impl QuizAssemblyRule {
+    pub fn matches(&self, question: &QuizQuestion) -> bool {
+        self.difficulty.is_none_or(|d| d == question.difficulty)
+            && (self.tags.is_empty() || self.tags.iter().any(|t| question.tags.contains(t)))
+            && !self.exclude_tags.iter().any(|t| question.tags.contains(t))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(difficulty: QuizDifficulty, tags: &[&str]) -> QuizQuestion {
+        QuizQuestion {
+            difficulty,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn rule(
+        difficulty: Option<QuizDifficulty>,
+        tags: &[&str],
+        exclude: &[&str],
+    ) -> QuizAssemblyRule {
+        QuizAssemblyRule {
+            count: 1,
+            difficulty,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            exclude_tags: exclude.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn task(opens_at: Option<i64>, closes_at: Option<i64>) -> Task {
+        Task {
+            opens_at,
+            closes_at,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_status_without_schedule() {
+        assert_eq!(task(None, None).status(0), TaskStatus::Open);
+        assert_eq!(
+            task(None, None).status_with_grace(i64::MAX, 120),
+            TaskStatus::Open
+        );
+    }
+
+    #[test]
+    fn test_status_edges() {
+        let task = task(Some(1000), Some(2000));
+        assert_eq!(task.status(999), TaskStatus::Scheduled);
+        assert_eq!(task.status(1000), TaskStatus::Open);
+        assert_eq!(task.status(1999), TaskStatus::Open);
+        assert_eq!(task.status(2000), TaskStatus::Closed);
+    }
+
+    #[test]
+    fn test_status_grace_extends_close_only() {
+        let task = task(Some(1000), Some(2000));
+        assert_eq!(task.status_with_grace(999, 120), TaskStatus::Scheduled);
+        assert_eq!(task.status_with_grace(1000, 120), TaskStatus::Open);
+        assert_eq!(task.status_with_grace(1060, 120), TaskStatus::Open);
+        assert_eq!(task.status_with_grace(2119, 120), TaskStatus::Open);
+        assert_eq!(task.status_with_grace(2120, 120), TaskStatus::Closed);
+    }
+
+    #[test]
+    fn test_rule_matches() {
+        let q = question(QuizDifficulty::Hard, &["algebra", "proof"]);
+        assert!(rule(None, &[], &[]).matches(&q));
+        assert!(rule(Some(QuizDifficulty::Hard), &[], &[]).matches(&q));
+        assert!(!rule(Some(QuizDifficulty::Easy), &[], &[]).matches(&q));
+        assert!(rule(None, &["geometry", "proof"], &[]).matches(&q));
+        assert!(!rule(None, &["geometry"], &[]).matches(&q));
+        assert!(!rule(None, &["algebra"], &["proof"]).matches(&q));
+        assert!(rule(None, &[], &["geometry"]).matches(&question(QuizDifficulty::Medium, &[])));
+    }
+}
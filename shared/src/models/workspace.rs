@@ -37,6 +37,8 @@ pub struct Workspace {
         with = "indexmap_as_vec"
     )]
     pub checklist_tree: IndexMap<String, TreeNode>,
+    #[serde(default, skip_serializing_if = "GradebookWeights::is_empty")]
+    pub gradebook_weights: GradebookWeights,
     pub metadata: Metadata,
 }
 
@@ -0,0 +1,18 @@
+use crate::models::*;
+use ::serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct AnalyticsQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiz: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<i64>,
+    #[serde(default)]
+    pub period: AnalyticsPeriod,
+}
@@ -1,4 +1,5 @@
 use ::serde::{Deserialize, Serialize};
+use ::std::collections::HashMap;
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct ExchangeExportPayload {
@@ -9,4 +10,126 @@ pub struct ExchangeExportPayload {
 #[derive(Clone, Deserialize, Serialize)]
 pub struct ExchangeImportPayload {
     pub path: String,
-}
\ No newline at end of file
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ExchangeDownloadPayload {
+    pub entities: Vec<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ExportPackagePayload {
+    pub entities: Vec<String>,
+    pub path: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PackageTarget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiz_node: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub survey_node: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ImportPackagePayload {
+    pub path: String,
+    #[serde(flatten)]
+    pub target: PackageTarget,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportDiffStatus {
+    #[default]
+    Added,
+    Updated,
+    Older,
+    Unchanged,
+}
+
+impl ImportDiffStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportDiffStatus::Added => "added",
+            ImportDiffStatus::Updated => "updated",
+            ImportDiffStatus::Older => "older",
+            ImportDiffStatus::Unchanged => "unchanged",
+        }
+    }
+
+    pub fn default_choice(&self) -> ImportChoice {
+        match self {
+            ImportDiffStatus::Added | ImportDiffStatus::Updated => ImportChoice::TakeIncoming,
+            ImportDiffStatus::Older | ImportDiffStatus::Unchanged => ImportChoice::KeepLocal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportChoice {
+    KeepLocal,
+    TakeIncoming,
+    KeepBoth,
+}
+
+impl ImportChoice {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportChoice::KeepLocal => "keep-local",
+            ImportChoice::TakeIncoming => "take-incoming",
+            ImportChoice::KeepBoth => "keep-both",
+        }
+    }
+}
+
+impl From<String> for ImportChoice {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "take-incoming" => ImportChoice::TakeIncoming,
+            "keep-both" => ImportChoice::KeepBoth,
+            _ => ImportChoice::KeepLocal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ImportDiffItem {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    pub status: ImportDiffStatus,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ImportPreview {
+    pub id: String,
+    pub workspace: String,
+    pub exists: bool,
+    pub items: Vec<ImportDiffItem>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ImportResolution {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub kinds: HashMap<String, ImportChoice>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub items: HashMap<String, ImportChoice>,
+}
+
+impl ImportResolution {
+    pub fn choice(&self, item: &ImportDiffItem) -> ImportChoice {
+        let choice = self
+            .items
+            .get(&format!("{}/{}", item.kind, item.id))
+            .or_else(|| self.kinds.get(&item.kind))
+            .copied()
+            .unwrap_or_else(|| item.status.default_choice());
+        if item.status == ImportDiffStatus::Added && choice == ImportChoice::KeepBoth {
+            ImportChoice::TakeIncoming
+        } else {
+            choice
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use ::serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct GradebookQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub student: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+}
@@ -7,6 +7,9 @@ mod task;
 mod image;
 mod exchange;
 mod entity;
+mod storage;
+mod gradebook;
+mod analytics;
 
 pub use self::{
     workspace::*,   
@@ -18,4 +21,7 @@ pub use self::{
     image::*,
     exchange::*,
     entity::*,
+    storage::*,
+    gradebook::*,
+    analytics::*,
 };
\ No newline at end of file
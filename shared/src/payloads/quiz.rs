@@ -1,5 +1,6 @@
 use crate::models::*;
 use ::serde::{Deserialize, Serialize};
+use ::std::collections::{HashMap, HashSet};
 
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -15,6 +16,8 @@ pub struct UpdateQuizPayload {
     pub attempts: usize,
     pub duration: i64,
     pub grade: QuizGrade,
+    #[serde(default)]
+    pub languages: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub categories: Vec<QuizCategory>,
 }
@@ -32,4 +35,92 @@ pub struct UpdateQuizQuestionPayload {
     pub name: String,
     pub img: bool,
     pub answers: Vec<QuizAnswer>,
-}
\ No newline at end of file
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub translations: HashMap<String, String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub difficulty: QuizDifficulty,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open: Option<QuizOpenAnswer>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub explanation: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CreateQuizVariantsPayload {
+    pub count: usize,
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct CheckQuizAnswerPayload {
+    pub workspace: String,
+    pub task: String,
+    pub student: String,
+    #[serde(default)]
+    pub language: String,
+    pub question: QuizActivityQuestion,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct QuizReviewQuery {
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuizReviewItem {
+    pub student: String,
+    pub student_name: String,
+    pub question: String,
+    pub question_name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accepted_answers: Vec<String>,
+    pub result: QuizOpenResult,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ReviewQuizAnswerPayload {
+    pub student: String,
+    pub question: String,
+    pub accepted: bool,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct RegradeQuizPayload {
+    #[serde(default)]
+    pub refresh: bool,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub key: HashMap<String, HashSet<String>>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct AddQuizStudentsPayload {
+    pub students: Vec<String>,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct UpdateQuizStudentPayload {
+    #[serde(default)]
+    pub reset: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_attempts: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excused: Option<bool>,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct OverrideQuizGradePayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grade: Option<usize>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub results: HashMap<String, usize>,
+    #[serde(default)]
+    pub reason: String,
+}
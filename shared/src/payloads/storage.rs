@@ -0,0 +1,13 @@
+use crate::models::*;
+use ::serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct MigrateStoragePayload {
+    pub backend: StorageKind,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct MigrateStorageSummary {
+    pub workspaces: usize,
+    pub records: usize,
+}
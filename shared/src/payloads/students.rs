@@ -5,4 +5,29 @@ pub struct AddStudentPayload {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rank: Option<String>,
     pub name: String,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportStudentPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportStudentsPayload {
+    pub node: String,
+    #[serde(default)]
+    pub dry_run: bool,
+    pub students: Vec<ImportStudentPayload>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportStudentsSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub unresolved: usize,
+}
@@ -14,6 +14,8 @@ pub struct UpdateSurveyPayload {
     pub node: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub categories: Vec<SurveyCategory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_segment_size: Option<usize>,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -22,4 +24,18 @@ pub struct UpdateSurveyCategoryPayload {
     pub order: usize,
     pub answers: Vec<SurveyCategoryItem>,
     pub questions: Vec<SurveyCategoryItem>,
+    #[serde(default)]
+    pub kind: SurveyCategoryKind,
+    #[serde(default)]
+    pub min: Option<i64>,
+    #[serde(default)]
+    pub max: Option<i64>,
+    #[serde(default)]
+    pub conditions: Vec<SurveyCondition>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub max_selections: Option<usize>,
+    #[serde(default)]
+    pub segment: bool,
 }
\ No newline at end of file
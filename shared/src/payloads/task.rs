@@ -1,5 +1,6 @@
 use crate::models::*;
 use ::serde::{Deserialize, Serialize};
+use ::std::collections::HashMap;
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct CreateTaskPayload {
@@ -8,4 +9,30 @@ pub struct CreateTaskPayload {
     pub name: String,
     pub path: String,
     pub categories: Vec<TaskCategory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opens_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closes_at: Option<i64>,
+    #[serde(default)]
+    pub practice: bool,
+    #[serde(default)]
+    pub review: QuizReviewSettings,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct UpdateTaskSchedulePayload {
+    #[serde(default)]
+    pub opens_at: Option<i64>,
+    #[serde(default)]
+    pub closes_at: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct UpdateQuizDeadlinesPayload {
+    pub deadlines: HashMap<String, Option<i64>>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct UpdateQuizReviewPayload {
+    pub review: QuizReviewSettings,
 }
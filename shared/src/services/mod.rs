@@ -16,6 +16,8 @@ mod form_event;
 mod clipboard;
 #[cfg(feature = "desktop")]
 mod qr_generator;
+#[cfg(feature = "desktop")]
+mod roster;
 #[cfg(any(feature = "desktop", feature = "server"))]
 pub mod dispatcher;
 
@@ -26,6 +28,7 @@ pub use self::{
     log::*,
     clipboard::*,
     qr_generator::*,
+    roster::*,
 };
 #[cfg(any(feature = "desktop", feature = "server"))]
 pub use self::{
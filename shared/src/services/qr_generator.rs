@@ -14,4 +14,22 @@ impl QrGenerator {
         let base64_svg = STANDARD.encode(svg_content);
         format!("data:image/svg+xml;base64,{base64_svg}")
     }
+
+    pub fn symbol(text: impl Into<String>) -> String {
+        let matrix =
+            qrcode_generator::to_matrix(text.into(), qrcode_generator::QrCodeEcc::Medium)
+                .unwrap_or_default();
+        let size = matrix.len();
+        let mut path = String::new();
+        for (y, row) in matrix.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, dark)| **dark) {
+                path.push_str(&format!("M{x} {y}h1v1h-1z"));
+            }
+        }
+        let svg_content = format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {size} {size}" shape-rendering="crispEdges"><path fill="#000" d="{path}"/></svg>"##
+        );
+        let base64_svg = STANDARD.encode(svg_content);
+        format!("data:image/svg+xml;base64,{base64_svg}")
+    }
 }
@@ -0,0 +1,106 @@
+use crate::common::*;
+use ::calamine::{Reader, open_workbook_auto};
+use ::csv::{ReaderBuilder, WriterBuilder};
+use ::rust_xlsxwriter::Workbook;
+use ::std::path::Path;
+
+const CSV_DELIMITERS: [u8; 3] = [b';', b'\t', b','];
+const UTF8_BOM: &str = "\u{feff}";
+
+#[derive(Copy, Clone)]
+pub struct Roster;
+
+impl Roster {
+    pub fn read_rows(path: impl AsRef<Path>) -> Result<Vec<Vec<String>>> {
+        let path = path.as_ref();
+        let rows = if Self::is_workbook(path) {
+            Self::read_workbook(path)?
+        } else {
+            Self::read_csv(path)?
+        };
+
+        Ok(rows
+            .into_iter()
+            .filter(|row| row.iter().any(|cell| !cell.is_empty()))
+            .collect())
+    }
+
+    pub fn write_rows(path: impl AsRef<Path>, rows: &[Vec<String>]) -> Result<()> {
+        let path = path.as_ref();
+        if Self::is_workbook(path) {
+            Self::write_workbook(path, rows)
+        } else {
+            Self::write_csv(path, rows)
+        }
+    }
+
+    fn is_workbook(path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                matches!(
+                    ext.to_lowercase().as_str(),
+                    "xlsx" | "xlsm" | "xls" | "ods"
+                )
+            })
+    }
+
+    fn read_workbook(path: &Path) -> Result<Vec<Vec<String>>> {
+        let mut workbook = open_workbook_auto(path).map_err(|_| map_err("import-file-corrupted"))?;
+        let range = workbook
+            .worksheet_range_at(0)
+            .ok_or_else(|| map_err("import-file-corrupted"))?
+            .map_err(|_| map_err("import-file-corrupted"))?;
+
+        Ok(range
+            .rows()
+            .map(|row| row.iter().map(|cell| cell.to_string().trim().to_string()).collect())
+            .collect())
+    }
+
+    fn read_csv(path: &Path) -> Result<Vec<Vec<String>>> {
+        let data = std::fs::read(path).map_err(|_| map_err("import-file-corrupted"))?;
+        let text = String::from_utf8_lossy(&data);
+        let text = text.trim_start_matches(UTF8_BOM);
+
+        let first_line = text.lines().next().unwrap_or_default();
+        let delimiter = CSV_DELIMITERS
+            .into_iter()
+            .max_by_key(|d| first_line.bytes().filter(|b| b == d).count())
+            .unwrap_or(b',');
+
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+
+        Ok(rdr
+            .records()
+            .filter_map(|rec| rec.ok())
+            .map(|rec| rec.iter().map(|cell| cell.trim().to_string()).collect())
+            .collect())
+    }
+
+    fn write_workbook(path: &Path, rows: &[Vec<String>]) -> Result<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        for (row_idx, row) in rows.iter().enumerate() {
+            for (col_idx, cell) in row.iter().enumerate() {
+                worksheet
+                    .write_string(row_idx as u32, col_idx as u16, cell)
+                    .map_err(map_log_err)?;
+            }
+        }
+        workbook.save(path).map_err(map_log_err)
+    }
+
+    fn write_csv(path: &Path, rows: &[Vec<String>]) -> Result<()> {
+        let mut wtr = WriterBuilder::new().flexible(true).from_writer(UTF8_BOM.as_bytes().to_vec());
+        for row in rows {
+            wtr.write_record(row).map_err(map_log_err)?;
+        }
+        let data = wtr.into_inner().map_err(map_log_err)?;
+        std::fs::write(path, data).map_err(map_log_err)
+    }
+}
@@ -4,6 +4,10 @@ pub trait Cachable: Send + Sync {
     fn kind() -> EntityKind;
     fn get_id(&self) -> String;
     fn get_ws(&self) -> String;
+
+    fn get_node(&self) -> String {
+        String::new()
+    }
 }
 
 impl Cachable for Workspace {
@@ -32,6 +36,10 @@ impl Cachable for Quiz {
     fn get_ws(&self) -> String {
         self.workspace.clone()
     }
+
+    fn get_node(&self) -> String {
+        self.node.clone()
+    }
 }
 
 impl Cachable for Survey {
@@ -46,6 +54,10 @@ impl Cachable for Survey {
     fn get_ws(&self) -> String {
         self.workspace.clone()
     }
+
+    fn get_node(&self) -> String {
+        self.node.clone()
+    }
 }
 
 impl Cachable for QuizRecord {
@@ -60,6 +72,10 @@ impl Cachable for QuizRecord {
     fn get_ws(&self) -> String {
         self.workspace.clone()
     }
+
+    fn get_node(&self) -> String {
+        self.node.clone()
+    }
 }
 
 impl Cachable for SurveyRecord {
@@ -74,4 +90,8 @@ impl Cachable for SurveyRecord {
     fn get_ws(&self) -> String {
         self.workspace.clone()
     }
+
+    fn get_node(&self) -> String {
+        self.node.clone()
+    }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use ::chrono::NaiveDate;
+use ::chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
 
 const POSSIBLE_FORMATS: &[&str] = &[
     "%Y-%m-%d",
@@ -24,6 +24,39 @@ pub fn parse_date_with_unknown_format(date_str: impl AsRef<str>) -> Option<Naive
     None
 }
 
+const DATETIME_LOCAL_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+pub fn parse_datetime_local(value: impl AsRef<str>) -> Option<i64> {
+    let datetime = NaiveDateTime::parse_from_str(value.as_ref(), DATETIME_LOCAL_FORMAT).ok()?;
+    Local
+        .from_local_datetime(&datetime)
+        .earliest()
+        .map(|datetime| datetime.timestamp())
+}
+
+pub fn format_datetime_local(timestamp: i64) -> String {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|datetime| datetime.format(DATETIME_LOCAL_FORMAT).to_string())
+        .unwrap_or_default()
+}
+
+pub fn format_countdown(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
 pub fn extract_first_chars(input: &str) -> String {
     let mut words = input.split_whitespace();
     match (words.next(), words.next()) {
@@ -67,6 +100,27 @@ pub fn extract_form_checkboxes(payload: &[String]) -> Vec<bool> {
     result
 }
 
+pub fn split_tags(value: impl AsRef<str>) -> Vec<String> {
+    let mut tags = value
+        .as_ref()
+        .split(',')
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>();
+    tags.sort_unstable();
+    tags.dedup();
+    tags
+}
+
+pub fn normalize_name(value: impl AsRef<str>) -> String {
+    value
+        .as_ref()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 pub fn find_last_common<T: Clone + PartialEq>(arr1: &[T], arr2: &[T]) -> Option<T> {
     let min_len = arr1.len().min(arr2.len());
 
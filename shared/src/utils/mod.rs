@@ -5,6 +5,7 @@ mod tree_ext;
 mod misc;
 mod cachable;
 mod serializer;
+mod text_match;
 
 pub use self::{
     host::*,
@@ -12,6 +13,7 @@ pub use self::{
     misc::*,
     cachable::*,
     serializer::*,
+    text_match::*,
 };
 
 #[cfg(feature = "desktop")]
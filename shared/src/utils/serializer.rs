@@ -44,6 +44,10 @@ impl HasId<String> for SurveyRecordCategory {
     fn key(&self) -> String { self.id.clone() }
 }
 
+impl HasId<String> for SurveySegmentCategory {
+    fn key(&self) -> String { self.id.clone() }
+}
+
 impl HasId<String> for QuizActivityAnswer {
     fn key(&self) -> String { self.id.clone() }
 }
@@ -0,0 +1,111 @@
+const APOSTROPHES: [char; 6] = ['\'', '’', 'ʼ', '‘', '`', '´'];
+
+pub fn fold_text(value: impl AsRef<str>) -> String {
+    value
+        .as_ref()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !APOSTROPHES.contains(c))
+        .map(|c| match c {
+            'ё' => 'е',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn exact_similarity(a: impl AsRef<str>, b: impl AsRef<str>) -> usize {
+    if fold_text(a) == fold_text(b) { 100 } else { 0 }
+}
+
+pub fn fuzzy_similarity(a: impl AsRef<str>, b: impl AsRef<str>) -> usize {
+    let a = fold_text(a).chars().collect::<Vec<_>>();
+    let b = fold_text(b).chars().collect::<Vec<_>>();
+    let len = a.len().max(b.len());
+    if len == 0 {
+        return 100;
+    }
+
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    100 - (prev[b.len()] * 100).div_ceil(len)
+}
+
+pub fn contains_keywords(answer: impl AsRef<str>, keywords: &[String]) -> bool {
+    let answer = format!(" {} ", fold_text(answer));
+    keywords
+        .iter()
+        .map(fold_text)
+        .filter(|k| !k.is_empty())
+        .all(|k| answer.contains(&format!(" {k}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_apostrophes_and_yo() {
+        assert_eq!(fold_text("Пам’ять"), "память");
+        assert_eq!(fold_text("пам'ять"), fold_text("ПАМʼЯТЬ"));
+        assert_eq!(fold_text("Ёлка"), "елка");
+        assert_eq!(fold_text("  Київ,  столиця! "), "київ столиця");
+        assert_eq!(exact_similarity("м’ята", "м'ята"), 100);
+        assert_eq!(exact_similarity("м’ята", "мята з"), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_similarity_bounds() {
+        assert_eq!(fuzzy_similarity("", ""), 100);
+        assert_eq!(fuzzy_similarity("abc", ""), 0);
+        assert_eq!(fuzzy_similarity("", "abc"), 0);
+        assert_eq!(fuzzy_similarity("Пам’ять", "памʼять"), 100);
+        assert_eq!(fuzzy_similarity("математика", "матиматика"), 90);
+        assert_eq!(fuzzy_similarity("kitten", "sitting"), 57);
+        assert_eq!(fuzzy_similarity("abc", "xyz"), 0);
+        for (a, b) in [("a", "abcdef"), ("довгий текст", "д"), ("x", "y")] {
+            let similarity = fuzzy_similarity(a, b);
+            assert!(similarity <= 100);
+            assert_eq!(similarity, fuzzy_similarity(b, a));
+        }
+    }
+
+    #[test]
+    fn test_keyword_prefix_matching() {
+        let keywords = |items: &[&str]| items.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+        assert!(contains_keywords(
+            "Теорема Піфагора",
+            &keywords(&["піфагор"])
+        ));
+        assert!(contains_keywords(
+            "Теорема Піфагора",
+            &keywords(&["ТЕОРЕМ", "піф"])
+        ));
+        assert!(!contains_keywords(
+            "Теорема Піфагора",
+            &keywords(&["фагор"])
+        ));
+        assert!(!contains_keywords(
+            "Теорема Піфагора",
+            &keywords(&["теорема", "евклід"])
+        ));
+        assert!(contains_keywords(
+            "це прямий кутовий",
+            &keywords(&["прямий кут"])
+        ));
+        assert!(contains_keywords("будь-що", &keywords(&[])));
+        assert!(contains_keywords("будь-що", &keywords(&["!!"])));
+    }
+}